@@ -1,21 +1,51 @@
 """Generate code from serialized flatbuffers schema in bfbs format.
 
 Usage:
-  blockc [(--hash-table=<table>... --hash-mod=<mod>)] [-o <dir>] <bfbs>
+  blockc [(--hash-table=<table>... --hash-mod=<mod>)] [--rust-mod=<mod> [--closed-enums] [--no-builders]] [-o <dir>] <bfbs>
 
 Options:
   <bfbs>                    bfbs file which is generated using `flatc -b --schema <fbs>`
   -h --help                 Show this screen.
   -t --hash-table=<table>   Generate code to compute hashes for the specified tables.
   -m --hash-mod=<mod>       The name of the module which will contain the code to computing hashes.
+  -r --rust-mod=<mod>       Generate Rust types and readers for the schema into the module.
+  --closed-enums            Generate Rust enums, which readers panic on unknown values of.
+  --no-builders             Do not generate builders and owned types, which require `std`.
   -o <dir>                  Output directory.
 """
+import os
+import sys
+
 from docopt import docopt
 
+from blockbuffers import hash_gen, rust_gen
+
 
 def parse_arguments(argv=None):
     return docopt(__doc__, argv)
 
 
+def write_mod(out_dir, name, generate):
+    try:
+        code = generate()
+    except hash_gen.GenerateError as e:
+        sys.exit('blockc: {}'.format(e))
+
+    if not os.path.isdir(out_dir):
+        os.makedirs(out_dir)
+    with open(os.path.join(out_dir, name + '.rs'), 'w') as out:
+        out.write(code)
+
+
 def main():
-    print(parse_arguments())
+    args = parse_arguments()
+    out_dir = args['-o'] or '.'
+    schema = hash_gen.read_schema(args['<bfbs>'])
+
+    if args['--hash-table']:
+        write_mod(out_dir, args['--hash-mod'],
+                  lambda: hash_gen.generate_hash_mod(schema, args['--hash-table']))
+    if args['--rust-mod']:
+        write_mod(out_dir, args['--rust-mod'],
+                  lambda: rust_gen.generate_rust_mod(
+                      schema, not args['--closed-enums'], not args['--no-builders']))
@@ -0,0 +1,301 @@
+"""Generate Rust code which computes hashes of tables.
+
+The generated module embeds a static description of the whole schema, and contains a function for
+each requested table. The function computes the structural hash of the table using
+`blockbuffers::hash::structural_hash_table_with_options` and a `blockbuffers::hash::Hasher`, so
+the module does not require std. Two buffers holding the same logical value always produce the
+same digest, no matter how they are laid out. Fields which are not in the schema are rejected
+rather than skipped, so they cannot be used to make different buffers share a digest.
+"""
+import math
+import re
+
+from reflection.BaseType import BaseType
+from reflection.Schema import Schema
+
+SCALAR_TYPES = {
+    BaseType.Bool: 'Bool',
+    BaseType.Byte: 'Byte',
+    BaseType.UByte: 'UByte',
+    BaseType.Short: 'Short',
+    BaseType.UShort: 'UShort',
+    BaseType.Int: 'Int',
+    BaseType.UInt: 'UInt',
+    BaseType.Long: 'Long',
+    BaseType.ULong: 'ULong',
+    BaseType.Float: 'Float',
+    BaseType.Double: 'Double',
+}
+
+
+class GenerateError(Exception):
+    pass
+
+
+def snake_case(name):
+    """Converts a fully qualified name like `Bbs.CellOutput` to `bbs_cell_output`."""
+    name = re.sub(r'([A-Z]+)([A-Z][a-z])', r'\1_\2', name)
+    name = re.sub(r'([a-z0-9])([A-Z])', r'\1_\2', name)
+    return name.replace('.', '_').lower()
+
+
+def rust_float(value):
+    if math.isnan(value):
+        return '::std::f64::NAN'
+    if math.isinf(value):
+        return '::std::f64::INFINITY' if value > 0 else '::std::f64::NEG_INFINITY'
+    return repr(float(value))
+
+
+def rust_bool(value):
+    return 'true' if value else 'false'
+
+
+def decode(name):
+    return name.decode('utf-8') if isinstance(name, bytes) else name
+
+
+class SchemaIndex(object):
+    """Maps reflection objects and enums to the indices in the static description.
+
+    Tables and structs are numbered separately in the order they appear in the schema, and unions
+    are numbered among the enums which are unions.
+    """
+
+    def __init__(self, schema):
+        self.schema = schema
+        self.objects = [schema.Objects(i) for i in range(schema.ObjectsLength())]
+        self.enums = [schema.Enums(i) for i in range(schema.EnumsLength())]
+
+        self.tables = [o for o in self.objects if not o.IsStruct()]
+        self.structs = [o for o in self.objects if o.IsStruct()]
+        self.unions = [e for e in self.enums if e.IsUnion()]
+
+        self.object_indices = {}
+        table_count = 0
+        struct_count = 0
+        for i, o in enumerate(self.objects):
+            if o.IsStruct():
+                self.object_indices[i] = struct_count
+                struct_count += 1
+            else:
+                self.object_indices[i] = table_count
+                table_count += 1
+
+        self.union_indices = {}
+        for i, e in enumerate(self.enums):
+            if e.IsUnion():
+                self.union_indices[i] = len(self.union_indices)
+
+    def table_index(self, name):
+        for i, table in enumerate(self.tables):
+            if decode(table.Name()) == name:
+                return i
+        raise GenerateError('table {} not found'.format(name))
+
+    def nested_flatbuffer(self, table, field):
+        """Gets the table index of the attribute `nested_flatbuffer`, which name is relative to the
+        namespace of `table` or fully qualified."""
+        name = attribute(field, 'nested_flatbuffer')
+        if name is None:
+            return 'None'
+        namespace = decode(table.Name()).rpartition('.')[0]
+        for candidate in [namespace + '.' + name, name]:
+            for i, t in enumerate(self.tables):
+                if decode(t.Name()) == candidate:
+                    return 'Some({})'.format(i)
+        raise GenerateError('nested_flatbuffer table {} not found'.format(name))
+
+    def object_type(self, index):
+        kind = 'Struct' if self.objects[index].IsStruct() else 'Table'
+        return '{}({})'.format(kind, self.object_indices[index])
+
+    def element(self, ty):
+        element = ty.Element()
+        if element == BaseType.String:
+            return 'Element::String'
+        if element == BaseType.Obj:
+            return 'Element::' + self.object_type(ty.Index())
+        if element == BaseType.UType:
+            return 'Element::Scalar(ScalarType::UByte)'
+        return 'Element::Scalar(ScalarType::{})'.format(SCALAR_TYPES[element])
+
+    def type(self, ty):
+        base_type = ty.BaseType()
+        if base_type == BaseType.UType:
+            return 'Type::UnionType({})'.format(self.union_indices[ty.Index()])
+        if base_type == BaseType.Union:
+            return 'Type::Union({})'.format(self.union_indices[ty.Index()])
+        if base_type == BaseType.String:
+            return 'Type::String'
+        if base_type == BaseType.Obj:
+            return 'Type::' + self.object_type(ty.Index())
+        if base_type == BaseType.Vector:
+            return 'Type::Vector({})'.format(self.element(ty))
+        return 'Type::Scalar(ScalarType::{})'.format(SCALAR_TYPES[base_type])
+
+
+class Writer(object):
+    def __init__(self):
+        self.lines = []
+        self.indent = 0
+
+    def line(self, text=''):
+        self.lines.append('    ' * self.indent + text if text else '')
+
+    def open(self, text):
+        self.line(text)
+        self.indent += 1
+
+    def close(self, text):
+        self.indent -= 1
+        self.line(text)
+
+    def text(self):
+        return '\n'.join(self.lines) + '\n'
+
+
+def attribute(obj, key):
+    """Gets the value of the custom attribute `key`, or None if it is absent."""
+    for i in range(obj.AttributesLength()):
+        kv = obj.Attributes(i)
+        if decode(kv.Key()) == key:
+            return decode(kv.Value()) or ''
+    return None
+
+
+def version_attribute(field, key):
+    value = attribute(field, key)
+    return int(value) if value is not None and value.isdigit() else None
+
+
+def since_version(field):
+    return version_attribute(field, 'since_version') or 0
+
+
+def deprecated_version(field):
+    """Fields marked `deprecated` without `deprecated_version` are deprecated in all versions."""
+    version = version_attribute(field, 'deprecated_version')
+    if version is None and field.Deprecated():
+        version = 0
+    return 'None' if version is None else 'Some({})'.format(version)
+
+
+def fields_of(obj):
+    return [obj.Fields(i) for i in range(obj.FieldsLength())]
+
+
+def write_list(w, prefix, name, items, write_item):
+    """Writes a slice of struct literals in the style of rustfmt."""
+    if not items:
+        w.line('{}&[],'.format(prefix))
+    elif len(items) == 1:
+        w.open('{}&[{} {{'.format(prefix, name))
+        write_item(items[0])
+        w.close('}],')
+    else:
+        w.open('{}&['.format(prefix))
+        for item in items:
+            w.open('{} {{'.format(name))
+            write_item(item)
+            w.close('},')
+        w.close('],')
+
+
+def write_schema(w, index):
+    def write_field(table, field):
+        w.line('name: "{}",'.format(decode(field.Name())))
+        w.line('id: {},'.format(field.Id()))
+        w.line('ty: {},'.format(index.type(field.Type())))
+        w.line('required: {},'.format(rust_bool(field.Required())))
+        w.line('default_integer: {},'.format(field.DefaultInteger()))
+        w.line('default_real: {},'.format(rust_float(field.DefaultReal())))
+        w.line('since_version: {},'.format(since_version(field)))
+        w.line('deprecated_version: {},'.format(deprecated_version(field)))
+        w.line('nested_flatbuffer: {},'.format(index.nested_flatbuffer(table, field)))
+
+    def write_table(table):
+        w.line('name: "{}",'.format(decode(table.Name())))
+        fields = sorted(fields_of(table), key=lambda f: f.Id())
+        write_list(w, 'fields: ', 'Field', fields, lambda field: write_field(table, field))
+        keys = [f.Id() for f in fields if f.Key()]
+        w.line('key: {},'.format('Some({})'.format(keys[0]) if keys else 'None'))
+
+    def write_struct_field(field):
+        w.line('name: "{}",'.format(decode(field.Name())))
+        w.line('offset: {},'.format(field.Offset()))
+        w.line('ty: {},'.format(index.type(field.Type())))
+
+    def write_struct(st):
+        w.line('name: "{}",'.format(decode(st.Name())))
+        w.line('bytesize: {},'.format(st.Bytesize()))
+        w.line('minalign: {},'.format(st.Minalign()))
+        fields = sorted(fields_of(st), key=lambda f: f.Offset())
+        write_list(w, 'fields: ', 'StructField', fields, write_struct_field)
+
+    def write_variant(value):
+        w.line('name: "{}",'.format(decode(value.Name())))
+        w.line('tag: {},'.format(value.Value()))
+        w.line('table: {},'.format(index.object_indices[value.UnionType().Index()]))
+
+    def write_union(union):
+        w.line('name: "{}",'.format(decode(union.Name())))
+        values = [union.Values(i) for i in range(union.ValuesLength())]
+        variants = [v for v in values if v.Value() != 0 and v.UnionType() is not None]
+        write_list(w, 'variants: ', 'UnionVariant', variants, write_variant)
+
+    w.open('pub static SCHEMA: Schema<\'static> = Schema {')
+    write_list(w, 'tables: ', 'Table', index.tables, write_table)
+    write_list(w, 'structs: ', 'Struct', index.structs, write_struct)
+    write_list(w, 'unions: ', 'Union', index.unions, write_union)
+    w.close('};')
+
+
+def write_hash_fn(w, index, name):
+    table = index.table_index(name)
+    w.line('/// Computes the structural hash of the table `{}` at `pos`.'.format(name))
+    w.line('///')
+    w.line('/// The table is verified first, and fields which are not in the schema are rejected.')
+    w.open('pub fn {}<H: Hasher>(buf: &[u8], pos: TablePosition) -> Result<H::Output> {{'
+           .format(snake_case(name)))
+    w.open('let options = Options {')
+    w.line('deny_unknown_fields: true,')
+    w.line('..Options::default()')
+    w.close('};')
+    w.line('structural_hash_table_with_options::<H>(&SCHEMA, {}, buf, pos, options)'.format(table))
+    w.close('}')
+
+
+def generate_hash_mod(schema, tables):
+    """Generates the Rust module which computes hashes of `tables`.
+
+    `schema` is the reflection `Schema` read from the bfbs file, and `tables` are the fully
+    qualified names of the tables.
+    """
+    index = SchemaIndex(schema)
+
+    w = Writer()
+    w.line('// automatically generated by blockc, do not modify')
+    w.line()
+    w.line('use blockbuffers::error::Result;')
+    w.line('use blockbuffers::hash::{structural_hash_table_with_options, Hasher};')
+    w.line('use blockbuffers::position::TablePosition;')
+    w.line('#[allow(unused_imports)]')
+    w.line('use blockbuffers::schema::{')
+    w.line('    Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,')
+    w.line('};')
+    w.line('use blockbuffers::verify::Options;')
+    w.line()
+    w.line('/// Describes the schema which the hashed tables are defined in.')
+    write_schema(w, index)
+    for name in tables:
+        w.line()
+        write_hash_fn(w, index, name)
+
+    return w.text()
+
+
+def read_schema(path):
+    with open(path, 'rb') as bfbs_file:
+        buf = bytearray(bfbs_file.read())
+    return Schema.GetRootAsSchema(buf, 0)
@@ -0,0 +1,1023 @@
+"""Generate Rust types for a schema.
+
+Structs tagged with the attribute `fixed_array: "[ubyte:32]"` are mapped to array newtypes such as
+`pub struct U256(pub [u8; 32])`, which are copied from the buffer in native endian using
+`blockbuffers::position::StructPosition::read_array`.
+
+Enums are declared using `open_enum!`, so readers can keep values added by newer peers. Unless
+`open_enums` is true, they are mapped to Rust enums implementing `LE` via `impl_le_for_enum!`
+instead. The verifier cannot check enum values, so readers of such closed enums panic on values
+they do not define, even in verified buffers. Both macros require
+`#[macro_use] extern crate blockbuffers;` in the crate.
+
+Tables and structs are mapped to zero-copy readers such as `ExampleReader<'a>`, which wrap the
+buffer and a `TablePosition` or `StructPosition`. Readers do not check the buffer, which must be
+verified first, e.g., using `blockbuffers::verify::verify`.
+
+Unless `builders` is false, typed builders such as `ExampleBuilder` are generated as well, which
+encode through `blockbuffers::builder` and thus require the `std` feature. Missing `required`
+fields are reported by a panic when the table builder is consumed.
+
+Along with the builders, owned types such as `Example` are generated for code which prefers plain
+values to lifetimes. `decode` verifies the buffer before copying the values out of it, and
+`encode` writes the canonical buffer.
+"""
+import re
+
+from reflection.BaseType import BaseType
+from blockbuffers.hash_gen import (GenerateError, SchemaIndex, Writer, attribute, decode, fields_of,
+                                  rust_bool, rust_float, snake_case, write_schema)
+
+RUST_SCALAR_TYPES = {
+    BaseType.UType: 'u8',
+    BaseType.Bool: 'bool',
+    BaseType.Byte: 'i8',
+    BaseType.UByte: 'u8',
+    BaseType.Short: 'i16',
+    BaseType.UShort: 'u16',
+    BaseType.Int: 'i32',
+    BaseType.UInt: 'u32',
+    BaseType.Long: 'i64',
+    BaseType.ULong: 'u64',
+    BaseType.Float: 'f32',
+    BaseType.Double: 'f64',
+}
+
+FBS_SCALAR_TYPES = {
+    'bool': BaseType.Bool,
+    'byte': BaseType.Byte,
+    'int8': BaseType.Byte,
+    'ubyte': BaseType.UByte,
+    'uint8': BaseType.UByte,
+    'short': BaseType.Short,
+    'int16': BaseType.Short,
+    'ushort': BaseType.UShort,
+    'uint16': BaseType.UShort,
+    'int': BaseType.Int,
+    'int32': BaseType.Int,
+    'uint': BaseType.UInt,
+    'uint32': BaseType.UInt,
+    'long': BaseType.Long,
+    'int64': BaseType.Long,
+    'ulong': BaseType.ULong,
+    'uint64': BaseType.ULong,
+    'float': BaseType.Float,
+    'float32': BaseType.Float,
+    'double': BaseType.Double,
+    'float64': BaseType.Double,
+}
+
+SIZE_OF_UOFFSET = 4
+
+SCALAR_SIZES = {
+    BaseType.Bool: 1,
+    BaseType.Byte: 1,
+    BaseType.UByte: 1,
+    BaseType.Short: 2,
+    BaseType.UShort: 2,
+    BaseType.Int: 4,
+    BaseType.UInt: 4,
+    BaseType.Long: 8,
+    BaseType.ULong: 8,
+    BaseType.Float: 4,
+    BaseType.Double: 8,
+}
+
+
+RUST_KEYWORDS = set("""
+    abstract as become box break const continue crate do else enum extern false final fn for if impl
+    in let loop macro match mod move mut override priv pub ref return self static struct super trait
+    true type typeof unsafe unsized use virtual where while yield
+""".split())
+
+
+def type_name(name):
+    """Converts a fully qualified name like `example.U256` to the Rust type name `U256`."""
+    return name.split('.')[-1]
+
+
+def field_name(field):
+    """Gets the method name of the field, keywords are suffixed with `_`."""
+    name = decode(field.Name())
+    return name + '_' if name in RUST_KEYWORDS else name
+
+
+def fixed_array(obj):
+    """Parses the attribute `fixed_array` of a struct.
+
+    Returns the tuple `(base_type, len)`, or None if the struct is not tagged. The struct must
+    consist of exactly `len` fields of `base_type` without padding. Items cannot be bools or
+    enums, which are not `Plain` because the buffer may contain invalid values for them.
+    """
+    value = attribute(obj, 'fixed_array')
+    if value is None or not obj.IsStruct():
+        return None
+
+    name = decode(obj.Name())
+    match = re.match(r'^\[\s*(\w+)\s*:\s*(\d+)\s*\]$', value)
+    if match is None or match.group(1) not in FBS_SCALAR_TYPES:
+        raise GenerateError('{}: invalid fixed_array "{}"'.format(name, value))
+    base_type = FBS_SCALAR_TYPES[match.group(1)]
+    length = int(match.group(2))
+
+    fields = fields_of(obj)
+    if (len(fields) != length or obj.Bytesize() != length * SCALAR_SIZES[base_type]
+            or any(f.Type().BaseType() != base_type for f in fields)):
+        raise GenerateError('{}: fields do not match fixed_array "{}"'.format(name, value))
+    if base_type == BaseType.Bool or any(f.Type().Index() >= 0 for f in fields):
+        raise GenerateError('{}: fixed_array "{}" of bools or enums is not supported'.format(
+            name, value))
+
+    return base_type, length
+
+
+def write_fixed_array(w, obj, base_type, length):
+    name = type_name(decode(obj.Name()))
+    item = RUST_SCALAR_TYPES[base_type]
+
+    w.line('/// The struct `{}`, which is the fixed array `{}`.'.format(
+        decode(obj.Name()), attribute(obj, 'fixed_array')))
+    w.line('#[repr(transparent)]')
+    w.line('#[derive(Copy, Clone)]')
+    w.line('pub struct {}(pub [{}; {}]);'.format(name, item, length))
+    w.line()
+    w.open('unsafe impl FixedArray for {} {{'.format(name))
+    w.line('type Item = {};'.format(item))
+    w.line('const LEN: usize = {};'.format(length))
+    w.close('}')
+
+
+def enum_values(enum):
+    values = [enum.Values(i) for i in range(enum.ValuesLength())]
+    return [(decode(v.Name()), v.Value()) for v in values]
+
+
+def write_closed_enum(w, enum):
+    name = type_name(decode(enum.Name()))
+    repr_type = RUST_SCALAR_TYPES[enum.UnderlyingType().BaseType()]
+    values = enum_values(enum)
+
+    w.line('/// The enum `{}`.'.format(decode(enum.Name())))
+    w.line('#[repr({})]'.format(repr_type))
+    w.line('#[derive(Copy, Clone, Debug, PartialEq, Eq)]')
+    w.open('pub enum {} {{'.format(name))
+    for variant, value in values:
+        w.line('{} = {},'.format(variant, value))
+    w.close('}')
+    w.line()
+    w.line('// The impls generated by the macro are visible outside of the function.')
+    w.line('#[allow(dead_code)]')
+    w.open('fn impl_le_for_{}() {{'.format(snake_case(name)))
+    w.line('impl_le_for_enum!({}, {}, {});'.format(
+        name, repr_type, ', '.join(variant for variant, _ in values)))
+    w.close('}')
+
+
+def write_open_enum(w, enum):
+    name = type_name(decode(enum.Name()))
+    repr_type = RUST_SCALAR_TYPES[enum.UnderlyingType().BaseType()]
+
+    w.open('open_enum! {')
+    w.line('/// The enum `{}`, which keeps unknown values.'.format(decode(enum.Name())))
+    w.open('pub struct {}({}): {}Known {{'.format(name, repr_type, name))
+    for variant, value in enum_values(enum):
+        w.line('{} = {},'.format(variant, value))
+    w.close('}')
+    w.close('}')
+
+
+class RustTypes(object):
+    """Resolves the Rust types of the objects and enums in the schema."""
+
+    def __init__(self, schema, open_enums):
+        self.objects = [schema.Objects(i) for i in range(schema.ObjectsLength())]
+        self.enums = [schema.Enums(i) for i in range(schema.EnumsLength())]
+        self.open_enums = open_enums
+        self.fixed_arrays = dict((i, fixed_array(o)) for i, o in enumerate(self.objects))
+
+    def object_name(self, index):
+        return type_name(decode(self.objects[index].Name()))
+
+    def object_by_name(self, name, namespace):
+        """Finds the object index by name, which is relative to `namespace` or fully qualified."""
+        for candidate in [namespace + '.' + name, name]:
+            for i, obj in enumerate(self.objects):
+                if decode(obj.Name()) == candidate:
+                    return i
+        raise GenerateError('object {} not found'.format(name))
+
+    def is_fixed_array(self, index):
+        return self.fixed_arrays[index] is not None
+
+    def reader(self, index):
+        return '{}Reader<\'a>'.format(self.object_name(index))
+
+    def inline_struct(self, index):
+        """Gets the type returned for the struct, which lives as long as the buffer."""
+        if self.is_fixed_array(index):
+            return self.object_name(index)
+        return self.reader(index)
+
+    def scalar(self, base_type, index):
+        """Gets the type of the scalar, which is an enum if `index` is not -1."""
+        if index >= 0:
+            return type_name(decode(self.enums[index].Name()))
+        return RUST_SCALAR_TYPES[base_type]
+
+    def enum_default(self, index, value):
+        name = type_name(decode(self.enums[index].Name()))
+        if self.open_enums:
+            return '{}({})'.format(name, value)
+        for variant, variant_value in enum_values(self.enums[index]):
+            if variant_value == value:
+                return '{}::{}'.format(name, variant)
+        raise GenerateError('{}: no variant for the default value {}'.format(name, value))
+
+    def default(self, field):
+        ty = field.Type()
+        base_type = ty.BaseType()
+        if ty.Index() >= 0:
+            return self.enum_default(ty.Index(), field.DefaultInteger())
+        if base_type == BaseType.Bool:
+            return rust_bool(field.DefaultInteger())
+        if base_type in (BaseType.Float, BaseType.Double):
+            value = rust_float(field.DefaultReal())
+            return value.replace('f64', 'f32') if base_type == BaseType.Float else value
+        return str(field.DefaultInteger())
+
+    def element(self, ty):
+        """Gets the element type and stride of the vector, and whether it is a scalar vector."""
+        element = ty.Element()
+        if element == BaseType.String:
+            return '&\'a str', SIZE_OF_UOFFSET, False
+        if element == BaseType.Obj:
+            obj = self.objects[ty.Index()]
+            if obj.IsStruct():
+                return self.inline_struct(ty.Index()), obj.Bytesize(), False
+            return self.reader(ty.Index()), SIZE_OF_UOFFSET, False
+        return self.scalar(element, ty.Index()), SCALAR_SIZES.get(element, 1), True
+
+
+def write_field_position(w, field):
+    w.line('let pos = self.position.field_position(self.buf, {})?;'.format(4 + 2 * field.Id()))
+
+
+def write_table_field(w, types, obj, field):
+    name = field_name(field)
+    ty = field.Type()
+    base_type = ty.BaseType()
+    slot = 4 + 2 * field.Id()
+
+    if base_type in RUST_SCALAR_TYPES:
+        rust_type = types.scalar(base_type, ty.Index())
+        w.open('pub fn {}(&self) -> {} {{'.format(name, rust_type))
+        w.open('match self.position.field_position(self.buf, {}) {{'.format(slot))
+        w.line('Some(pos) => {}::from_le_slice(&self.buf[pos..]),'.format(rust_type))
+        w.line('None => {},'.format(types.default(field)))
+        w.close('}')
+        w.close('}')
+    elif base_type == BaseType.String:
+        w.open('pub fn {}(&self) -> Option<&\'a str> {{'.format(name))
+        write_field_position(w, field)
+        w.line('let string = StringPosition(seek_uoffset(self.buf, pos));')
+        w.line('Some(string.as_str(self.buf))')
+        w.close('}')
+    elif base_type == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+        w.open('pub fn {}(&self) -> Option<{}> {{'.format(name, types.inline_struct(ty.Index())))
+        write_field_position(w, field)
+        if types.is_fixed_array(ty.Index()):
+            w.line('Some(StructPosition(pos).read_array(self.buf))')
+        else:
+            w.line('Some({}::new(self.buf, StructPosition(pos)))'.format(
+                types.object_name(ty.Index()) + 'Reader'))
+        w.close('}')
+    elif base_type == BaseType.Obj:
+        w.open('pub fn {}(&self) -> Option<{}> {{'.format(name, types.reader(ty.Index())))
+        write_field_position(w, field)
+        w.line('let table = TablePosition(seek_uoffset(self.buf, pos));')
+        w.line('Some({}Reader::new(self.buf, table))'.format(types.object_name(ty.Index())))
+        w.close('}')
+    elif base_type == BaseType.Union:
+        write_union_field(w, types, obj, field)
+    elif base_type == BaseType.Vector:
+        element, stride, is_scalar = types.element(ty)
+        vector_type = 'LeSlice<\'a, {}>' if is_scalar else 'Elements<\'a, {}>'
+        w.open('pub fn {}(&self) -> Option<{}> {{'.format(name, vector_type.format(element)))
+        write_field_position(w, field)
+        w.line('let vector = VectorPosition(seek_uoffset(self.buf, pos));')
+        if is_scalar:
+            w.line('Some(vector.as_le_slice(self.buf))')
+        else:
+            w.line('Some(vector.elements(self.buf, {}))'.format(stride))
+        w.close('}')
+
+        nested = attribute(field, 'nested_flatbuffer')
+        if nested:
+            namespace = decode(obj.Name()).rpartition('.')[0]
+            nested_name = types.object_name(types.object_by_name(nested, namespace))
+            w.line()
+            w.open('pub fn {}_nested_flatbuffer(&self) -> Option<{}Reader<\'a>> {{'.format(
+                decode(field.Name()), nested_name))
+            write_field_position(w, field)
+            w.line('let nested = NestedBufferPosition(VectorPosition(seek_uoffset(self.buf, pos)));')
+            w.line('let (buf, root) = nested.root(self.buf);')
+            w.line('Some({}Reader::new(buf, root))'.format(nested_name))
+            w.close('}')
+
+
+def write_union_field(w, types, obj, field):
+    name = field_name(field)
+    union = types.enums[field.Type().Index()]
+    union_name = type_name(decode(union.Name()))
+
+    w.open('pub fn {}(&self) -> Option<TablePosition> {{'.format(name))
+    write_field_position(w, field)
+    w.line('Some(TablePosition(seek_uoffset(self.buf, pos)))')
+    w.close('}')
+
+    for i in range(union.ValuesLength()):
+        value = union.Values(i)
+        if value.Value() == 0 or value.UnionType() is None:
+            continue
+        variant = decode(value.Name())
+        reader = types.object_name(value.UnionType().Index()) + 'Reader'
+        w.line()
+        w.open('pub fn {}_as_{}(&self) -> Option<{}<\'a>> {{'.format(
+            decode(field.Name()), snake_case(variant), reader))
+        w.open('if self.{}_type() != {}::{} {{'.format(decode(field.Name()), union_name, variant))
+        w.line('return None;')
+        w.close('}')
+        w.line('let table = self.{}()?;'.format(name))
+        w.line('Some({}::new(self.buf, table))'.format(reader))
+        w.close('}')
+
+
+def write_struct_field(w, types, field):
+    name = field_name(field)
+    ty = field.Type()
+    if ty.BaseType() == BaseType.Obj:
+        if types.is_fixed_array(ty.Index()):
+            w.open('pub fn {}(&self) -> {} {{'.format(name, types.object_name(ty.Index())))
+            w.line('self.position.field_struct({}).read_array(self.buf)'.format(field.Offset()))
+        else:
+            reader = types.object_name(ty.Index()) + 'Reader'
+            w.open('pub fn {}(&self) -> {}<\'a> {{'.format(name, reader))
+            w.line('{}::new(self.buf, self.position.field_struct({}))'.format(
+                reader, field.Offset()))
+        w.close('}')
+    else:
+        w.open('pub fn {}(&self) -> {} {{'.format(name, types.scalar(ty.BaseType(), ty.Index())))
+        w.line('self.position.read(self.buf, {})'.format(field.Offset()))
+        w.close('}')
+
+
+def write_reader(w, types, obj):
+    name = type_name(decode(obj.Name()))
+    reader = name + 'Reader'
+    position = 'StructPosition' if obj.IsStruct() else 'TablePosition'
+
+    w.line('/// Reader of the {} `{}`.'.format(
+        'struct' if obj.IsStruct() else 'table', decode(obj.Name())))
+    w.line('#[derive(Copy, Clone, Debug)]')
+    w.open('pub struct {}<\'a> {{'.format(reader))
+    w.line('pub buf: &\'a [u8],')
+    w.line('pub position: {},'.format(position))
+    w.close('}')
+    w.line()
+    w.open('impl<\'a> {}<\'a> {{'.format(reader))
+    w.line('/// Reads the {} at `position` in the verified buffer.'.format(
+        'struct' if obj.IsStruct() else 'table'))
+    w.open('pub fn new(buf: &\'a [u8], position: {}) -> Self {{'.format(position))
+    w.line('{} {{ buf, position }}'.format(reader))
+    w.close('}')
+
+    if obj.IsStruct():
+        for field in sorted(fields_of(obj), key=lambda f: f.Offset()):
+            w.line()
+            write_struct_field(w, types, field)
+    else:
+        for field in sorted(fields_of(obj), key=lambda f: f.Id()):
+            if field.Deprecated():
+                continue
+            w.line()
+            write_table_field(w, types, obj, field)
+    w.close('}')
+    w.line()
+    w.open('impl<\'a> VectorElement<\'a> for {}<\'a> {{'.format(reader))
+    w.open('fn from_element(buf: &\'a [u8], pos: usize) -> Self {')
+    if obj.IsStruct():
+        w.line('{}::new(buf, StructPosition(pos))'.format(reader))
+    else:
+        w.line('{}::new(buf, TablePosition(seek_uoffset(buf, pos)))'.format(reader))
+    w.close('}')
+    w.close('}')
+
+
+def write_chain(w, prefix, receiver, calls, suffix):
+    """Writes the method chain in the form which rustfmt keeps."""
+    chain = receiver + ''.join('.' + call for call in calls)
+    if len(chain) <= 60:
+        w.line(prefix + chain + suffix)
+        return
+    w.line(prefix + receiver)
+    w.indent += 1
+    for call in calls[:-1]:
+        w.line('.' + call)
+    w.line('.' + calls[-1] + suffix)
+    w.indent -= 1
+
+
+def union_tag(types, union_name, variant):
+    if types.open_enums:
+        return '{}::{}.0'.format(union_name, variant)
+    return '{}::{} as u8'.format(union_name, variant)
+
+
+def write_table_builder_field(w, types, obj, field):
+    name = decode(field.Name())
+    ty = field.Type()
+    base_type = ty.BaseType()
+    field_id = field.Id()
+
+    if base_type == BaseType.UType:
+        # The type is set together with the value of the union.
+        return
+    w.line()
+    if base_type in RUST_SCALAR_TYPES:
+        w.open('pub fn set_{}(&mut self, value: {}) {{'.format(
+            name, types.scalar(base_type, ty.Index())))
+        w.line('self.table.add_scalar({}, value, {});'.format(field_id, types.default(field)))
+        w.close('}')
+    elif base_type == BaseType.String:
+        w.open('pub fn add_{}(&mut self, value: &str) {{'.format(name))
+        w.line('self.table.add_string({}, value);'.format(field_id))
+        w.close('}')
+    elif base_type == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+        struct_name = types.object_name(ty.Index())
+        if types.is_fixed_array(ty.Index()):
+            w.open('pub fn set_{}(&mut self, value: &{}) {{'.format(name, struct_name))
+            w.line('self.table.add_struct({}, &StructBuilder::from_array(value));'.format(
+                field_id))
+        else:
+            w.open('pub fn set_{}(&mut self, value: &{}Builder) {{'.format(name, struct_name))
+            w.line('self.table.add_struct({}, &value.st);'.format(field_id))
+        w.close('}')
+    elif base_type == BaseType.Obj:
+        w.open('pub fn set_{}(&mut self, value: {}Builder) {{'.format(
+            name, types.object_name(ty.Index())))
+        w.line('self.table.add_table({}, value.into_table());'.format(field_id))
+        w.close('}')
+    elif base_type == BaseType.Union:
+        union = types.enums[ty.Index()]
+        union_name = type_name(decode(union.Name()))
+        first = True
+        for i in range(union.ValuesLength()):
+            value = union.Values(i)
+            if value.Value() == 0 or value.UnionType() is None:
+                continue
+            variant = decode(value.Name())
+            if not first:
+                w.line()
+            first = False
+            w.open('pub fn set_{}_as_{}(&mut self, value: {}Builder) {{'.format(
+                name, snake_case(variant), types.object_name(value.UnionType().Index())))
+            w.line('let tag = {};'.format(union_tag(types, union_name, variant)))
+            w.line('self.table.add_union({}, tag, value.into_table());'.format(field_id))
+            w.close('}')
+    elif base_type == BaseType.Vector:
+        write_vector_builder_field(w, types, obj, field)
+
+
+def write_vector_builder_field(w, types, obj, field):
+    name = decode(field.Name())
+    ty = field.Type()
+    element = ty.Element()
+    field_id = field.Id()
+
+    if element == BaseType.String:
+        w.open('pub fn add_{}<S: AsRef<str>>(&mut self, items: &[S]) {{'.format(name))
+        w.line('self.table.add_string_vector({}, items);'.format(field_id))
+        w.close('}')
+    elif element == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+        struct_obj = types.objects[ty.Index()]
+        struct_name = types.object_name(ty.Index())
+        if types.is_fixed_array(ty.Index()):
+            w.open('pub fn add_{}(&mut self, items: &[{}]) {{'.format(name, struct_name))
+            write_chain(w, 'let items: Vec<StructBuilder> = ', 'items',
+                        ['iter()', 'map(StructBuilder::from_array)', 'collect()'], ';')
+        else:
+            w.open('pub fn add_{}(&mut self, items: &[{}Builder]) {{'.format(name, struct_name))
+            write_chain(w, 'let items: Vec<StructBuilder> = ', 'items',
+                        ['iter()', 'map(|item| item.st.clone())', 'collect()'], ';')
+        w.line('self.table.add_struct_vector({}, {}, &items);'.format(
+            field_id, struct_obj.Minalign()))
+        w.close('}')
+    elif element == BaseType.Obj:
+        builder = types.object_name(ty.Index()) + 'Builder'
+        w.open('pub fn add_{}(&mut self, items: Vec<{}>) {{'.format(name, builder))
+        write_chain(w, 'let items = ', 'items',
+                    ['into_iter()', 'map({}::into_table)'.format(builder), 'collect()'], ';')
+        w.line('self.table.add_table_vector({}, items);'.format(field_id))
+        w.close('}')
+    else:
+        w.open('pub fn add_{}(&mut self, items: &[{}]) {{'.format(
+            name, types.scalar(element, ty.Index())))
+        w.line('self.table.add_vector({}, items);'.format(field_id))
+        w.close('}')
+
+        nested = attribute(field, 'nested_flatbuffer')
+        if nested:
+            namespace = decode(obj.Name()).rpartition('.')[0]
+            nested_name = types.object_name(types.object_by_name(nested, namespace))
+            w.line()
+            w.open('pub fn set_{}_nested_flatbuffer(&mut self, value: {}Builder) {{'.format(
+                name, nested_name))
+            w.line('self.table.add_vector({}, &value.finish());'.format(field_id))
+            w.close('}')
+
+
+def write_table_builder(w, types, obj):
+    full_name = decode(obj.Name())
+    builder = type_name(full_name) + 'Builder'
+    fields = [f for f in sorted(fields_of(obj), key=lambda f: f.Id()) if not f.Deprecated()]
+
+    w.line('/// Builder of the table `{}`.'.format(full_name))
+    w.line('#[derive(Clone, Debug, Default, PartialEq, Eq)]')
+    w.open('pub struct {} {{'.format(builder))
+    w.line('table: TableBuilder,')
+    w.close('}')
+    w.line()
+    w.open('impl {} {{'.format(builder))
+    w.open('pub fn new() -> Self {')
+    w.line('{}::default()'.format(builder))
+    w.close('}')
+    for field in fields:
+        write_table_builder_field(w, types, obj, field)
+    w.line()
+    w.line('/// Gets the table, panics if a required field is missing.')
+    w.open('pub fn into_table(self) -> TableBuilder {')
+    for field in fields:
+        if field.Required():
+            args = ['self.table.has_field({})'.format(field.Id()),
+                    '"missing required field {}.{}"'.format(full_name, decode(field.Name()))]
+            if len(', '.join(args)) <= 60:
+                w.line('assert!({});'.format(', '.join(args)))
+            else:
+                w.open('assert!(')
+                w.line(args[0] + ',')
+                w.line(args[1])
+                w.close(');')
+    w.line('self.table')
+    w.close('}')
+    w.line()
+    w.line('/// Writes the buffer using this table as the root.')
+    w.open('pub fn finish(self) -> Vec<u8> {')
+    w.line('self.into_table().finish()')
+    w.close('}')
+    w.close('}')
+
+
+def write_struct_builder(w, types, obj):
+    builder = type_name(decode(obj.Name())) + 'Builder'
+
+    w.line('/// Builder of the struct `{}`.'.format(decode(obj.Name())))
+    w.line('#[derive(Clone, Debug, PartialEq, Eq)]')
+    w.open('pub struct {} {{'.format(builder))
+    w.line('st: StructBuilder,')
+    w.close('}')
+    w.line()
+    w.open('impl {} {{'.format(builder))
+    w.open('pub fn new() -> Self {')
+    w.open('{} {{'.format(builder))
+    w.line('st: StructBuilder::new({}, {}),'.format(obj.Bytesize(), obj.Minalign()))
+    w.close('}')
+    w.close('}')
+    for field in sorted(fields_of(obj), key=lambda f: f.Offset()):
+        name = decode(field.Name())
+        ty = field.Type()
+        w.line()
+        if ty.BaseType() == BaseType.Obj and types.is_fixed_array(ty.Index()):
+            w.open('pub fn set_{}(&mut self, value: &{}) {{'.format(
+                name, types.object_name(ty.Index())))
+            w.line('let value = StructBuilder::from_array(value);')
+            w.line('self.st.set_struct({}, &value);'.format(field.Offset()))
+        elif ty.BaseType() == BaseType.Obj:
+            w.open('pub fn set_{}(&mut self, value: &{}Builder) {{'.format(
+                name, types.object_name(ty.Index())))
+            w.line('self.st.set_struct({}, &value.st);'.format(field.Offset()))
+        else:
+            w.open('pub fn set_{}(&mut self, value: {}) {{'.format(
+                name, types.scalar(ty.BaseType(), ty.Index())))
+            w.line('self.st.set({}, value);'.format(field.Offset()))
+        w.close('}')
+    w.line()
+    w.line('/// Gets the inline bytes of the struct.')
+    w.open('pub fn into_struct(self) -> StructBuilder {')
+    w.line('self.st')
+    w.close('}')
+    w.close('}')
+    w.line()
+    w.open('impl Default for {} {{'.format(builder))
+    w.open('fn default() -> Self {')
+    w.line('{}::new()'.format(builder))
+    w.close('}')
+    w.close('}')
+
+
+def union_variants(types, union):
+    """Gets the variant names and the object indices of the union."""
+    values = [union.Values(i) for i in range(union.ValuesLength())]
+    return [(decode(v.Name()), v.UnionType().Index())
+            for v in values if v.Value() != 0 and v.UnionType() is not None]
+
+
+class OwnedField(object):
+    """Describes how a table field is stored in the owned type."""
+
+    def __init__(self, types, field):
+        self.field = field
+        self.name = field_name(field)
+        self.ty = field.Type()
+        self.base_type = self.ty.BaseType()
+        self.slot = 4 + 2 * field.Id()
+
+        if self.base_type in RUST_SCALAR_TYPES:
+            self.rust_type = types.scalar(self.base_type, self.ty.Index())
+            self.default = types.default(field)
+        else:
+            self.rust_type = 'Option<{}>'.format(self.value_type(types))
+            self.default = 'None'
+
+    def value_type(self, types):
+        ty = self.ty
+        if self.base_type == BaseType.String:
+            return 'String'
+        if self.base_type == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+            return types.object_name(ty.Index())
+        if self.base_type == BaseType.Obj:
+            return 'Box<{}>'.format(types.object_name(ty.Index()))
+        if self.base_type == BaseType.Union:
+            return type_name(decode(types.enums[ty.Index()].Name())) + 'Value'
+        element = ty.Element()
+        if element == BaseType.String:
+            return 'Vec<String>'
+        if element == BaseType.Obj:
+            return 'Vec<{}>'.format(types.object_name(ty.Index()))
+        return 'Vec<{}>'.format(types.scalar(element, ty.Index()))
+
+    def is_rust_default(self):
+        return self.default in ('0', 'false', '0.0', 'None')
+
+
+def write_fixed_array_impls(w, obj):
+    name = type_name(decode(obj.Name()))
+    w.open('impl PartialEq for {} {{'.format(name))
+    w.open('fn eq(&self, other: &Self) -> bool {')
+    w.line('self.0[..] == other.0[..]')
+    w.close('}')
+    w.close('}')
+    w.line()
+    w.open('impl ::std::fmt::Debug for {} {{'.format(name))
+    w.open('fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {')
+    w.line('self.0[..].fmt(f)')
+    w.close('}')
+    w.close('}')
+
+
+def write_union_value(w, types, union):
+    name = type_name(decode(union.Name())) + 'Value'
+    w.line('/// Owned value of the union `{}`.'.format(decode(union.Name())))
+    w.line('#[derive(Clone, Debug, PartialEq)]')
+    w.open('pub enum {} {{'.format(name))
+    for variant, index in union_variants(types, union):
+        w.line('{}(Box<{}>),'.format(variant, types.object_name(index)))
+    w.close('}')
+
+
+def write_owned_field_read(w, types, owned):
+    """Writes the statement which copies the field into the local variable of the same name."""
+    name = owned.name
+    ty = owned.ty
+    base_type = owned.base_type
+
+    if base_type in RUST_SCALAR_TYPES:
+        w.line('let {} = position.try_read_field(buf, {}, {})?;'.format(
+            name, owned.slot, owned.default))
+    elif base_type == BaseType.String:
+        write_chain(w, 'let {} = '.format(name), 'reader',
+                    ['{}()'.format(name), 'map(String::from)'], ';')
+    elif base_type == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+        if types.is_fixed_array(ty.Index()):
+            w.line('let {} = reader.{}();'.format(name, name))
+        else:
+            w.open('let {} = match reader.{}() {{'.format(name, name))
+            w.line('Some(value) => Some({}::from_reader(value)?),'.format(
+                types.object_name(ty.Index())))
+            w.line('None => None,')
+            w.close('};')
+    elif base_type == BaseType.Obj:
+        w.open('let {} = match reader.{}() {{'.format(name, name))
+        w.line('Some(value) => Some(Box::new({}::from_reader(value)?)),'.format(
+            types.object_name(ty.Index())))
+        w.line('None => None,')
+        w.close('};')
+    elif base_type == BaseType.Union:
+        union = types.enums[ty.Index()]
+        union_name = type_name(decode(union.Name()))
+        w.line('let mut {} = None;'.format(name))
+        for variant, index in union_variants(types, union):
+            w.open('if let Some(value) = reader.{}_as_{}() {{'.format(
+                decode(owned.field.Name()), snake_case(variant)))
+            w.line('let value = {}::from_reader(value)?;'.format(types.object_name(index)))
+            w.line('{} = Some({}Value::{}(Box::new(value)));'.format(name, union_name, variant))
+            w.close('}')
+    else:
+        element = ty.Element()
+        if element == BaseType.String:
+            write_chain(w, 'let {} = '.format(name), 'reader',
+                        ['{}()'.format(name), 'map(|items| items.map(String::from).collect())'], ';')
+        elif element == BaseType.Obj and types.is_fixed_array(ty.Index()):
+            write_chain(w, 'let {} = '.format(name), 'reader',
+                        ['{}()'.format(name), 'map(|items| items.collect())'], ';')
+        elif element == BaseType.Obj:
+            calls = ['map({}::from_reader)'.format(types.object_name(ty.Index())),
+                     'collect::<Result<Vec<_>>>()?']
+            chain = 'items.' + '.'.join(calls)
+            w.open('let {} = match reader.{}() {{'.format(name, name))
+            if len(chain) <= 60 and len('    ' * w.indent + chain) + 21 <= 100:
+                w.line('Some(items) => Some({}),'.format(chain))
+            else:
+                w.open('Some(items) => Some(')
+                write_chain(w, '', 'items', calls, ',')
+                w.close('),')
+            w.line('None => None,')
+            w.close('};')
+        else:
+            w.open('let {} = match position.try_field_position(buf, {})? {{'.format(
+                name, owned.slot))
+            w.line('Some(pos) => Some(VectorPosition(seek_uoffset(buf, pos)).try_to_vec(buf)?),')
+            w.line('None => None,')
+            w.close('};')
+
+
+def write_owned_field_build(w, types, owned):
+    """Writes the statements which set the field in `builder`."""
+    name = owned.name
+    field_name_raw = decode(owned.field.Name())
+    ty = owned.ty
+    base_type = owned.base_type
+
+    if base_type in RUST_SCALAR_TYPES:
+        w.line('builder.set_{}(self.{});'.format(field_name_raw, name))
+        return
+
+    if base_type == BaseType.Union:
+        union = types.enums[ty.Index()]
+        union_name = type_name(decode(union.Name()))
+        w.open('if let Some(ref value) = self.{} {{'.format(name))
+        w.open('match *value {')
+        for variant, _ in union_variants(types, union):
+            w.open('{}Value::{}(ref value) => {{'.format(union_name, variant))
+            w.line('builder.set_{}_as_{}(value.to_builder());'.format(
+                field_name_raw, snake_case(variant)))
+            w.close('}')
+        w.close('}')
+        w.close('}')
+        return
+
+    w.open('if let Some(ref value) = self.{} {{'.format(name))
+    if base_type == BaseType.String:
+        w.line('builder.add_{}(value);'.format(field_name_raw))
+    elif base_type == BaseType.Obj and types.is_fixed_array(ty.Index()):
+        w.line('builder.set_{}(value);'.format(field_name_raw))
+    elif base_type == BaseType.Obj and types.objects[ty.Index()].IsStruct():
+        w.line('builder.set_{}(&value.to_builder());'.format(field_name_raw))
+    elif base_type == BaseType.Obj:
+        w.line('builder.set_{}(value.to_builder());'.format(field_name_raw))
+    else:
+        element = ty.Element()
+        if element == BaseType.Obj and not types.is_fixed_array(ty.Index()):
+            item = types.object_name(ty.Index())
+            is_struct = types.objects[ty.Index()].IsStruct()
+            to_builder = '|item| item.to_builder()' if is_struct else item + '::to_builder'
+            write_chain(w, 'let items: Vec<_> = ', 'value',
+                        ['iter()', 'map({})'.format(to_builder), 'collect()'], ';')
+            w.line('builder.add_{}({}items);'.format(field_name_raw, '&' if is_struct else ''))
+        else:
+            w.line('builder.add_{}(value);'.format(field_name_raw))
+    w.close('}')
+
+
+def write_owned_table(w, types, index, obj):
+    full_name = decode(obj.Name())
+    name = type_name(full_name)
+    fields = [OwnedField(types, f) for f in sorted(fields_of(obj), key=lambda f: f.Id())
+              if not f.Deprecated() and f.Type().BaseType() != BaseType.UType]
+    derivable = all(f.is_rust_default() and f.ty.Index() < 0 for f in fields
+                    if f.base_type in RUST_SCALAR_TYPES)
+
+    w.line('/// Owned value of the table `{}`.'.format(full_name))
+    w.line('#[derive(Clone, Debug, {}PartialEq)]'.format('Default, ' if derivable else ''))
+    if fields:
+        w.open('pub struct {} {{'.format(name))
+        for f in fields:
+            w.line('pub {}: {},'.format(f.name, f.rust_type))
+        w.close('}')
+    else:
+        w.line('pub struct {} {{}}'.format(name))
+    w.line()
+    if not derivable:
+        w.open('impl Default for {} {{'.format(name))
+        w.open('fn default() -> Self {')
+        w.open('{} {{'.format(name))
+        for f in fields:
+            w.line('{}: {},'.format(f.name, f.default))
+        w.close('}')
+        w.close('}')
+        w.close('}')
+        w.line()
+    w.open('impl {} {{'.format(name))
+    w.line('/// Verifies the buffer which root table is `{}`, and copies the table out.'.format(
+        full_name))
+    w.open('pub fn decode(buf: &[u8]) -> Result<Self> {')
+    w.line('let root = verify(&SCHEMA, {}, buf)?;'.format(index.object_indices[
+        types.objects.index(obj)]))
+    w.line('{}::from_reader({}Reader::new(buf, root))'.format(name, name))
+    w.close('}')
+    w.line()
+    w.line('/// Copies the table out of the verified buffer.')
+    w.open('pub fn from_reader(reader: {}Reader) -> Result<Self> {{'.format(name))
+    if any(f.base_type in RUST_SCALAR_TYPES or f.base_type == BaseType.Vector and
+           f.ty.Element() not in (BaseType.String, BaseType.Obj) for f in fields):
+        w.line('let (buf, position) = (reader.buf, reader.position);')
+    for f in fields:
+        write_owned_field_read(w, types, f)
+    names = ', '.join(f.name for f in fields)
+    if fields and len(names) <= 18:
+        w.line('Ok({} {{ {} }})'.format(name, names))
+    elif fields:
+        w.open('Ok({} {{'.format(name))
+        for f in fields:
+            w.line('{},'.format(f.name))
+        w.close('})')
+    else:
+        w.line('Ok({} {{}})'.format(name))
+    w.close('}')
+    w.line()
+    w.line('/// Encodes the table as the root of a canonical buffer.')
+    w.open('pub fn encode(&self) -> Vec<u8> {')
+    w.line('self.to_builder().finish()')
+    w.close('}')
+    w.line()
+    w.line('/// Gets the builder which encodes the table.')
+    w.open('pub fn to_builder(&self) -> {}Builder {{'.format(name))
+    w.line('let mut builder = {}Builder::new();'.format(name))
+    for f in fields:
+        write_owned_field_build(w, types, f)
+    w.line('builder')
+    w.close('}')
+    w.close('}')
+
+
+def write_owned_struct(w, types, obj):
+    full_name = decode(obj.Name())
+    name = type_name(full_name)
+    fields = sorted(fields_of(obj), key=lambda f: f.Offset())
+
+    def field_type(field):
+        ty = field.Type()
+        if ty.BaseType() == BaseType.Obj:
+            return types.object_name(ty.Index())
+        return types.scalar(ty.BaseType(), ty.Index())
+
+    w.line('/// Owned value of the struct `{}`.'.format(full_name))
+    w.line('#[derive(Copy, Clone, Debug, PartialEq)]')
+    w.open('pub struct {} {{'.format(name))
+    for field in fields:
+        w.line('pub {}: {},'.format(field_name(field), field_type(field)))
+    w.close('}')
+    w.line()
+    w.open('impl {} {{'.format(name))
+    w.line('/// Copies the struct out of the verified buffer.')
+    w.open('pub fn from_reader(reader: {}Reader) -> Result<Self> {{'.format(name))
+    w.open('Ok({} {{'.format(name))
+    for field in fields:
+        ty = field.Type()
+        if ty.BaseType() == BaseType.Obj and types.is_fixed_array(ty.Index()):
+            value = 'reader.{}()'.format(field_name(field))
+        elif ty.BaseType() == BaseType.Obj:
+            value = '{}::from_reader(reader.{}())?'.format(
+                types.object_name(ty.Index()), field_name(field))
+        else:
+            value = 'reader.position.try_read(reader.buf, {})?'.format(field.Offset())
+        w.line('{}: {},'.format(field_name(field), value))
+    w.close('})')
+    w.close('}')
+    w.line()
+    w.line('/// Gets the builder which encodes the struct.')
+    w.open('pub fn to_builder(self) -> {}Builder {{'.format(name))
+    w.line('let mut builder = {}Builder::new();'.format(name))
+    for field in fields:
+        ty = field.Type()
+        if ty.BaseType() == BaseType.Obj and types.is_fixed_array(ty.Index()):
+            value = '&self.{}'.format(field_name(field))
+        elif ty.BaseType() == BaseType.Obj:
+            value = '&self.{}.to_builder()'.format(field_name(field))
+        else:
+            value = 'self.{}'.format(field_name(field))
+        w.line('builder.set_{}({});'.format(decode(field.Name()), value))
+    w.line('builder')
+    w.close('}')
+    w.close('}')
+
+
+def write_root_fn(w, obj):
+    name = type_name(decode(obj.Name()))
+    w.line('/// Reads the root table `{}` of the verified buffer.'.format(decode(obj.Name())))
+    w.open('pub fn get_root_as_{}(buf: &[u8]) -> {}Reader<\'_> {{'.format(snake_case(name), name))
+    w.line('{}Reader::new(buf, root_table(buf))'.format(name))
+    w.close('}')
+
+
+def generate_rust_mod(schema, open_enums=True, builders=True):
+    """Generates the Rust module which contains the types, readers and builders of `schema`.
+
+    `schema` is the reflection `Schema` read from the bfbs file. Enums are generated as closed enums
+    if `open_enums` is false, and builders are skipped if `builders` is false.
+    """
+    types = RustTypes(schema, open_enums)
+
+    w = Writer()
+    w.line('// automatically generated by blockc, do not modify')
+    w.line()
+    if builders:
+        w.line('#[allow(unused_imports)]')
+        w.line('use blockbuffers::builder::{StructBuilder, TableBuilder};')
+        w.line('#[allow(unused_imports)]')
+        w.line('use blockbuffers::error::Result;')
+    w.line('#[allow(unused_imports)]')
+    w.line('use blockbuffers::le::{LeSlice, LE};')
+    w.line('#[allow(unused_imports)]')
+    w.open('use blockbuffers::position::{')
+    w.line('Elements, FixedArray, NestedBufferPosition, StringPosition, StructPosition, TablePosition,')
+    w.line('VectorElement, VectorPosition,')
+    w.close('};')
+    w.line('#[allow(unused_imports)]')
+    w.line('use blockbuffers::root::root_table;')
+    if builders:
+        w.line('#[allow(unused_imports)]')
+        w.open('use blockbuffers::schema::{')
+        w.line('Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,')
+        w.close('};')
+    w.line('#[allow(unused_imports)]')
+    w.line('use blockbuffers::seek::seek_uoffset;')
+    if builders:
+        w.line('#[allow(unused_imports)]')
+        w.line('use blockbuffers::verify::verify;')
+
+    for i, obj in enumerate(types.objects):
+        if types.is_fixed_array(i):
+            w.line()
+            write_fixed_array(w, obj, *types.fixed_arrays[i])
+    for enum in types.enums:
+        w.line()
+        if open_enums:
+            write_open_enum(w, enum)
+        else:
+            write_closed_enum(w, enum)
+    for i, obj in enumerate(types.objects):
+        if types.is_fixed_array(i):
+            continue
+        w.line()
+        write_reader(w, types, obj)
+        if builders:
+            w.line()
+            if obj.IsStruct():
+                write_struct_builder(w, types, obj)
+            else:
+                write_table_builder(w, types, obj)
+
+    if builders:
+        index = SchemaIndex(schema)
+        w.line()
+        w.line('/// Describes the schema, which is used to verify the buffers to decode.')
+        write_schema(w, index)
+        for i, obj in enumerate(types.objects):
+            w.line()
+            if types.is_fixed_array(i):
+                write_fixed_array_impls(w, obj)
+            elif obj.IsStruct():
+                write_owned_struct(w, types, obj)
+            else:
+                write_owned_table(w, types, index, obj)
+        for enum in types.enums:
+            if enum.IsUnion():
+                w.line()
+                write_union_value(w, types, enum)
+
+    root = schema.RootTable()
+    if root is not None:
+        w.line()
+        write_root_fn(w, root)
+
+    return w.text()
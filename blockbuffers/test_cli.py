@@ -29,3 +29,19 @@ class TestCli(TestCase):
         args = cli.parse_arguments(['-o', 'out', 'test.bfbs'])
         self.assertEqual('out', args['-o'])
         self.assertEqual('test.bfbs', args['<bfbs>'])
+
+        args = cli.parse_arguments(['--rust-mod=example', 'test.bfbs'])
+        self.assertEqual('example', args['--rust-mod'])
+        self.assertEqual([], args['--hash-table'])
+        self.assertFalse(args['--closed-enums'])
+        self.assertFalse(args['--no-builders'])
+
+        args = cli.parse_arguments(['--rust-mod=example', '--closed-enums', 'test.bfbs'])
+        self.assertTrue(args['--closed-enums'])
+        with self.assertRaises(DocoptExit):
+            cli.parse_arguments(['--closed-enums', 'test.bfbs'])
+
+        args = cli.parse_arguments(['--rust-mod=example', '--no-builders', 'test.bfbs'])
+        self.assertTrue(args['--no-builders'])
+        with self.assertRaises(DocoptExit):
+            cli.parse_arguments(['--no-builders', 'test.bfbs'])
@@ -0,0 +1,28 @@
+import os
+from os import path
+from unittest import TestCase
+from blockbuffers import hash_gen
+
+COMMON_DIR = path.join(path.dirname(path.dirname(os.path.realpath(__file__))), 'tests', 'common')
+
+
+class TestHashGen(TestCase):
+    def test_snake_case(self):
+        self.assertEqual('bbs_transaction', hash_gen.snake_case('Bbs.Transaction'))
+        self.assertEqual('bbs_cell_output', hash_gen.snake_case('Bbs.CellOutput'))
+        self.assertEqual('example_u256', hash_gen.snake_case('example.U256'))
+        self.assertEqual('rpc_call', hash_gen.snake_case('RPCCall'))
+
+    def test_generate_hash_mod(self):
+        schema = hash_gen.read_schema(path.join(COMMON_DIR, 'example.bfbs'))
+        code = hash_gen.generate_hash_mod(schema, ['example.Example', 'example.Block'])
+
+        with open(path.join(COMMON_DIR, 'example_hash.rs')) as expected:
+            self.assertEqual(expected.read(), code)
+
+    def test_table_not_found(self):
+        schema = hash_gen.read_schema(path.join(COMMON_DIR, 'example.bfbs'))
+        with self.assertRaises(hash_gen.GenerateError):
+            hash_gen.generate_hash_mod(schema, ['example.Missing'])
+        with self.assertRaises(hash_gen.GenerateError):
+            hash_gen.generate_hash_mod(schema, ['example.Scalars'])
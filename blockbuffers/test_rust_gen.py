@@ -0,0 +1,568 @@
+from unittest import TestCase
+from blockbuffers import rust_gen
+from blockbuffers.hash_gen import GenerateError
+from reflection.BaseType import BaseType
+
+
+class FakeKeyValue(object):
+    def __init__(self, key, value):
+        self.key = key
+        self.value = value
+
+    def Key(self):
+        return self.key
+
+    def Value(self):
+        return self.value
+
+
+class FakeType(object):
+    def __init__(self, base_type, index=-1, element=BaseType.None_):
+        self.base_type = base_type
+        self.index = index
+        self.element = element
+
+    def BaseType(self):
+        return self.base_type
+
+    def Index(self):
+        return self.index
+
+    def Element(self):
+        return self.element
+
+
+class FakeField(object):
+    """Mimics the reflection `Field`."""
+
+    def __init__(self, base_type, name=b'', id=0, default=0, deprecated=False, required=False,
+                 key=False, **kwargs):
+        self.ty = FakeType(base_type, **kwargs)
+        self.name = name
+        self.id = id
+        self.default = default
+        self.deprecated = deprecated
+        self.required = required
+        self.key = key
+
+    def Type(self):
+        return self.ty
+
+    def Name(self):
+        return self.name
+
+    def Id(self):
+        return self.id
+
+    def DefaultInteger(self):
+        return self.default
+
+    def DefaultReal(self):
+        return float(self.default)
+
+    def Deprecated(self):
+        return self.deprecated
+
+    def Required(self):
+        return self.required
+
+    def Key(self):
+        return self.key
+
+    def AttributesLength(self):
+        return 0
+
+
+class FakeTable(object):
+    """Mimics the reflection `Object` of a table."""
+
+    def __init__(self, name, fields):
+        self.name = name
+        self.fields = fields
+
+    def Name(self):
+        return self.name
+
+    def IsStruct(self):
+        return False
+
+    def AttributesLength(self):
+        return 0
+
+    def FieldsLength(self):
+        return len(self.fields)
+
+    def Fields(self, i):
+        return self.fields[i]
+
+
+class FakeStruct(object):
+    """Mimics the reflection `Object` of a struct."""
+
+    def __init__(self, name, fixed_array, base_type, count, bytesize, **kwargs):
+        self.name = name
+        self.attributes = [FakeKeyValue(b'fixed_array', fixed_array)]
+        self.fields = [FakeField(base_type, **kwargs) for _ in range(count)]
+        self.bytesize = bytesize
+
+    def Name(self):
+        return self.name
+
+    def IsStruct(self):
+        return True
+
+    def Bytesize(self):
+        return self.bytesize
+
+    def Minalign(self):
+        return 1
+
+    def AttributesLength(self):
+        return len(self.attributes)
+
+    def Attributes(self, i):
+        return self.attributes[i]
+
+    def FieldsLength(self):
+        return len(self.fields)
+
+    def Fields(self, i):
+        return self.fields[i]
+
+
+class FakeEnumVal(object):
+    def __init__(self, name, value):
+        self.name = name
+        self.value = value
+
+    def Name(self):
+        return self.name
+
+    def Value(self):
+        return self.value
+
+    def UnionType(self):
+        return None
+
+
+class FakeEnum(object):
+    """Mimics the reflection `Enum`."""
+
+    def __init__(self, name, base_type, values, is_union=False):
+        self.name = name
+        self.underlying_type = FakeType(base_type)
+        self.values = [FakeEnumVal(n, v) for n, v in values]
+        self.is_union = is_union
+
+    def Name(self):
+        return self.name
+
+    def UnderlyingType(self):
+        return self.underlying_type
+
+    def ValuesLength(self):
+        return len(self.values)
+
+    def Values(self, i):
+        return self.values[i]
+
+    def IsUnion(self):
+        return self.is_union
+
+
+class FakeSchema(object):
+    def __init__(self, objects, enums=(), root=None):
+        self.objects = objects
+        self.enums = list(enums)
+        self.root = root
+
+    def RootTable(self):
+        return self.root
+
+    def ObjectsLength(self):
+        return len(self.objects)
+
+    def Objects(self, i):
+        return self.objects[i]
+
+    def EnumsLength(self):
+        return len(self.enums)
+
+    def Enums(self, i):
+        return self.enums[i]
+
+
+U256 = FakeStruct(b'example.U256', b'[ubyte:32]', BaseType.UByte, 32, 32)
+
+READERS_HEADER = '''// automatically generated by blockc, do not modify
+
+#[allow(unused_imports)]
+use blockbuffers::le::{LeSlice, LE};
+#[allow(unused_imports)]
+use blockbuffers::position::{
+    Elements, FixedArray, NestedBufferPosition, StringPosition, StructPosition, TablePosition,
+    VectorElement, VectorPosition,
+};
+#[allow(unused_imports)]
+use blockbuffers::root::root_table;
+#[allow(unused_imports)]
+use blockbuffers::seek::seek_uoffset;
+'''
+
+HEADER = '''// automatically generated by blockc, do not modify
+
+#[allow(unused_imports)]
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+#[allow(unused_imports)]
+use blockbuffers::error::Result;
+#[allow(unused_imports)]
+use blockbuffers::le::{LeSlice, LE};
+#[allow(unused_imports)]
+use blockbuffers::position::{
+    Elements, FixedArray, NestedBufferPosition, StringPosition, StructPosition, TablePosition,
+    VectorElement, VectorPosition,
+};
+#[allow(unused_imports)]
+use blockbuffers::root::root_table;
+#[allow(unused_imports)]
+use blockbuffers::schema::{
+    Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,
+};
+#[allow(unused_imports)]
+use blockbuffers::seek::seek_uoffset;
+#[allow(unused_imports)]
+use blockbuffers::verify::verify;
+'''
+
+U256_CODE = READERS_HEADER + '''
+/// The struct `example.U256`, which is the fixed array `[ubyte:32]`.
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct U256(pub [u8; 32]);
+
+unsafe impl FixedArray for U256 {
+    type Item = u8;
+    const LEN: usize = 32;
+}
+'''
+
+LANGUAGE = FakeEnum(b'example.Language', BaseType.Short, [(b'Rust', 0), (b'Python', 1)])
+CODE_OPTION = FakeEnum(b'example.CodeOption', BaseType.UType, [(b'NONE', 0), (b'Code', 1)], True)
+
+ENUMS_CODE = READERS_HEADER + '''
+/// The enum `example.Language`.
+#[repr(i16)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Language {
+    Rust = 0,
+    Python = 1,
+}
+
+// The impls generated by the macro are visible outside of the function.
+#[allow(dead_code)]
+fn impl_le_for_language() {
+    impl_le_for_enum!(Language, i16, Rust, Python);
+}
+
+/// The enum `example.CodeOption`.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CodeOption {
+    NONE = 0,
+    Code = 1,
+}
+
+// The impls generated by the macro are visible outside of the function.
+#[allow(dead_code)]
+fn impl_le_for_code_option() {
+    impl_le_for_enum!(CodeOption, u8, NONE, Code);
+}
+'''
+
+OPEN_ENUMS_CODE = READERS_HEADER + '''
+open_enum! {
+    /// The enum `example.Language`, which keeps unknown values.
+    pub struct Language(i16): LanguageKnown {
+        Rust = 0,
+        Python = 1,
+    }
+}
+
+open_enum! {
+    /// The enum `example.CodeOption`, which keeps unknown values.
+    pub struct CodeOption(u8): CodeOptionKnown {
+        NONE = 0,
+        Code = 1,
+    }
+}
+'''
+
+READERS_CODE = READERS_HEADER + '''
+open_enum! {
+    /// The enum `example.Language`, which keeps unknown values.
+    pub struct Language(i16): LanguageKnown {
+        Rust = 0,
+        Python = 1,
+    }
+}
+
+/// Reader of the table `example.Author`.
+#[derive(Copy, Clone, Debug)]
+pub struct AuthorReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> AuthorReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        AuthorReader { buf, position }
+    }
+
+    pub fn name(&self) -> Option<&'a str> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let string = StringPosition(seek_uoffset(self.buf, pos));
+        Some(string.as_str(self.buf))
+    }
+
+    pub fn birth(&self) -> u32 {
+        match self.position.field_position(self.buf, 6) {
+            Some(pos) => u32::from_le_slice(&self.buf[pos..]),
+            None => 1990,
+        }
+    }
+}
+
+impl<'a> VectorElement<'a> for AuthorReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        AuthorReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Reader of the table `example.Book`.
+#[derive(Copy, Clone, Debug)]
+pub struct BookReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> BookReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        BookReader { buf, position }
+    }
+
+    pub fn language(&self) -> Language {
+        match self.position.field_position(self.buf, 4) {
+            Some(pos) => Language::from_le_slice(&self.buf[pos..]),
+            None => Language(1),
+        }
+    }
+
+    pub fn authors(&self) -> Option<Elements<'a, AuthorReader<'a>>> {
+        let pos = self.position.field_position(self.buf, 6)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.elements(self.buf, 4))
+    }
+
+    pub fn pages(&self) -> Option<LeSlice<'a, u16>> {
+        let pos = self.position.field_position(self.buf, 8)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.as_le_slice(self.buf))
+    }
+}
+
+impl<'a> VectorElement<'a> for BookReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        BookReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Reads the root table `example.Book` of the verified buffer.
+pub fn get_root_as_book(buf: &[u8]) -> BookReader<'_> {
+    BookReader::new(buf, root_table(buf))
+}
+'''
+
+AUTHOR_BUILDER_CODE = '''/// Builder of the table `example.Author`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthorBuilder {
+    table: TableBuilder,
+}
+
+impl AuthorBuilder {
+    pub fn new() -> Self {
+        AuthorBuilder::default()
+    }
+
+    pub fn add_name(&mut self, value: &str) {
+        self.table.add_string(0, value);
+    }
+
+    pub fn set_birth(&mut self, value: u32) {
+        self.table.add_scalar(1, value, 1990);
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        assert!(
+            self.table.has_field(0),
+            "missing required field example.Author.name"
+        );
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+'''
+
+BOOK_BUILDER_CODE = '''/// Builder of the table `example.Book`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BookBuilder {
+    table: TableBuilder,
+}
+
+impl BookBuilder {
+    pub fn new() -> Self {
+        BookBuilder::default()
+    }
+
+    pub fn set_language(&mut self, value: Language) {
+        self.table.add_scalar(0, value, Language(1));
+    }
+
+    pub fn add_authors(&mut self, items: Vec<AuthorBuilder>) {
+        let items = items.into_iter().map(AuthorBuilder::into_table).collect();
+        self.table.add_table_vector(1, items);
+    }
+
+    pub fn add_pages(&mut self, items: &[u16]) {
+        self.table.add_vector(2, items);
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+'''
+
+AUTHOR_OWNED_CODE = '''/// Owned value of the table `example.Author`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Author {
+    pub name: Option<String>,
+    pub birth: u32,
+}
+
+impl Default for Author {
+    fn default() -> Self {
+        Author {
+            name: None,
+            birth: 1990,
+        }
+    }
+}
+
+impl Author {
+    /// Verifies the buffer which root table is `example.Author`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 0, buf)?;
+        Author::from_reader(AuthorReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: AuthorReader) -> Result<Self> {
+        let (buf, position) = (reader.buf, reader.position);
+        let name = reader.name().map(String::from);
+        let birth = position.try_read_field(buf, 6, 1990)?;
+        Ok(Author { name, birth })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> AuthorBuilder {
+        let mut builder = AuthorBuilder::new();
+        if let Some(ref value) = self.name {
+            builder.add_name(value);
+        }
+        builder.set_birth(self.birth);
+        builder
+    }
+}
+'''
+
+AUTHOR = FakeTable(b'example.Author', [
+    FakeField(BaseType.UInt, b'birth', id=1, default=1990),
+    FakeField(BaseType.String, b'name', id=0, required=True, key=True),
+    FakeField(BaseType.UShort, b'age', id=2, deprecated=True),
+])
+BOOK = FakeTable(b'example.Book', [
+    FakeField(BaseType.Short, b'language', id=0, default=1, index=0),
+    FakeField(BaseType.Vector, b'authors', id=1, index=0, element=BaseType.Obj),
+    FakeField(BaseType.Vector, b'pages', id=2, element=BaseType.UShort),
+])
+
+
+class TestRustGen(TestCase):
+    def test_type_name(self):
+        self.assertEqual('U256', rust_gen.type_name('example.U256'))
+        self.assertEqual('Transaction', rust_gen.type_name('Transaction'))
+
+    def test_fixed_array(self):
+        self.assertEqual((BaseType.UByte, 32), rust_gen.fixed_array(U256))
+        words = FakeStruct(b'Words', b'[uint32: 4]', BaseType.UInt, 4, 16)
+        self.assertEqual((BaseType.UInt, 4), rust_gen.fixed_array(words))
+
+    def test_invalid_fixed_array(self):
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[ubyte]', BaseType.UByte, 32, 32))
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[string:2]', BaseType.UByte, 2, 2))
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[ubyte:32]', BaseType.UByte, 31, 32))
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[ushort:2]', BaseType.UByte, 2, 4))
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[bool:2]', BaseType.Bool, 2, 2))
+        with self.assertRaises(GenerateError):
+            rust_gen.fixed_array(FakeStruct(b'A', b'[short:2]', BaseType.Short, 2, 4, index=0))
+
+    def test_generate_rust_mod(self):
+        self.assertEqual(U256_CODE, rust_gen.generate_rust_mod(FakeSchema([U256]), builders=False))
+
+    def test_generate_enums(self):
+        schema = FakeSchema([], [LANGUAGE, CODE_OPTION])
+        self.assertEqual(OPEN_ENUMS_CODE, rust_gen.generate_rust_mod(schema, builders=False))
+        self.assertEqual(ENUMS_CODE,
+                         rust_gen.generate_rust_mod(schema, open_enums=False, builders=False))
+
+    def test_generate_readers(self):
+        schema = FakeSchema([AUTHOR, BOOK], [LANGUAGE], root=BOOK)
+        self.assertEqual(READERS_CODE, rust_gen.generate_rust_mod(schema, builders=False))
+
+    def test_generate_builders(self):
+        schema = FakeSchema([AUTHOR, BOOK], [LANGUAGE], root=BOOK)
+        code = rust_gen.generate_rust_mod(schema)
+        self.assertTrue(code.startswith(HEADER))
+        self.assertIn(AUTHOR_BUILDER_CODE, code)
+        self.assertIn(BOOK_BUILDER_CODE, code)
+
+    def test_generate_owned_types(self):
+        schema = FakeSchema([AUTHOR, BOOK], [LANGUAGE], root=BOOK)
+        code = rust_gen.generate_rust_mod(schema)
+        self.assertIn('pub static SCHEMA: Schema<\'static> = Schema {', code)
+        self.assertIn('key: Some(0),', code)
+        self.assertIn(AUTHOR_OWNED_CODE, code)
+        self.assertNotIn('pub struct Author {', rust_gen.generate_rust_mod(schema, builders=False))
@@ -0,0 +1,599 @@
+//! Builds buffers in the canonical form.
+//!
+//! The builder collects the fields of a table by their ids, and writes the whole tree at once
+//! when [`finish`](struct.TableBuilder.html#method.finish) is called. The output only depends on
+//! the logical value, not the order the fields are added, and it follows the
+//! [canonical form](../verify/index.html#canonical-form) rules, so it always passes
+//! [`verify_canonical`](../verify/fn.verify_canonical.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//!
+//! // table Monster { hp: short = 100; name: string; }
+//! let mut a = TableBuilder::new();
+//! a.add_scalar(0, 80i16, 100);
+//! a.add_string(1, "orc");
+//!
+//! let mut b = TableBuilder::new();
+//! b.add_string(1, "orc");
+//! b.add_scalar(0, 80i16, 100);
+//!
+//! assert_eq!(a.finish(), b.finish());
+//! assert_eq!(
+//!     vec![
+//!         12u8, 0, 0, 0,           // root offset
+//!         8, 0, 12, 0, 4, 0, 8, 0, // vtable
+//!         8, 0, 0, 0,              // table
+//!         80, 0, 0, 0,             // hp and padding
+//!         4, 0, 0, 0,              // name
+//!         3, 0, 0, 0, b'o', b'r', b'c', 0,
+//!     ],
+//!     a.finish()
+//! );
+//! ```
+
+use error::Result;
+use le::LE;
+use position::{FixedArray, StringPosition, TablePosition, VectorPosition};
+use schema::{Element, Field, ScalarType, Schema, Type};
+use seek::{align_up, seek_uoffset};
+use std::collections::BTreeMap;
+use std::mem::{align_of, size_of};
+use std::slice;
+use types::{
+    Len, SOffset, UOffset, VOffset, SIZE_OF_LEN, SIZE_OF_SOFFSET, SIZE_OF_UOFFSET, SIZE_OF_VOFFSET,
+};
+use verify::verify_table;
+
+/// Builds the inline bytes of a struct.
+///
+/// The bytes not set are padding and stay zero.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::builder::StructBuilder;
+///
+/// // struct Vec3 { x: short; y: int; }
+/// let mut vec3 = StructBuilder::new(8, 4);
+/// vec3.set(0, 1i16);
+/// vec3.set(4, 2i32);
+///
+/// assert_eq!(&[1u8, 0, 0, 0, 2, 0, 0, 0], vec3.as_bytes());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructBuilder {
+    bytes: Vec<u8>,
+    minalign: usize,
+}
+
+impl StructBuilder {
+    /// Creates a struct which size is `bytesize` and alignment is `minalign`.
+    pub fn new(bytesize: usize, minalign: usize) -> StructBuilder {
+        StructBuilder {
+            bytes: vec![0; bytesize],
+            minalign,
+        }
+    }
+
+    /// Creates the struct from the fixed array, which items are in native endian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::StructBuilder;
+    ///
+    /// let st = StructBuilder::from_array(&[1u16, 2]);
+    /// assert_eq!(&[1u8, 0, 2, 0], st.as_bytes());
+    /// assert_eq!(2, st.minalign());
+    /// ```
+    pub fn from_array<A: FixedArray>(value: &A) -> StructBuilder {
+        let items = unsafe { slice::from_raw_parts(value as *const A as *const A::Item, A::LEN) };
+        let mut st = StructBuilder::new(size_of::<A>(), align_of::<A>());
+        for (i, item) in items.iter().enumerate() {
+            st.set(i * size_of::<A::Item>(), *item);
+        }
+        st
+    }
+
+    /// Sets the scalar field at `offset`.
+    pub fn set<T: LE>(&mut self, offset: usize, value: T) {
+        value.write_le_slice(&mut self.bytes[offset..offset + size_of::<T>()]);
+    }
+
+    /// Sets the nested struct field at `offset`.
+    pub fn set_struct(&mut self, offset: usize, value: &StructBuilder) {
+        self.bytes[offset..offset + value.bytes.len()].copy_from_slice(&value.bytes);
+    }
+
+    /// Gets the inline bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Gets the alignment.
+    pub fn minalign(&self) -> usize {
+        self.minalign
+    }
+}
+
+/// Builds a table.
+///
+/// Fields are specified by their ids. Adding a field twice overwrites the previous value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableBuilder {
+    fields: BTreeMap<u16, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    /// Scalars and structs.
+    Inline {
+        bytes: Vec<u8>,
+        align: usize,
+    },
+    String(Vec<u8>),
+    /// Vector of scalars or structs.
+    Vector {
+        len: usize,
+        align: usize,
+        bytes: Vec<u8>,
+    },
+    StringVector(Vec<Vec<u8>>),
+    TableVector(Vec<TableBuilder>),
+    Table(TableBuilder),
+}
+
+impl TableBuilder {
+    /// Creates an empty table.
+    pub fn new() -> TableBuilder {
+        TableBuilder::default()
+    }
+
+    /// Checks whether the field `id` has been added.
+    ///
+    /// Scalars equal to their defaults are omitted, so they are reported as absent.
+    pub fn has_field(&self, id: u16) -> bool {
+        self.fields.contains_key(&id)
+    }
+
+    /// Adds a scalar field. The field is omitted when `value` has the same bytes as `default`, so
+    /// floats are compared by their bits, e.g., `-0.0` is kept when the default is `0.0`.
+    pub fn add_scalar<T: LE>(&mut self, id: u16, value: T, default: T) {
+        let mut bytes = vec![0; size_of::<T>()];
+        value.write_le_slice(&mut bytes);
+        let mut default_bytes = vec![0; size_of::<T>()];
+        default.write_le_slice(&mut default_bytes);
+        if bytes == default_bytes {
+            self.fields.remove(&id);
+            return;
+        }
+        self.fields.insert(
+            id,
+            Value::Inline {
+                bytes,
+                align: size_of::<T>(),
+            },
+        );
+    }
+
+    /// Adds a struct field.
+    pub fn add_struct(&mut self, id: u16, value: &StructBuilder) {
+        self.fields.insert(
+            id,
+            Value::Inline {
+                bytes: value.bytes.clone(),
+                align: value.minalign,
+            },
+        );
+    }
+
+    /// Adds a string field.
+    pub fn add_string(&mut self, id: u16, value: &str) {
+        self.fields
+            .insert(id, Value::String(value.as_bytes().to_vec()));
+    }
+
+    /// Adds a table field.
+    pub fn add_table(&mut self, id: u16, value: TableBuilder) {
+        self.fields.insert(id, Value::Table(value));
+    }
+
+    /// Adds a union field.
+    ///
+    /// The union value is stored in the field `id`, and the type tag is stored in the field
+    /// `id - 1`, so `id` must not be 0. The tag must not be 0, which is reserved for `NONE`.
+    pub fn add_union(&mut self, id: u16, tag: u8, value: TableBuilder) {
+        assert!(id != 0, "union value cannot be the field 0");
+        assert!(tag != 0, "union type NONE has no value");
+        self.add_scalar(id - 1, tag, 0);
+        self.add_table(id, value);
+    }
+
+    /// Adds a vector of scalars.
+    pub fn add_vector<T: LE + Copy>(&mut self, id: u16, items: &[T]) {
+        let size = size_of::<T>();
+        let mut bytes = vec![0; items.len() * size];
+        for (item, chunk) in items.iter().zip(bytes.chunks_mut(size)) {
+            item.write_le_slice(chunk);
+        }
+        self.fields.insert(
+            id,
+            Value::Vector {
+                len: items.len(),
+                align: size,
+                bytes,
+            },
+        );
+    }
+
+    /// Adds a vector of structs which alignment is `minalign`.
+    pub fn add_struct_vector(&mut self, id: u16, minalign: usize, items: &[StructBuilder]) {
+        let mut bytes = Vec::new();
+        for item in items {
+            bytes.extend_from_slice(&item.bytes);
+        }
+        self.fields.insert(
+            id,
+            Value::Vector {
+                len: items.len(),
+                align: minalign,
+                bytes,
+            },
+        );
+    }
+
+    /// Adds a vector of strings.
+    pub fn add_string_vector<S: AsRef<str>>(&mut self, id: u16, items: &[S]) {
+        let items = items
+            .iter()
+            .map(|item| item.as_ref().as_bytes().to_vec())
+            .collect();
+        self.fields.insert(id, Value::StringVector(items));
+    }
+
+    /// Adds a vector of tables.
+    pub fn add_table_vector(&mut self, id: u16, items: Vec<TableBuilder>) {
+        self.fields.insert(id, Value::TableVector(items));
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(&self) -> Vec<u8> {
+        let mut writer = Writer {
+            buf: vec![0; SIZE_OF_UOFFSET],
+        };
+        let root = writer.table(self);
+        writer.patch_uoffset(0, root);
+        writer.buf
+    }
+}
+
+/// Copies the table at `pos` and everything it references into a builder.
+///
+/// Parameter `table` is the index into `schema.tables`. The table is verified first. Fields which
+/// are not in the schema are dropped, so the copy can be written in the canonical form no matter
+/// how the source buffer is laid out.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::builder::{copy_table, TableBuilder};
+/// use blockbuffers::position::TablePosition;
+/// use blockbuffers::schema::{Field, ScalarType, Schema, Table, Type};
+///
+/// // table Monster { hp: short = 100; }
+/// static MONSTER_FIELDS: &[Field] = &[Field {
+///     name: "hp",
+///     id: 0,
+///     ty: Type::Scalar(ScalarType::Short),
+///     required: false,
+///     default_integer: 100,
+///     default_real: 0.0,
+///     since_version: 0,
+///     deprecated_version: None,
+///     nested_flatbuffer: None,
+/// }];
+/// let schema = Schema {
+///     tables: &[Table { name: "Monster", fields: MONSTER_FIELDS, key: None }],
+///     structs: &[],
+///     unions: &[],
+/// };
+///
+/// // hp is stored explicitly though it equals the default.
+/// let buf = &[
+///     12u8, 0, 0, 0,        // root offset
+///     0, 0, 0, 0,           // padding
+///     6, 0, 8, 0, 4, 0,     // vtable
+///     0, 0,                 // padding
+///     8, 0, 0, 0, 100, 0,   // table
+///     0, 0,                 // padding
+/// ][..];
+///
+/// let copy = copy_table(&schema, 0, buf, TablePosition(16)).unwrap();
+/// assert_eq!(TableBuilder::new(), copy);
+/// ```
+pub fn copy_table(
+    schema: &Schema,
+    table: usize,
+    buf: &[u8],
+    pos: TablePosition,
+) -> Result<TableBuilder> {
+    verify_table(schema, table, buf, pos)?;
+    Ok(Copier { schema, buf }.table(table, pos))
+}
+
+/// Copies a verified buffer.
+struct Copier<'a> {
+    schema: &'a Schema<'a>,
+    buf: &'a [u8],
+}
+
+impl<'a> Copier<'a> {
+    fn table(&self, table: usize, pos: TablePosition) -> TableBuilder {
+        let mut builder = TableBuilder::new();
+        for field in self.schema.tables[table].fields {
+            if let Some(field_pos) = pos.field_position(self.buf, field.slot()) {
+                self.field(&mut builder, field, pos, field_pos);
+            }
+        }
+        builder
+    }
+
+    fn field(&self, builder: &mut TableBuilder, field: &Field, table: TablePosition, pos: usize) {
+        let id = field.id;
+        match field.ty {
+            Type::Scalar(scalar) => self.scalar(builder, field, scalar, pos),
+            Type::UnionType(_) => builder.add_scalar(id, u8::from_le_slice(&self.buf[pos..]), 0),
+            Type::Union(index) => {
+                let tag = table
+                    .field_position(self.buf, field.slot() - 2)
+                    .map_or(0, |tag_pos| u8::from_le_slice(&self.buf[tag_pos..]));
+                let variant = self.schema.unions[index]
+                    .variant(tag)
+                    .expect("verified union type");
+                builder.add_table(id, self.table(variant.table, self.child(pos)));
+            }
+            Type::Struct(index) => builder.add_struct(id, &self.struct_(index, pos)),
+            Type::String => builder.add_string(id, self.string(seek_uoffset(self.buf, pos))),
+            Type::Table(index) => builder.add_table(id, self.table(index, self.child(pos))),
+            Type::Vector(element) => self.vector(builder, id, element, seek_uoffset(self.buf, pos)),
+        }
+    }
+
+    fn child(&self, pos: usize) -> TablePosition {
+        TablePosition(seek_uoffset(self.buf, pos))
+    }
+
+    fn string(&self, pos: usize) -> &'a str {
+        StringPosition(pos).as_str(self.buf)
+    }
+
+    fn scalar(&self, builder: &mut TableBuilder, field: &Field, scalar: ScalarType, pos: usize) {
+        let (id, bytes) = (field.id, &self.buf[pos..]);
+        let default = field.default_integer;
+        match scalar {
+            ScalarType::Bool => builder.add_scalar(id, u8::from_le_slice(bytes) != 0, default != 0),
+            ScalarType::Byte => builder.add_scalar(id, i8::from_le_slice(bytes), default as i8),
+            ScalarType::UByte => builder.add_scalar(id, u8::from_le_slice(bytes), default as u8),
+            ScalarType::Short => builder.add_scalar(id, i16::from_le_slice(bytes), default as i16),
+            ScalarType::UShort => builder.add_scalar(id, u16::from_le_slice(bytes), default as u16),
+            ScalarType::Int => builder.add_scalar(id, i32::from_le_slice(bytes), default as i32),
+            ScalarType::UInt => builder.add_scalar(id, u32::from_le_slice(bytes), default as u32),
+            ScalarType::Long => builder.add_scalar(id, i64::from_le_slice(bytes), default),
+            ScalarType::ULong => builder.add_scalar(id, u64::from_le_slice(bytes), default as u64),
+            ScalarType::Float => {
+                builder.add_scalar(id, f32::from_le_slice(bytes), field.default_real as f32)
+            }
+            ScalarType::Double => {
+                builder.add_scalar(id, f64::from_le_slice(bytes), field.default_real)
+            }
+        }
+    }
+
+    fn struct_(&self, index: usize, pos: usize) -> StructBuilder {
+        let st = &self.schema.structs[index];
+        let mut builder = StructBuilder::new(st.bytesize, st.minalign);
+        for field in st.fields {
+            let field_pos = pos + field.offset;
+            match field.ty {
+                Type::Struct(index) => {
+                    builder.set_struct(field.offset, &self.struct_(index, field_pos))
+                }
+                Type::Scalar(ScalarType::Bool) => {
+                    builder.set(field.offset, self.buf[field_pos] != 0)
+                }
+                Type::Scalar(scalar) => {
+                    let size = scalar.size();
+                    builder.bytes[field.offset..field.offset + size]
+                        .copy_from_slice(&self.buf[field_pos..field_pos + size]);
+                }
+                _ => unreachable!("struct fields are scalars or structs"),
+            }
+        }
+        builder
+    }
+
+    fn vector(&self, builder: &mut TableBuilder, id: u16, element: Element, pos: usize) {
+        let vector = VectorPosition(pos);
+        let len = vector.len(self.buf);
+        let (size, _) = self.schema.element_size_and_align(element);
+        let start = pos + SIZE_OF_LEN;
+        let positions = (0..len).map(|i| start + i * size);
+
+        match element {
+            Element::Scalar(ScalarType::Bool) => {
+                let items: Vec<bool> = positions.map(|pos| self.buf[pos] != 0).collect();
+                builder.add_vector(id, &items);
+            }
+            Element::Scalar(scalar) => {
+                builder.fields.insert(
+                    id,
+                    Value::Vector {
+                        len,
+                        align: scalar.size(),
+                        bytes: self.buf[start..start + len * size].to_vec(),
+                    },
+                );
+            }
+            Element::Struct(index) => {
+                let items: Vec<StructBuilder> =
+                    positions.map(|pos| self.struct_(index, pos)).collect();
+                builder.add_struct_vector(id, self.schema.structs[index].minalign, &items);
+            }
+            Element::String => {
+                let items: Vec<&str> = positions
+                    .map(|pos| self.string(seek_uoffset(self.buf, pos)))
+                    .collect();
+                builder.add_string_vector(id, &items);
+            }
+            Element::Table(index) => {
+                let items = positions
+                    .map(|pos| self.table(index, self.child(pos)))
+                    .collect();
+                builder.add_table_vector(id, items);
+            }
+        }
+    }
+}
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Pads zeros until the end is aligned, and returns the end position.
+    fn pad(&mut self, align: usize) -> usize {
+        let pos = align_up(self.buf.len(), align);
+        self.buf.resize(pos, 0);
+        pos
+    }
+
+    fn write<T: LE>(&mut self, pos: usize, value: T) {
+        value.write_le_slice(&mut self.buf[pos..pos + size_of::<T>()]);
+    }
+
+    fn patch_uoffset(&mut self, pos: usize, target: usize) {
+        self.write(pos, (target - pos) as UOffset);
+    }
+
+    fn table(&mut self, table: &TableBuilder) -> usize {
+        let slots_count = table
+            .fields
+            .keys()
+            .next_back()
+            .map_or(0, |&id| id as usize + 1);
+        let vtable_len = 2 * SIZE_OF_VOFFSET + slots_count * SIZE_OF_VOFFSET;
+        let vtable_pos = self.pad(SIZE_OF_VOFFSET);
+        self.buf.resize(vtable_pos + vtable_len, 0);
+
+        let table_pos = self.pad(SIZE_OF_SOFFSET);
+        self.buf.resize(table_pos + SIZE_OF_SOFFSET, 0);
+        self.write(table_pos, (table_pos - vtable_pos) as SOffset);
+
+        let mut children = Vec::new();
+        for (&id, value) in &table.fields {
+            let pos = match *value {
+                Value::Inline { ref bytes, align } => {
+                    let pos = self.pad(align);
+                    self.buf.extend_from_slice(bytes);
+                    pos
+                }
+                _ => {
+                    let pos = self.pad(SIZE_OF_UOFFSET);
+                    self.buf.resize(pos + SIZE_OF_UOFFSET, 0);
+                    children.push((pos, value));
+                    pos
+                }
+            };
+            let offset = pos - table_pos;
+            assert!(
+                offset <= VOffset::max_value() as usize,
+                "table inline bytes are too large"
+            );
+            self.write(
+                vtable_pos + 2 * SIZE_OF_VOFFSET + id as usize * SIZE_OF_VOFFSET,
+                offset as VOffset,
+            );
+        }
+
+        let table_len = self.buf.len() - table_pos;
+        assert!(
+            table_len <= VOffset::max_value() as usize,
+            "table inline bytes are too large"
+        );
+        self.write(vtable_pos, vtable_len as VOffset);
+        self.write(vtable_pos + SIZE_OF_VOFFSET, table_len as VOffset);
+
+        for (pos, value) in children {
+            let target = self.child(value);
+            self.patch_uoffset(pos, target);
+        }
+
+        table_pos
+    }
+
+    fn child(&mut self, value: &Value) -> usize {
+        match *value {
+            Value::Inline { .. } => unreachable!("inline value is not a child"),
+            Value::String(ref bytes) => self.string(bytes),
+            Value::Table(ref table) => self.table(table),
+            Value::Vector {
+                len,
+                align,
+                ref bytes,
+            } => {
+                let pos = self.vector_len(len, align);
+                self.buf.extend_from_slice(bytes);
+                pos
+            }
+            Value::StringVector(ref items) => {
+                let pos = self.vector_offsets(items.len());
+                for (i, item) in items.iter().enumerate() {
+                    let target = self.string(item);
+                    self.patch_uoffset(pos + SIZE_OF_LEN + i * SIZE_OF_UOFFSET, target);
+                }
+                pos
+            }
+            Value::TableVector(ref items) => {
+                let pos = self.vector_offsets(items.len());
+                for (i, item) in items.iter().enumerate() {
+                    let target = self.table(item);
+                    self.patch_uoffset(pos + SIZE_OF_LEN + i * SIZE_OF_UOFFSET, target);
+                }
+                pos
+            }
+        }
+    }
+
+    /// Writes the vector length so the elements following it are aligned.
+    fn vector_len(&mut self, len: usize, align: usize) -> usize {
+        let align = if align > SIZE_OF_LEN {
+            align
+        } else {
+            SIZE_OF_LEN
+        };
+        let pos = align_up(self.buf.len() + SIZE_OF_LEN, align) - SIZE_OF_LEN;
+        self.buf.resize(pos + SIZE_OF_LEN, 0);
+        self.write(pos, len as Len);
+        pos
+    }
+
+    /// Writes a vector of offsets, which are patched later.
+    fn vector_offsets(&mut self, len: usize) -> usize {
+        let pos = self.vector_len(len, SIZE_OF_UOFFSET);
+        self.buf
+            .resize(pos + SIZE_OF_LEN + len * SIZE_OF_UOFFSET, 0);
+        pos
+    }
+
+    fn string(&mut self, bytes: &[u8]) -> usize {
+        let pos = self.pad(SIZE_OF_LEN);
+        self.buf.resize(pos + SIZE_OF_LEN, 0);
+        self.write(pos, bytes.len() as Len);
+        self.buf.extend_from_slice(bytes);
+        self.buf.push(0);
+        pos
+    }
+}
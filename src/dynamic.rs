@@ -0,0 +1,363 @@
+//! Accesses fields by name using a [reflection schema](../reflection/index.html), without the
+//! generated code.
+//!
+//! All the accessors are checked, so malformed buffers return errors instead of panicking.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::dynamic::{DynTable, DynValue, Scalar};
+//! use blockbuffers::reflection::Schema;
+//!
+//! let bfbs = include_bytes!("../tests/common/example.bfbs");
+//! let schema = Schema::from_slice(&bfbs[..]).unwrap();
+//!
+//! let mut builder = TableBuilder::new();
+//! builder.add_scalar(0, 2u32, 0);
+//! let buf = builder.finish();
+//!
+//! let example = DynTable::root(&schema, &buf).unwrap();
+//! match example.get(&schema, &buf, "version") {
+//!     Ok(Some(DynValue::Scalar(Scalar::UInt(version)))) => assert_eq!(2, version),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! assert!(example.get(&schema, &buf, "blocks").unwrap().is_none());
+//! ```
+
+use error::{Error, Result};
+use position::{StringPosition, TablePosition, VectorPosition};
+use reflection::{BaseType, Field, Object, Schema, Type};
+use seek::{check_bounds, try_read, try_seek_uoffset};
+use types::{SIZE_OF_LEN, SIZE_OF_UOFFSET};
+
+/// A scalar value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Scalar {
+    Bool(bool),
+    Byte(i8),
+    UByte(u8),
+    Short(i16),
+    UShort(u16),
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    Float(f32),
+    Double(f64),
+}
+
+impl Scalar {
+    /// Reads the scalar of `base_type` at `pos`.
+    ///
+    /// Union type tags are read as `UByte`. Fails if `base_type` is not a scalar.
+    pub fn read(buf: &[u8], pos: usize, base_type: BaseType) -> Result<Scalar> {
+        let scalar = match base_type {
+            BaseType::Bool => Scalar::Bool(try_read::<u8>(buf, pos)? != 0),
+            BaseType::Byte => Scalar::Byte(try_read(buf, pos)?),
+            BaseType::UByte | BaseType::UType => Scalar::UByte(try_read(buf, pos)?),
+            BaseType::Short => Scalar::Short(try_read(buf, pos)?),
+            BaseType::UShort => Scalar::UShort(try_read(buf, pos)?),
+            BaseType::Int => Scalar::Int(try_read(buf, pos)?),
+            BaseType::UInt => Scalar::UInt(try_read(buf, pos)?),
+            BaseType::Long => Scalar::Long(try_read(buf, pos)?),
+            BaseType::ULong => Scalar::ULong(try_read(buf, pos)?),
+            BaseType::Float => Scalar::Float(try_read(buf, pos)?),
+            BaseType::Double => Scalar::Double(try_read(buf, pos)?),
+            _ => return Err(Error::BadType { pos }),
+        };
+        Ok(scalar)
+    }
+
+    /// Gets the default value of the scalar field.
+    pub fn default_of(field: &Field) -> Scalar {
+        let integer = field.default_integer;
+        match field.ty.base_type {
+            BaseType::Bool => Scalar::Bool(integer != 0),
+            BaseType::Byte => Scalar::Byte(integer as i8),
+            BaseType::UByte | BaseType::UType => Scalar::UByte(integer as u8),
+            BaseType::Short => Scalar::Short(integer as i16),
+            BaseType::UShort => Scalar::UShort(integer as u16),
+            BaseType::Int => Scalar::Int(integer as i32),
+            BaseType::UInt => Scalar::UInt(integer as u32),
+            BaseType::Long => Scalar::Long(integer),
+            BaseType::ULong => Scalar::ULong(integer as u64),
+            BaseType::Float => Scalar::Float(field.default_real as f32),
+            BaseType::Double => Scalar::Double(field.default_real),
+            base_type => panic!("{:?} is not a scalar", base_type),
+        }
+    }
+}
+
+/// A value of a field, or an element in a vector.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DynValue<'a> {
+    Scalar(Scalar),
+    String(&'a str),
+    Vector(DynVector<'a>),
+    Table(DynTable<'a>),
+    Struct(DynStruct<'a>),
+    Union(DynUnion<'a>),
+}
+
+/// A table and its reflection object.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynTable<'a> {
+    pub object: &'a Object,
+    pub position: TablePosition,
+}
+
+/// A struct and its reflection object.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynStruct<'a> {
+    pub object: &'a Object,
+    /// The start position of the struct inline bytes.
+    pub position: usize,
+}
+
+/// A vector and its reflection type, which `base_type` is `Vector`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynVector<'a> {
+    pub ty: &'a Type,
+    pub position: VectorPosition,
+}
+
+/// A union value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DynUnion<'a> {
+    pub tag: u8,
+    /// The name of the union variant.
+    pub name: &'a str,
+    pub table: DynTable<'a>,
+}
+
+impl<'a> DynTable<'a> {
+    /// Gets the root table of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema has no `root_type`.
+    pub fn root(schema: &'a Schema, buf: &[u8]) -> Result<DynTable<'a>> {
+        let object = schema.root_table.as_ref().expect("schema has no root_type");
+        Ok(DynTable {
+            object,
+            position: TablePosition(try_seek_uoffset(buf, 0)?),
+        })
+    }
+
+    /// Gets the field by name.
+    ///
+    /// Absent scalar fields return the default value. Returns `None` if the field is absent or
+    /// the table has no such field.
+    pub fn get(
+        &self,
+        schema: &'a Schema,
+        buf: &'a [u8],
+        name: &str,
+    ) -> Result<Option<DynValue<'a>>> {
+        match self.object.field(name) {
+            Some(field) => self.get_field(schema, buf, field),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the field, which must be one of `self.object.fields`.
+    pub fn get_field(
+        &self,
+        schema: &'a Schema,
+        buf: &'a [u8],
+        field: &'a Field,
+    ) -> Result<Option<DynValue<'a>>> {
+        let pos = self
+            .position
+            .try_field_position(buf, field.offset as usize)?;
+        let ty = &field.ty;
+
+        if ty.base_type.scalar().is_some() || ty.base_type == BaseType::UType {
+            let scalar = match pos {
+                Some(pos) => Scalar::read(buf, pos, ty.base_type)?,
+                None => Scalar::default_of(field),
+            };
+            return Ok(Some(DynValue::Scalar(scalar)));
+        }
+
+        let pos = match pos {
+            Some(pos) => pos,
+            None => return Ok(None),
+        };
+        match ty.base_type {
+            BaseType::Union => {
+                let bad_type = Error::BadType {
+                    pos: self.position.0,
+                };
+                let tag_id = field.id.checked_sub(1).ok_or(bad_type)?;
+                let tag_field = self
+                    .object
+                    .fields
+                    .iter()
+                    .find(|f| f.id == tag_id && f.ty.base_type == BaseType::UType)
+                    .ok_or(bad_type)?;
+                let tag = match self.get_field(schema, buf, tag_field)? {
+                    Some(DynValue::Scalar(Scalar::UByte(tag))) => tag,
+                    _ => return Err(bad_type),
+                };
+                if tag == 0 {
+                    return Err(Error::BadUnion {
+                        pos: self.position.0,
+                    });
+                }
+                let variant = schema
+                    .enums
+                    .get(ty.index as usize)
+                    .ok_or(bad_type)?
+                    .value(i64::from(tag))
+                    .and_then(|v| v.union_type.as_ref().map(|ty| (&v.name, ty)));
+                match variant {
+                    Some((name, union_type)) => Ok(Some(DynValue::Union(DynUnion {
+                        tag,
+                        name,
+                        table: DynTable {
+                            object: object_of(schema, union_type, self.position.0)?,
+                            position: TablePosition(try_seek_uoffset(buf, pos)?),
+                        },
+                    }))),
+                    None => Err(Error::UnknownUnionType {
+                        pos: self.position.0,
+                        tag,
+                    }),
+                }
+            }
+            _ => offset_value(schema, buf, pos, ty.base_type, ty).map(Some),
+        }
+    }
+}
+
+impl<'a> DynStruct<'a> {
+    /// Gets the field by name. Returns `None` if the struct has no such field.
+    pub fn get(
+        &self,
+        schema: &'a Schema,
+        buf: &'a [u8],
+        name: &str,
+    ) -> Result<Option<DynValue<'a>>> {
+        match self.object.field(name) {
+            Some(field) => self.get_field(schema, buf, field).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the field, which must be one of `self.object.fields`.
+    pub fn get_field(
+        &self,
+        schema: &'a Schema,
+        buf: &'a [u8],
+        field: &'a Field,
+    ) -> Result<DynValue<'a>> {
+        let pos = self.position + field.offset as usize;
+        if field.ty.base_type == BaseType::Obj {
+            let object = object_of(schema, &field.ty, pos)?;
+            check_bounds(buf, pos, object.bytesize as usize)?;
+            Ok(DynValue::Struct(DynStruct {
+                object,
+                position: pos,
+            }))
+        } else {
+            Scalar::read(buf, pos, field.ty.base_type).map(DynValue::Scalar)
+        }
+    }
+}
+
+impl<'a> DynVector<'a> {
+    /// Gets the number of elements.
+    pub fn len(&self, buf: &[u8]) -> Result<usize> {
+        self.position.try_len(buf)
+    }
+
+    /// Returns true if the vector has no elements.
+    pub fn is_empty(&self, buf: &[u8]) -> Result<bool> {
+        self.len(buf).map(|len| len == 0)
+    }
+
+    /// Gets the element at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, schema: &'a Schema, buf: &'a [u8], index: usize) -> Result<DynValue<'a>> {
+        let len = self.len(buf)?;
+        assert!(index < len, "index {} out of bounds {}", index, len);
+
+        let element = self.ty.element;
+        let bad_type = Error::BadType {
+            pos: self.position.0,
+        };
+        let size = match element {
+            BaseType::String => SIZE_OF_UOFFSET,
+            BaseType::Obj => {
+                let object = object_of(schema, self.ty, self.position.0)?;
+                if object.is_struct {
+                    object.bytesize as usize
+                } else {
+                    SIZE_OF_UOFFSET
+                }
+            }
+            BaseType::UType => 1,
+            _ => element.scalar().ok_or(bad_type)?.size(),
+        };
+        let pos = self.position.0 + SIZE_OF_LEN + index * size;
+        check_bounds(buf, pos, size)?;
+
+        match element {
+            BaseType::String | BaseType::Obj => offset_value(schema, buf, pos, element, self.ty),
+            _ => Scalar::read(buf, pos, element).map(DynValue::Scalar),
+        }
+    }
+}
+
+/// Reads the value of `base_type` stored inline at `pos`, which is either an offset or a struct.
+///
+/// The `index` of `ty` refers to the object when `base_type` is `Obj`. Fails if `base_type` is
+/// not stored as an offset or a struct.
+fn offset_value<'a>(
+    schema: &'a Schema,
+    buf: &'a [u8],
+    pos: usize,
+    base_type: BaseType,
+    ty: &'a Type,
+) -> Result<DynValue<'a>> {
+    let value = match base_type {
+        BaseType::String => {
+            let target = try_seek_uoffset(buf, pos)?;
+            DynValue::String(StringPosition(target).try_as_str(buf)?)
+        }
+        BaseType::Vector => DynValue::Vector(DynVector {
+            ty,
+            position: VectorPosition(try_seek_uoffset(buf, pos)?),
+        }),
+        BaseType::Obj => {
+            let object = object_of(schema, ty, pos)?;
+            if object.is_struct {
+                check_bounds(buf, pos, object.bytesize as usize)?;
+                DynValue::Struct(DynStruct {
+                    object,
+                    position: pos,
+                })
+            } else {
+                DynValue::Table(DynTable {
+                    object,
+                    position: TablePosition(try_seek_uoffset(buf, pos)?),
+                })
+            }
+        }
+        _ => return Err(Error::BadType { pos }),
+    };
+    Ok(value)
+}
+
+/// Gets the object which `ty.index` refers to, for the value at `pos`.
+fn object_of<'a>(schema: &'a Schema, ty: &Type, pos: usize) -> Result<&'a Object> {
+    schema
+        .objects
+        .get(ty.index as usize)
+        .ok_or(Error::BadType { pos })
+}
@@ -0,0 +1,155 @@
+use core::fmt;
+use core::result;
+#[cfg(feature = "std")]
+use std::error;
+
+/// Errors returned by the checked accessors when the buffer is malformed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Reading `need` bytes starting at `pos` exceeds the buffer length `buf_len`.
+    OutOfBounds {
+        pos: usize,
+        need: usize,
+        buf_len: usize,
+    },
+    /// The offset at `pos` points before the start of the buffer.
+    OffsetBeforeStart { pos: usize },
+    /// The position `pos` is not aligned to `align` bytes.
+    Misaligned { pos: usize, align: usize },
+    /// The string at `pos` is not valid UTF-8.
+    InvalidUtf8 { pos: usize },
+    /// The vtable at `pos` is malformed.
+    BadVTable { pos: usize },
+    /// The string at `pos` is not terminated by 0.
+    MissingNul { pos: usize },
+    /// The table at `pos` does not have the required field `id`.
+    RequiredFieldMissing { pos: usize, id: u16 },
+    /// The union value and its type tag in the table at `pos` do not match, e.g., the tag is
+    /// `NONE` but the value is present.
+    BadUnion { pos: usize },
+    /// The union type tag `tag` in the table at `pos` is unknown.
+    UnknownUnionType { pos: usize, tag: u8 },
+    /// Tables are nested too deep at `pos`.
+    DepthLimitExceeded { pos: usize },
+    /// Too many tables are found when reaching the table at `pos`.
+    TableLimitExceeded { pos: usize },
+    /// The buffer is valid but the bytes at `pos` are not in the canonical form.
+    NonCanonical { pos: usize },
+    /// The bool at `pos` is neither 0 nor 1.
+    InvalidBool { pos: usize },
+    /// The value at `pos` is not defined in the enum.
+    UnknownEnumValue { pos: usize, value: i64 },
+    /// The JSON text is malformed or does not match the schema at byte `pos`.
+    InvalidJson { pos: usize },
+    /// The table at `pos` has the field `id` which is not valid in the protocol version
+    /// `version`.
+    FieldNotInVersion { pos: usize, id: u16, version: u32 },
+    /// The file identifier at `pos` does not match the expected one.
+    IdentifierMismatch { pos: usize },
+    /// The key of the table at `pos` is not greater than the key of the previous table in a
+    /// vector sorted by the key.
+    KeyNotSorted { pos: usize },
+    /// The table at `pos` has the field `id` which is not in the schema.
+    UnknownField { pos: usize, id: u16 },
+    /// The field `id` of the object `object`, an index into `reflection::Schema::objects`, has a
+    /// type which refers to a missing object or union, or which is not allowed in the object.
+    BadFieldType { object: usize, id: u16 },
+    /// The variant `value` of the union `index`, an index into `reflection::Schema::enums`, is
+    /// not a table, or the value does not fit in the type tag.
+    BadUnionVariant { index: usize, value: i64 },
+    /// The value at `pos` has a type in the reflection schema which refers to a missing object
+    /// or union, or which cannot be stored there.
+    BadType { pos: usize },
+}
+
+/// The result type returned by the checked accessors.
+pub type Result<T> = result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::OutOfBounds { pos, need, buf_len } => write!(
+                f,
+                "out of bounds: need {} bytes at {} but buffer length is {}",
+                need, pos, buf_len
+            ),
+            Error::OffsetBeforeStart { pos } => {
+                write!(f, "offset at {} points before the buffer", pos)
+            }
+            Error::Misaligned { pos, align } => {
+                write!(
+                    f,
+                    "misaligned: position {} is not aligned to {}",
+                    pos, align
+                )
+            }
+            Error::InvalidUtf8 { pos } => write!(f, "invalid utf-8 in string at {}", pos),
+            Error::BadVTable { pos } => write!(f, "bad vtable at {}", pos),
+            Error::MissingNul { pos } => write!(f, "string at {} is not terminated by 0", pos),
+            Error::RequiredFieldMissing { pos, id } => {
+                write!(f, "required field {} is missing in table at {}", id, pos)
+            }
+            Error::BadUnion { pos } => {
+                write!(f, "union value and type do not match in table at {}", pos)
+            }
+            Error::UnknownUnionType { pos, tag } => {
+                write!(f, "unknown union type {} in table at {}", tag, pos)
+            }
+            Error::DepthLimitExceeded { pos } => write!(f, "depth limit exceeded at {}", pos),
+            Error::TableLimitExceeded { pos } => write!(f, "table limit exceeded at {}", pos),
+            Error::NonCanonical { pos } => write!(f, "non-canonical encoding at {}", pos),
+            Error::InvalidBool { pos } => write!(f, "invalid bool at {}", pos),
+            Error::UnknownEnumValue { pos, value } => {
+                write!(f, "unknown enum value {} at {}", value, pos)
+            }
+            Error::InvalidJson { pos } => write!(f, "invalid json at {}", pos),
+            Error::FieldNotInVersion { pos, id, version } => write!(
+                f,
+                "field {} in table at {} is not valid in version {}",
+                id, pos, version
+            ),
+            Error::IdentifierMismatch { pos } => write!(f, "file identifier mismatch at {}", pos),
+            Error::KeyNotSorted { pos } => write!(f, "key of table at {} is not sorted", pos),
+            Error::UnknownField { pos, id } => {
+                write!(f, "unknown field {} in table at {}", id, pos)
+            }
+            Error::BadFieldType { object, id } => {
+                write!(f, "bad type of field {} in object {}", id, object)
+            }
+            Error::BadUnionVariant { index, value } => {
+                write!(f, "bad variant {} of union {}", value, index)
+            }
+            Error::BadType { pos } => write!(f, "bad type in schema for value at {}", pos),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::OutOfBounds { .. } => "out of bounds",
+            Error::OffsetBeforeStart { .. } => "offset points before the buffer",
+            Error::Misaligned { .. } => "misaligned",
+            Error::InvalidUtf8 { .. } => "invalid utf-8",
+            Error::BadVTable { .. } => "bad vtable",
+            Error::MissingNul { .. } => "string is not terminated by 0",
+            Error::RequiredFieldMissing { .. } => "required field is missing",
+            Error::BadUnion { .. } => "union value and type do not match",
+            Error::UnknownUnionType { .. } => "unknown union type",
+            Error::DepthLimitExceeded { .. } => "depth limit exceeded",
+            Error::TableLimitExceeded { .. } => "table limit exceeded",
+            Error::NonCanonical { .. } => "non-canonical encoding",
+            Error::InvalidBool { .. } => "invalid bool",
+            Error::UnknownEnumValue { .. } => "unknown enum value",
+            Error::InvalidJson { .. } => "invalid json",
+            Error::FieldNotInVersion { .. } => "field is not valid in the version",
+            Error::IdentifierMismatch { .. } => "file identifier mismatch",
+            Error::KeyNotSorted { .. } => "vector is not sorted by key",
+            Error::UnknownField { .. } => "field is not in the schema",
+            Error::BadFieldType { .. } => "bad field type in schema",
+            Error::BadUnionVariant { .. } => "bad union variant in schema",
+            Error::BadType { .. } => "bad type in schema",
+        }
+    }
+}
@@ -0,0 +1,654 @@
+//! Hashes tables by their logical values.
+//!
+//! [`structural_hash`](fn.structural_hash.html) walks a verified table using the schema and
+//! feeds a [`Hasher`](trait.Hasher.html) with a well-defined encoding of the value, so the
+//! digest does not depend on how the buffer is laid out, and survives re-encoding.
+//!
+//! # Encoding
+//!
+//! Each table is hashed by a new hasher, and the fields are fed in the order of their ids.
+//! Absent fields, scalar fields equal to the default, union type `NONE` and fields which are not
+//! in the schema are skipped. Set `deny_unknown_fields` in the verifier
+//! [`Options`](../verify/struct.Options.html) to reject the unknown fields instead, so buffers
+//! which differ only in them cannot share a digest. A present field is fed as:
+//!
+//! - The field id as `u16` in little endian.
+//! - The type tag, a byte which is the `reflection.BaseType` of the field: `UType`, `Bool` to
+//!   `Double`, `String`, `Vector`, `Obj` for both tables and structs, and `Union`.
+//! - The value.
+//!
+//! The values are encoded as:
+//!
+//! - Scalars: the little endian bytes written by [`LE`](../le/trait.LE.html). Bools are 0 or 1.
+//! - Structs: the fields in the order of their offsets, without the padding.
+//! - Strings: the length as `u32` in little endian followed by the UTF-8 bytes.
+//! - Vectors: the length as `u32` in little endian followed by the elements.
+//! - Tables and union values: the digest of the child table.
+//! - Nested buffers, the `[ubyte]` fields which have `nested_flatbuffer`: the digest of the
+//!   nested root table, so they are hashed by the values as well.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::hash::{structural_hash, Blake2b256};
+//! use blockbuffers::schema::{Field, ScalarType, Schema, Table, Type};
+//!
+//! // table Monster { hp: short = 100; }
+//! static MONSTER_FIELDS: &[Field] = &[Field {
+//!     name: "hp",
+//!     id: 0,
+//!     ty: Type::Scalar(ScalarType::Short),
+//!     required: false,
+//!     default_integer: 100,
+//!     default_real: 0.0,
+//!     since_version: 0,
+//!     deprecated_version: None,
+//!     nested_flatbuffer: None,
+//! }];
+//! let schema = Schema {
+//!     tables: &[Table { name: "Monster", fields: MONSTER_FIELDS, key: None }],
+//!     structs: &[],
+//!     unions: &[],
+//! };
+//!
+//! let mut monster = TableBuilder::new();
+//! monster.add_scalar(0, 80i16, 100);
+//! let buf = monster.finish();
+//!
+//! // Records the fed bytes instead of hashing them.
+//! assert_eq!(
+//!     vec![0u8, 0, 5, 80, 0],
+//!     structural_hash::<Vec<u8>>(&schema, 0, &buf).unwrap()
+//! );
+//! assert_eq!(32, structural_hash::<Blake2b256>(&schema, 0, &buf).unwrap().len());
+//! ```
+
+use core::mem::size_of;
+use error::Result;
+use le::LE;
+use position::{NestedBufferPosition, StringPosition, TablePosition, VectorPosition};
+use schema::{BaseType, Element, Field, ScalarType, Schema, Type};
+use seek::seek_uoffset;
+use types::{Len, SIZE_OF_LEN};
+use verify::{verify, verify_table_with_options, Options};
+
+/// Consumes bytes and produces a digest.
+pub trait Hasher: Default {
+    type Output: AsRef<[u8]>;
+
+    /// Feeds `bytes` into the hasher.
+    fn update(&mut self, bytes: &[u8]);
+    /// Consumes the hasher and returns the digest.
+    fn finalize(self) -> Self::Output;
+}
+
+/// Records all the fed bytes, the digest is the bytes themselves.
+///
+/// It is useful to inspect the encoding in tests.
+#[cfg(feature = "std")]
+impl Hasher for Vec<u8> {
+    type Output = Vec<u8>;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self
+    }
+}
+
+/// Hashes the root table of the buffer, which type is `root`, an index into `schema.tables`.
+///
+/// The buffer is verified first.
+pub fn structural_hash<H: Hasher>(schema: &Schema, root: usize, buf: &[u8]) -> Result<H::Output> {
+    let pos = verify(schema, root, buf)?;
+    Ok(Walker { schema, buf }.table::<H>(root, pos))
+}
+
+/// Hashes the table at `pos`, which type is `table`, an index into `schema.tables`.
+///
+/// The table is verified first.
+pub fn structural_hash_table<H: Hasher>(
+    schema: &Schema,
+    table: usize,
+    buf: &[u8],
+    pos: TablePosition,
+) -> Result<H::Output> {
+    structural_hash_table_with_options::<H>(schema, table, buf, pos, Options::default())
+}
+
+/// Hashes the table at `pos` with the specified verifier options.
+pub fn structural_hash_table_with_options<H: Hasher>(
+    schema: &Schema,
+    table: usize,
+    buf: &[u8],
+    pos: TablePosition,
+    options: Options,
+) -> Result<H::Output> {
+    verify_table_with_options(schema, table, buf, pos, options)?;
+    Ok(Walker { schema, buf }.table::<H>(table, pos))
+}
+
+/// Walks a verified buffer.
+struct Walker<'a> {
+    schema: &'a Schema<'a>,
+    buf: &'a [u8],
+}
+
+impl<'a> Walker<'a> {
+    fn table<H: Hasher>(&self, table: usize, pos: TablePosition) -> H::Output {
+        let mut hasher = H::default();
+        for field in self.schema.tables[table].fields {
+            if let Some(field_pos) = pos.field_position(self.buf, field.slot()) {
+                self.field(&mut hasher, field, pos, field_pos);
+            }
+        }
+        hasher.finalize()
+    }
+
+    fn field<H: Hasher>(&self, hasher: &mut H, field: &Field, table: TablePosition, pos: usize) {
+        let id = field.id;
+        match field.ty {
+            Type::Scalar(scalar) => {
+                if !self.is_default(field, scalar, pos) {
+                    header(hasher, id, scalar_base_type(scalar));
+                    self.scalar(hasher, scalar, pos);
+                }
+            }
+            Type::UnionType(_) => {
+                let tag = self.buf[pos];
+                if tag != 0 {
+                    header(hasher, id, BaseType::UType);
+                    hasher.update(&[tag]);
+                }
+            }
+            Type::Union(index) => {
+                let tag = table
+                    .field_position(self.buf, field.slot() - 2)
+                    .map_or(0, |tag_pos| self.buf[tag_pos]);
+                let variant = self.schema.unions[index]
+                    .variant(tag)
+                    .expect("verified union type");
+                header(hasher, id, BaseType::Union);
+                let child = self.table::<H>(variant.table, self.child(pos));
+                hasher.update(child.as_ref());
+            }
+            Type::Struct(index) => {
+                header(hasher, id, BaseType::Obj);
+                self.struct_(hasher, index, pos);
+            }
+            Type::String => {
+                header(hasher, id, BaseType::String);
+                self.string(hasher, seek_uoffset(self.buf, pos));
+            }
+            Type::Table(index) => {
+                header(hasher, id, BaseType::Obj);
+                let child = self.table::<H>(index, self.child(pos));
+                hasher.update(child.as_ref());
+            }
+            Type::Vector(element) => {
+                header(hasher, id, BaseType::Vector);
+                let vector = seek_uoffset(self.buf, pos);
+                match field.nested_flatbuffer {
+                    Some(index) => {
+                        let (buf, root) =
+                            NestedBufferPosition(VectorPosition(vector)).root(self.buf);
+                        let nested = Walker {
+                            schema: self.schema,
+                            buf,
+                        };
+                        let child = nested.table::<H>(index, root);
+                        hasher.update(child.as_ref());
+                    }
+                    None => self.vector(hasher, element, vector),
+                }
+            }
+        }
+    }
+
+    fn child(&self, pos: usize) -> TablePosition {
+        TablePosition(seek_uoffset(self.buf, pos))
+    }
+
+    fn is_default(&self, field: &Field, scalar: ScalarType, pos: usize) -> bool {
+        let (bytes, default) = (&self.buf[pos..], field.default_integer);
+        match scalar {
+            ScalarType::Bool => (bytes[0] != 0) == (default != 0),
+            ScalarType::Byte => i8::from_le_slice(bytes) == default as i8,
+            ScalarType::UByte => u8::from_le_slice(bytes) == default as u8,
+            ScalarType::Short => i16::from_le_slice(bytes) == default as i16,
+            ScalarType::UShort => u16::from_le_slice(bytes) == default as u16,
+            ScalarType::Int => i32::from_le_slice(bytes) == default as i32,
+            ScalarType::UInt => u32::from_le_slice(bytes) == default as u32,
+            ScalarType::Long => i64::from_le_slice(bytes) == default,
+            ScalarType::ULong => u64::from_le_slice(bytes) == default as u64,
+            ScalarType::Float => {
+                f32::from_le_slice(bytes).to_bits() == (field.default_real as f32).to_bits()
+            }
+            ScalarType::Double => {
+                f64::from_le_slice(bytes).to_bits() == field.default_real.to_bits()
+            }
+        }
+    }
+
+    fn scalar<H: Hasher>(&self, hasher: &mut H, scalar: ScalarType, pos: usize) {
+        match scalar {
+            ScalarType::Bool => hasher.update(&[(self.buf[pos] != 0) as u8]),
+            ScalarType::Byte => self.le::<H, i8>(hasher, pos),
+            ScalarType::UByte => self.le::<H, u8>(hasher, pos),
+            ScalarType::Short => self.le::<H, i16>(hasher, pos),
+            ScalarType::UShort => self.le::<H, u16>(hasher, pos),
+            ScalarType::Int => self.le::<H, i32>(hasher, pos),
+            ScalarType::UInt => self.le::<H, u32>(hasher, pos),
+            ScalarType::Long => self.le::<H, i64>(hasher, pos),
+            ScalarType::ULong => self.le::<H, u64>(hasher, pos),
+            ScalarType::Float => self.le::<H, f32>(hasher, pos),
+            ScalarType::Double => self.le::<H, f64>(hasher, pos),
+        }
+    }
+
+    fn le<H: Hasher, T: LE>(&self, hasher: &mut H, pos: usize) {
+        let mut bytes = [0u8; 8];
+        T::from_le_slice(&self.buf[pos..]).write_le_slice(&mut bytes);
+        hasher.update(&bytes[..size_of::<T>()]);
+    }
+
+    fn struct_<H: Hasher>(&self, hasher: &mut H, index: usize, pos: usize) {
+        for field in self.schema.structs[index].fields {
+            let field_pos = pos + field.offset;
+            match field.ty {
+                Type::Struct(index) => self.struct_(hasher, index, field_pos),
+                Type::Scalar(scalar) => self.scalar(hasher, scalar, field_pos),
+                _ => unreachable!("struct fields are scalars or structs"),
+            }
+        }
+    }
+
+    fn string<H: Hasher>(&self, hasher: &mut H, pos: usize) {
+        let bytes = StringPosition(pos).as_str(self.buf).as_bytes();
+        length(hasher, bytes.len());
+        hasher.update(bytes);
+    }
+
+    fn vector<H: Hasher>(&self, hasher: &mut H, element: Element, pos: usize) {
+        let len = VectorPosition(pos).len(self.buf);
+        let (size, _) = self.schema.element_size_and_align(element);
+        length(hasher, len);
+
+        for item_pos in (0..len).map(|i| pos + SIZE_OF_LEN + i * size) {
+            match element {
+                Element::Scalar(scalar) => self.scalar(hasher, scalar, item_pos),
+                Element::Struct(index) => self.struct_(hasher, index, item_pos),
+                Element::String => self.string(hasher, seek_uoffset(self.buf, item_pos)),
+                Element::Table(index) => {
+                    let child = self.table::<H>(index, self.child(item_pos));
+                    hasher.update(child.as_ref());
+                }
+            }
+        }
+    }
+}
+
+fn header<H: Hasher>(hasher: &mut H, id: u16, base_type: BaseType) {
+    let mut bytes = [0u8; 3];
+    id.write_le_slice(&mut bytes);
+    bytes[2] = base_type as u8;
+    hasher.update(&bytes);
+}
+
+fn length<H: Hasher>(hasher: &mut H, len: usize) {
+    let mut bytes = [0u8; SIZE_OF_LEN];
+    (len as Len).write_le_slice(&mut bytes);
+    hasher.update(&bytes);
+}
+
+fn scalar_base_type(scalar: ScalarType) -> BaseType {
+    match scalar {
+        ScalarType::Bool => BaseType::Bool,
+        ScalarType::Byte => BaseType::Byte,
+        ScalarType::UByte => BaseType::UByte,
+        ScalarType::Short => BaseType::Short,
+        ScalarType::UShort => BaseType::UShort,
+        ScalarType::Int => BaseType::Int,
+        ScalarType::UInt => BaseType::UInt,
+        ScalarType::Long => BaseType::Long,
+        ScalarType::ULong => BaseType::ULong,
+        ScalarType::Float => BaseType::Float,
+        ScalarType::Double => BaseType::Double,
+    }
+}
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09_e667_f3bc_c908,
+    0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b,
+    0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1,
+    0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b,
+    0x5be0_cd19_137e_2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLAKE2B_BLOCK_SIZE: usize = 128;
+
+/// BLAKE2b with 32 bytes output, as defined in RFC 7693.
+#[derive(Clone)]
+pub struct Blake2b256 {
+    h: [u64; 8],
+    counter: u128,
+    block: [u8; BLAKE2B_BLOCK_SIZE],
+    block_len: usize,
+}
+
+impl Default for Blake2b256 {
+    fn default() -> Self {
+        Blake2b256::with_personal(&[0; 16])
+    }
+}
+
+impl Blake2b256 {
+    /// Creates the hasher with the personalization string, such as `b"ckb-default-hash"`.
+    pub fn with_personal(personal: &[u8; 16]) -> Blake2b256 {
+        let mut h = BLAKE2B_IV;
+        // Parameter block: digest length 32, no key, fanout 1 and depth 1.
+        h[0] ^= 0x0101_0020;
+        h[6] ^= u64::from_le_slice(&personal[..8]);
+        h[7] ^= u64::from_le_slice(&personal[8..]);
+        Blake2b256 {
+            h,
+            counter: 0,
+            block: [0; BLAKE2B_BLOCK_SIZE],
+            block_len: 0,
+        }
+    }
+
+    fn compress(&mut self, last: bool) {
+        let mut m = [0u64; 16];
+        for (i, word) in m.iter_mut().enumerate() {
+            *word = u64::from_le_slice(&self.block[i * 8..]);
+        }
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.h);
+        v[8..].copy_from_slice(&BLAKE2B_IV);
+        v[12] ^= self.counter as u64;
+        v[13] ^= (self.counter >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+
+        for round in 0..12 {
+            let s = &BLAKE2B_SIGMA[round % 10];
+            blake2b_g(&mut v, (0, 4, 8, 12), m[s[0]], m[s[1]]);
+            blake2b_g(&mut v, (1, 5, 9, 13), m[s[2]], m[s[3]]);
+            blake2b_g(&mut v, (2, 6, 10, 14), m[s[4]], m[s[5]]);
+            blake2b_g(&mut v, (3, 7, 11, 15), m[s[6]], m[s[7]]);
+            blake2b_g(&mut v, (0, 5, 10, 15), m[s[8]], m[s[9]]);
+            blake2b_g(&mut v, (1, 6, 11, 12), m[s[10]], m[s[11]]);
+            blake2b_g(&mut v, (2, 7, 8, 13), m[s[12]], m[s[13]]);
+            blake2b_g(&mut v, (3, 4, 9, 14), m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+            self.h[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+}
+
+/// The mixing function G, which mixes `x` and `y` into the words at `a`, `b`, `c` and `d`.
+fn blake2b_g(v: &mut [u64; 16], (a, b, c, d): (usize, usize, usize, usize), x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+impl Hasher for Blake2b256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            // Keeps the last block for finalize, which must be compressed with the last flag.
+            if self.block_len == BLAKE2B_BLOCK_SIZE {
+                self.counter += BLAKE2B_BLOCK_SIZE as u128;
+                self.compress(false);
+                self.block_len = 0;
+            }
+            let n = (BLAKE2B_BLOCK_SIZE - self.block_len).min(bytes.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&bytes[..n]);
+            self.block_len += n;
+            bytes = &bytes[n..];
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        self.counter += self.block_len as u128;
+        for byte in &mut self.block[self.block_len..] {
+            *byte = 0;
+        }
+        self.compress(true);
+
+        let mut digest = [0u8; 32];
+        for (i, word) in self.h[..4].iter().enumerate() {
+            word.write_le_slice(&mut digest[i * 8..]);
+        }
+        digest
+    }
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5, 0x3956_c25b, 0x59f1_11f1, 0x923f_82a4,
+    0xab1c_5ed5, 0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3, 0x72be_5d74, 0x80de_b1fe,
+    0x9bdc_06a7, 0xc19b_f174, 0xe49b_69c1, 0xefbe_4786, 0x0fc1_9dc6, 0x240c_a1cc, 0x2de9_2c6f,
+    0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da, 0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7,
+    0xc6e0_0bf3, 0xd5a7_9147, 0x06ca_6351, 0x1429_2967, 0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc,
+    0x5338_0d13, 0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85, 0xa2bf_e8a1, 0xa81a_664b,
+    0xc24b_8b70, 0xc76c_51a3, 0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070, 0x19a4_c116,
+    0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5, 0x391c_0cb3, 0x4ed8_aa4a, 0x5b9c_ca4f, 0x682e_6ff3,
+    0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208, 0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+const SHA256_H: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// SHA-256, as defined in FIPS 180-4.
+#[derive(Clone)]
+pub struct Sha256 {
+    h: [u32; 8],
+    len: u64,
+    block: [u8; SHA256_BLOCK_SIZE],
+    block_len: usize,
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Sha256 {
+            h: SHA256_H,
+            len: 0,
+            block: [0; SHA256_BLOCK_SIZE],
+            block_len: 0,
+        }
+    }
+}
+
+impl Sha256 {
+    fn compress(&mut self) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            let word = &self.block[i * 4..i * 4 + 4];
+            w[i] = u32::from(word[0]) << 24
+                | u32::from(word[1]) << 16
+                | u32::from(word[2]) << 8
+                | u32::from(word[3]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let mut v = self.h;
+        for i in 0..64 {
+            let s1 = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+            let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+            let t1 = v[7]
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(SHA256_K[i])
+                .wrapping_add(w[i]);
+            let s0 = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+            let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+            let t2 = s0.wrapping_add(maj);
+
+            v[7] = v[6];
+            v[6] = v[5];
+            v[5] = v[4];
+            v[4] = v[3].wrapping_add(t1);
+            v[3] = v[2];
+            v[2] = v[1];
+            v[1] = v[0];
+            v[0] = t1.wrapping_add(t2);
+        }
+
+        for (h, v) in self.h.iter_mut().zip(v.iter()) {
+            *h = h.wrapping_add(*v);
+        }
+    }
+}
+
+impl Hasher for Sha256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        self.len += bytes.len() as u64;
+        while !bytes.is_empty() {
+            let n = (SHA256_BLOCK_SIZE - self.block_len).min(bytes.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&bytes[..n]);
+            self.block_len += n;
+            bytes = &bytes[n..];
+            if self.block_len == SHA256_BLOCK_SIZE {
+                self.compress();
+                self.block_len = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let bits = self.len.wrapping_mul(8);
+        let mut padding = [0u8; SHA256_BLOCK_SIZE + 8];
+        padding[0] = 0x80;
+        let pad_len = if self.block_len < 56 {
+            56 - self.block_len
+        } else {
+            120 - self.block_len
+        };
+        for i in 0..8 {
+            padding[pad_len + i] = (bits >> (56 - 8 * i)) as u8;
+        }
+        self.update(&padding[..pad_len + 8]);
+
+        let mut digest = [0u8; 32];
+        for (i, word) in self.h.iter().enumerate() {
+            for j in 0..4 {
+                digest[i * 4 + j] = (word >> (24 - 8 * j)) as u8;
+            }
+        }
+        digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn digest<H: Hasher>(mut hasher: H, bytes: &[u8]) -> String {
+        hasher.update(bytes);
+        hex(hasher.finalize().as_ref())
+    }
+
+    #[test]
+    fn test_blake2b256() {
+        assert_eq!(
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+            digest(Blake2b256::default(), b"")
+        );
+        assert_eq!(
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+            digest(Blake2b256::default(), b"abc")
+        );
+        assert_eq!(
+            "6b6e59aaf00eb730cf93de53560846722184bbd92f8368c21ffa95380c2f9fe6",
+            digest(Blake2b256::default(), &[b'a'; 200])
+        );
+        assert_eq!(
+            "521c604cc09b814b0a9106305395def35d0211b9996a3e0f326ae4d671bd8fc2",
+            digest(Blake2b256::with_personal(b"ckb-default-hash"), b"abc")
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            digest(Sha256::default(), b"")
+        );
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            digest(Sha256::default(), b"abc")
+        );
+        assert_eq!(
+            "c2a908d98f5df987ade41b5fce213067efbcc21ef2240212a41e54b5e7c28ae5",
+            digest(Sha256::default(), &[b'a'; 200])
+        );
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let bytes: Vec<u8> = (0..300).map(|i| i as u8).collect();
+        let mut blake2b = Blake2b256::default();
+        let mut sha256 = Sha256::default();
+        for chunk in bytes.chunks(7) {
+            blake2b.update(chunk);
+            sha256.update(chunk);
+        }
+        assert_eq!(digest(Blake2b256::default(), &bytes), hex(&blake2b.finalize()));
+        assert_eq!(digest(Sha256::default(), &bytes), hex(&sha256.finalize()));
+    }
+}
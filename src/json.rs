@@ -0,0 +1,821 @@
+//! Converts buffers to JSON and back using a [reflection schema](../reflection/index.html).
+//!
+//! The JSON text has the same layout as `flatc -t --strict-json`: fields are printed in the order
+//! of their ids, enum values are printed by name, and only the fields present in the buffer are
+//! printed. The only difference is that floats are printed in the shortest form which parses
+//! back to the same value, instead of a fixed precision.
+//!
+//! [`from_json`](fn.from_json.html) writes the buffer with the
+//! [canonical builder](../builder/index.html), so converting a canonical buffer to JSON and back
+//! gives the identical bytes. The only exception is NaN: all NaNs are printed as `nan`, which is
+//! parsed back as the canonical quiet NaN of the type, so the sign and the payload are not kept.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::json::{from_json, to_json};
+//! use blockbuffers::reflection::Schema;
+//!
+//! let bfbs = include_bytes!("../tests/common/example.bfbs");
+//! let schema = Schema::from_slice(&bfbs[..]).unwrap();
+//!
+//! let mut builder = TableBuilder::new();
+//! builder.add_scalar(0, 2u32, 0);
+//! builder.add_scalar(1, 1i16, 0);
+//! let buf = builder.finish();
+//!
+//! let json = to_json(&schema, &buf).unwrap();
+//! assert_eq!("{\n  \"version\": 2,\n  \"language\": \"Python\"\n}\n", json);
+//! assert_eq!(buf, from_json(&schema, &json).unwrap());
+//! ```
+
+use builder::{StructBuilder, TableBuilder};
+use dynamic::{DynStruct, DynTable, DynValue, Scalar};
+use error::{Error, Result};
+use reflection::{BaseType, Field, Object, Schema, Type};
+use std::char;
+use std::fmt::Write;
+use std::ops::Neg;
+use std::str::FromStr;
+use verify::verify;
+
+/// Converts the buffer to JSON.
+///
+/// The buffer is verified before the conversion.
+///
+/// # Panics
+///
+/// Panics if the schema has no `root_type`.
+pub fn to_json(schema: &Schema, buf: &[u8]) -> Result<String> {
+    let root = root_index(schema);
+    schema.with_description(|desc| verify(desc, root, buf))??;
+
+    let mut printer = Printer {
+        schema,
+        buf,
+        out: String::new(),
+    };
+    printer.table(DynTable::root(schema, buf)?, 0)?;
+    printer.out.push('\n');
+    Ok(printer.out)
+}
+
+/// Parses the JSON and writes the buffer in the canonical form.
+///
+/// Fails with `RequiredFieldMissing` if a required field is absent or `null`, where `pos` is the
+/// position of the JSON object in the text.
+///
+/// # Panics
+///
+/// Panics if the schema has no `root_type`.
+pub fn from_json(schema: &Schema, json: &str) -> Result<Vec<u8>> {
+    let root = &schema.objects[root_index(schema)];
+    let mut parser = Parser { json, pos: 0 };
+    let node = parser.value()?;
+    parser.whitespace();
+    if parser.pos != json.len() {
+        return Err(Error::InvalidJson { pos: parser.pos });
+    }
+
+    Encoder { schema }
+        .table(root, &node)
+        .map(|table| table.finish())
+}
+
+fn root_index(schema: &Schema) -> usize {
+    let root = schema.root_table.as_ref().expect("schema has no root_type");
+    schema
+        .object_index(&root.name)
+        .expect("root_type is in objects")
+}
+
+/// Gets the enum which defines the values of the scalar type, either a field type or a vector
+/// element type.
+fn enum_index(base_type: BaseType, ty: &Type) -> Option<usize> {
+    let is_integer = match base_type {
+        BaseType::UType => true,
+        BaseType::Bool | BaseType::Float | BaseType::Double => false,
+        _ => base_type.scalar().is_some(),
+    };
+    if is_integer && ty.index >= 0 {
+        Some(ty.index as usize)
+    } else {
+        None
+    }
+}
+
+struct Printer<'a> {
+    schema: &'a Schema,
+    buf: &'a [u8],
+    out: String,
+}
+
+impl<'a> Printer<'a> {
+    fn indent(&mut self, indent: usize) {
+        for _ in 0..indent {
+            self.out.push(' ');
+        }
+    }
+
+    fn key(&mut self, name: &str, count: usize, indent: usize) {
+        if count > 0 {
+            self.out.push(',');
+        }
+        self.out.push('\n');
+        self.indent(indent + 2);
+        self.string(name);
+        self.out.push_str(": ");
+    }
+
+    fn table(&mut self, table: DynTable<'a>, indent: usize) -> Result<()> {
+        self.out.push('{');
+        let mut count = 0;
+        for field in table.object.fields_by_id() {
+            if table
+                .position
+                .try_field_position(self.buf, field.offset as usize)?
+                .is_none()
+            {
+                continue;
+            }
+            if let Some(value) = table.get_field(self.schema, self.buf, field)? {
+                self.key(&field.name, count, indent);
+                self.value(value, field.ty.base_type, &field.ty, indent + 2)?;
+                count += 1;
+            }
+        }
+        self.out.push('\n');
+        self.indent(indent);
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn struct_(&mut self, value: DynStruct<'a>, indent: usize) -> Result<()> {
+        self.out.push('{');
+        for (count, field) in value.object.fields_by_id().into_iter().enumerate() {
+            self.key(&field.name, count, indent);
+            let field_value = value.get_field(self.schema, self.buf, field)?;
+            self.value(field_value, field.ty.base_type, &field.ty, indent + 2)?;
+        }
+        self.out.push('\n');
+        self.indent(indent);
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn value(
+        &mut self,
+        value: DynValue<'a>,
+        base_type: BaseType,
+        ty: &Type,
+        indent: usize,
+    ) -> Result<()> {
+        match value {
+            DynValue::Scalar(scalar) => {
+                self.scalar(scalar, enum_index(base_type, ty));
+                Ok(())
+            }
+            DynValue::String(s) => {
+                self.string(s);
+                Ok(())
+            }
+            DynValue::Table(table) => self.table(table, indent),
+            DynValue::Struct(value) => self.struct_(value, indent),
+            DynValue::Union(union) => self.table(union.table, indent),
+            DynValue::Vector(vector) => {
+                self.out.push_str("[\n");
+                for i in 0..vector.len(self.buf)? {
+                    if i > 0 {
+                        self.out.push_str(",\n");
+                    }
+                    self.indent(indent + 2);
+                    let element = vector.get(self.schema, self.buf, i)?;
+                    self.value(element, ty.element, ty, indent + 2)?;
+                }
+                self.out.push('\n');
+                self.indent(indent);
+                self.out.push(']');
+                Ok(())
+            }
+        }
+    }
+
+    fn scalar(&mut self, scalar: Scalar, enum_index: Option<usize>) {
+        if let Some(index) = enum_index {
+            let integer = match scalar {
+                Scalar::Byte(v) => i64::from(v),
+                Scalar::UByte(v) => i64::from(v),
+                Scalar::Short(v) => i64::from(v),
+                Scalar::UShort(v) => i64::from(v),
+                Scalar::Int(v) => i64::from(v),
+                Scalar::UInt(v) => i64::from(v),
+                Scalar::Long(v) => v,
+                Scalar::ULong(v) => v as i64,
+                _ => unreachable!("enum is integral"),
+            };
+            let schema = self.schema;
+            if let Some(value) = schema.enums[index].value(integer) {
+                self.string(&value.name);
+                return;
+            }
+        }
+
+        let out = &mut self.out;
+        let _ = match scalar {
+            Scalar::Bool(v) => write!(out, "{}", v),
+            Scalar::Byte(v) => write!(out, "{}", v),
+            Scalar::UByte(v) => write!(out, "{}", v),
+            Scalar::Short(v) => write!(out, "{}", v),
+            Scalar::UShort(v) => write!(out, "{}", v),
+            Scalar::Int(v) => write!(out, "{}", v),
+            Scalar::UInt(v) => write!(out, "{}", v),
+            Scalar::Long(v) => write!(out, "{}", v),
+            Scalar::ULong(v) => write!(out, "{}", v),
+            Scalar::Float(v) => write_float(out, f64::from(v), &format!("{:?}", v)),
+            Scalar::Double(v) => write_float(out, v, &format!("{:?}", v)),
+        };
+    }
+
+    fn string(&mut self, s: &str) {
+        self.out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\t' => self.out.push_str("\\t"),
+                '\r' => self.out.push_str("\\r"),
+                '\u{8}' => self.out.push_str("\\b"),
+                '\u{c}' => self.out.push_str("\\f"),
+                ' '..='~' => self.out.push(c),
+                _ => {
+                    let mut units = [0u16; 2];
+                    for unit in c.encode_utf16(&mut units) {
+                        let _ = write!(self.out, "\\u{:04X}", unit);
+                    }
+                }
+            }
+        }
+        self.out.push('"');
+    }
+}
+
+/// Writes the float like `flatc` for non-finite values, or `debug` which is the shortest
+/// representation that round-trips.
+fn write_float(out: &mut String, value: f64, debug: &str) -> ::std::fmt::Result {
+    if value.is_nan() {
+        out.write_str("nan")
+    } else if value.is_infinite() {
+        out.write_str(if value > 0.0 { "inf" } else { "-inf" })
+    } else {
+        out.write_str(debug)
+    }
+}
+
+/// A parsed JSON value and its start position in the text.
+struct Node {
+    pos: usize,
+    value: Value,
+}
+
+enum Value {
+    Null,
+    Bool(bool),
+    /// The number text, including `nan`, `inf` and `-inf`.
+    Number(String),
+    String(String),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn error(&self) -> Error {
+        Error::InvalidJson { pos: self.pos }
+    }
+
+    fn is_null(&self) -> bool {
+        match self.value {
+            Value::Null => true,
+            _ => false,
+        }
+    }
+
+    fn as_object(&self) -> Result<&[(String, Node)]> {
+        match self.value {
+            Value::Object(ref members) => Ok(members),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn as_array(&self) -> Result<&[Node]> {
+        match self.value {
+            Value::Array(ref elements) => Ok(elements),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn as_str(&self) -> Result<&str> {
+        match self.value {
+            Value::String(ref s) => Ok(s),
+            _ => Err(self.error()),
+        }
+    }
+}
+
+struct Parser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> Error {
+        Error::InvalidJson { pos: self.pos }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.pos).cloned()
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if self.json[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        self.whitespace();
+        let pos = self.pos;
+        let value = match self.peek() {
+            Some(b'{') => self.object()?,
+            Some(b'[') => self.array()?,
+            Some(b'"') => Value::String(self.string()?),
+            Some(b'-') | Some(b'0'..=b'9') => self.number()?,
+            _ => {
+                if self.keyword("null") {
+                    Value::Null
+                } else if self.keyword("true") {
+                    Value::Bool(true)
+                } else if self.keyword("false") {
+                    Value::Bool(false)
+                } else if self.keyword("nan") || self.keyword("inf") {
+                    Value::Number(self.json[pos..self.pos].to_string())
+                } else {
+                    return Err(self.error());
+                }
+            }
+        };
+        Ok(Node { pos, value })
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error());
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        self.pos += 1;
+        let mut elements = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(elements));
+        }
+        loop {
+            elements.push(self.value()?);
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(elements));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            if self.keyword("inf") {
+                return Ok(Value::Number("-inf".to_string()));
+            }
+        }
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-' => self.pos += 1,
+                _ => break,
+            }
+        }
+        if self.pos == start {
+            return Err(self.error());
+        }
+        Ok(Value::Number(self.json[start..self.pos].to_string()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let start = self.pos;
+            while let Some(byte) = self.peek() {
+                if byte == b'"' || byte == b'\\' || byte < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            // The run stops at ASCII bytes, so it is on the char boundaries.
+            s.push_str(&self.json[start..self.pos]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            s.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    self.pos += 1;
+                    s.push(escaped);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Parses the hex digits after `\u`, and the low surrogate escape if any.
+    fn unicode_escape(&mut self) -> Result<char> {
+        let high = self.hex4()?;
+        let code = if high >= 0xD800 && high < 0xDC00 {
+            if !self.keyword("\\u") {
+                return Err(self.error());
+            }
+            let low = self.hex4()?;
+            if low < 0xDC00 || low >= 0xE000 {
+                return Err(self.error());
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error())
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let end = self.pos + 4;
+        let digits = self.json.get(self.pos..end).ok_or_else(|| self.error())?;
+        let mut code = 0;
+        for digit in digits.bytes() {
+            let value = (digit as char).to_digit(16).ok_or_else(|| self.error())?;
+            code = code * 16 + value;
+        }
+        self.pos = end;
+        Ok(code)
+    }
+}
+
+/// Converts the parsed JSON to builders.
+struct Encoder<'a> {
+    schema: &'a Schema,
+}
+
+macro_rules! add_vector {
+    ($builder:expr, $id:expr, $items:expr, $variant:ident) => {{
+        let items: Vec<_> = $items
+            .iter()
+            .map(|item| match *item {
+                Scalar::$variant(value) => value,
+                _ => unreachable!("scalars in a vector have the same type"),
+            })
+            .collect();
+        $builder.add_vector($id, &items);
+    }};
+}
+
+impl<'a> Encoder<'a> {
+    fn table(&self, object: &Object, node: &Node) -> Result<TableBuilder> {
+        let members = node.as_object()?;
+        let mut builder = TableBuilder::new();
+
+        for (name, value) in members {
+            let field = object.field(name).ok_or_else(|| value.error())?;
+            if value.is_null() {
+                continue;
+            }
+            let ty = &field.ty;
+            match ty.base_type {
+                BaseType::String => builder.add_string(field.id, value.as_str()?),
+                BaseType::Obj => {
+                    let child = &self.schema.objects[ty.index as usize];
+                    if child.is_struct {
+                        builder.add_struct(field.id, &self.struct_(child, value)?);
+                    } else {
+                        builder.add_table(field.id, self.table(child, value)?);
+                    }
+                }
+                BaseType::Union => {
+                    let tag = self.union_type(object, field, members, value)?;
+                    let union_type = self.schema.enums[ty.index as usize]
+                        .value(i64::from(tag))
+                        .and_then(|v| v.union_type.as_ref())
+                        .ok_or_else(|| value.error())?;
+                    let child = &self.schema.objects[union_type.index as usize];
+                    builder.add_table(field.id, self.table(child, value)?);
+                }
+                BaseType::Vector => self.vector(&mut builder, field, value)?,
+                base_type => {
+                    let scalar = self.scalar(value, base_type, ty)?;
+                    if base_type == BaseType::UType
+                        && scalar != Scalar::UByte(0)
+                        && !has_member(members, object, field.id + 1)
+                    {
+                        return Err(value.error());
+                    }
+                    add_scalar(&mut builder, field, scalar);
+                }
+            }
+        }
+
+        if let Some(field) = object
+            .fields
+            .iter()
+            .find(|f| f.required && !has_member(members, object, f.id))
+        {
+            return Err(Error::RequiredFieldMissing {
+                pos: node.pos,
+                id: field.id,
+            });
+        }
+
+        Ok(builder)
+    }
+
+    /// Gets the type tag of the union `field` from its type field in `members`.
+    fn union_type(
+        &self,
+        object: &Object,
+        field: &Field,
+        members: &[(String, Node)],
+        value: &Node,
+    ) -> Result<u8> {
+        let type_field = object
+            .fields
+            .iter()
+            .find(|f| f.id + 1 == field.id)
+            .expect("union type field");
+        let type_node = members
+            .iter()
+            .find(|&(name, _)| *name == type_field.name)
+            .map(|(_, node)| node)
+            .ok_or_else(|| value.error())?;
+        match self.scalar(type_node, BaseType::UType, &type_field.ty)? {
+            Scalar::UByte(0) => Err(value.error()),
+            Scalar::UByte(tag) => Ok(tag),
+            _ => unreachable!("union type is ubyte"),
+        }
+    }
+
+    fn struct_(&self, object: &Object, node: &Node) -> Result<StructBuilder> {
+        let mut builder = StructBuilder::new(object.bytesize as usize, object.minalign as usize);
+        for (name, value) in node.as_object()? {
+            let field = object.field(name).ok_or_else(|| value.error())?;
+            let offset = field.offset as usize;
+            if field.ty.base_type == BaseType::Obj {
+                let child = &self.schema.objects[field.ty.index as usize];
+                builder.set_struct(offset, &self.struct_(child, value)?);
+                continue;
+            }
+            match self.scalar(value, field.ty.base_type, &field.ty)? {
+                Scalar::Bool(v) => builder.set(offset, v),
+                Scalar::Byte(v) => builder.set(offset, v),
+                Scalar::UByte(v) => builder.set(offset, v),
+                Scalar::Short(v) => builder.set(offset, v),
+                Scalar::UShort(v) => builder.set(offset, v),
+                Scalar::Int(v) => builder.set(offset, v),
+                Scalar::UInt(v) => builder.set(offset, v),
+                Scalar::Long(v) => builder.set(offset, v),
+                Scalar::ULong(v) => builder.set(offset, v),
+                Scalar::Float(v) => builder.set(offset, v),
+                Scalar::Double(v) => builder.set(offset, v),
+            }
+        }
+        Ok(builder)
+    }
+
+    fn vector(&self, builder: &mut TableBuilder, field: &Field, node: &Node) -> Result<()> {
+        let ty = &field.ty;
+        let elements = node.as_array()?;
+        match ty.element {
+            BaseType::String => {
+                let items = elements
+                    .iter()
+                    .map(|element| element.as_str())
+                    .collect::<Result<Vec<_>>>()?;
+                builder.add_string_vector(field.id, &items);
+            }
+            BaseType::Obj => {
+                let child = &self.schema.objects[ty.index as usize];
+                if child.is_struct {
+                    let items = elements
+                        .iter()
+                        .map(|element| self.struct_(child, element))
+                        .collect::<Result<Vec<_>>>()?;
+                    builder.add_struct_vector(field.id, child.minalign as usize, &items);
+                } else {
+                    let items = elements
+                        .iter()
+                        .map(|element| self.table(child, element))
+                        .collect::<Result<Vec<_>>>()?;
+                    builder.add_table_vector(field.id, items);
+                }
+            }
+            element => {
+                let items = elements
+                    .iter()
+                    .map(|item| self.scalar(item, element, ty))
+                    .collect::<Result<Vec<_>>>()?;
+                match element {
+                    BaseType::Bool => add_vector!(builder, field.id, items, Bool),
+                    BaseType::Byte => add_vector!(builder, field.id, items, Byte),
+                    BaseType::UByte | BaseType::UType => {
+                        add_vector!(builder, field.id, items, UByte)
+                    }
+                    BaseType::Short => add_vector!(builder, field.id, items, Short),
+                    BaseType::UShort => add_vector!(builder, field.id, items, UShort),
+                    BaseType::Int => add_vector!(builder, field.id, items, Int),
+                    BaseType::UInt => add_vector!(builder, field.id, items, UInt),
+                    BaseType::Long => add_vector!(builder, field.id, items, Long),
+                    BaseType::ULong => add_vector!(builder, field.id, items, ULong),
+                    BaseType::Float => add_vector!(builder, field.id, items, Float),
+                    BaseType::Double => add_vector!(builder, field.id, items, Double),
+                    _ => return Err(node.error()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the scalar of `base_type`, which is either the type of a field or a vector element
+    /// of `ty`.
+    fn scalar(&self, node: &Node, base_type: BaseType, ty: &Type) -> Result<Scalar> {
+        let error = node.error();
+        let integer = match node.value {
+            Value::Bool(v) if base_type == BaseType::Bool => return Ok(Scalar::Bool(v)),
+            Value::String(ref name) => {
+                let index = enum_index(base_type, ty).ok_or(error)?;
+                self.schema.enums[index]
+                    .values
+                    .iter()
+                    .find(|v| v.name == *name)
+                    .map(|v| i128::from(v.value))
+                    .ok_or(error)?
+            }
+            Value::Number(ref number) => match base_type {
+                BaseType::Float => {
+                    let nan = f32::from_bits(0x7fc0_0000);
+                    let infinity = f32::from_bits(0x7f80_0000);
+                    return parse_float(number, nan, infinity)
+                        .map(Scalar::Float)
+                        .ok_or(error);
+                }
+                BaseType::Double => {
+                    let nan = f64::from_bits(0x7ff8_0000_0000_0000);
+                    let infinity = f64::from_bits(0x7ff0_0000_0000_0000);
+                    return parse_float(number, nan, infinity)
+                        .map(Scalar::Double)
+                        .ok_or(error);
+                }
+                _ => number.parse::<i128>().map_err(|_| error)?,
+            },
+            _ => return Err(error),
+        };
+
+        macro_rules! integer {
+            ($variant:ident, $ty:ty) => {{
+                let value = integer as $ty;
+                if i128::from(value) != integer {
+                    return Err(error);
+                }
+                Scalar::$variant(value)
+            }};
+        }
+        let scalar = match base_type {
+            BaseType::Bool => Scalar::Bool(integer != 0),
+            BaseType::Byte => integer!(Byte, i8),
+            BaseType::UByte | BaseType::UType => integer!(UByte, u8),
+            BaseType::Short => integer!(Short, i16),
+            BaseType::UShort => integer!(UShort, u16),
+            BaseType::Int => integer!(Int, i32),
+            BaseType::UInt => integer!(UInt, u32),
+            BaseType::Long => integer!(Long, i64),
+            BaseType::ULong => integer!(ULong, u64),
+            _ => return Err(error),
+        };
+        Ok(scalar)
+    }
+}
+
+/// Parses the float, including `nan`, `inf` and `-inf` printed by `write_float`.
+///
+/// They are matched explicitly because `str::parse` does not accept them on older Rust versions.
+/// `nan` is parsed as the canonical NaN passed in, whichever NaN was printed.
+fn parse_float<T: FromStr + Neg<Output = T>>(number: &str, nan: T, infinity: T) -> Option<T> {
+    match number {
+        "nan" => Some(nan),
+        "inf" => Some(infinity),
+        "-inf" => Some(-infinity),
+        _ => number.parse().ok(),
+    }
+}
+
+fn has_member(members: &[(String, Node)], object: &Object, id: u16) -> bool {
+    object
+        .fields
+        .iter()
+        .find(|f| f.id == id)
+        .map_or(false, |field| {
+            members
+                .iter()
+                .any(|(name, node)| *name == field.name && !node.is_null())
+        })
+}
+
+fn add_scalar(builder: &mut TableBuilder, field: &Field, scalar: Scalar) {
+    let id = field.id;
+    let default = field.default_integer;
+    match scalar {
+        Scalar::Bool(v) => builder.add_scalar(id, v, default != 0),
+        Scalar::Byte(v) => builder.add_scalar(id, v, default as i8),
+        Scalar::UByte(v) => builder.add_scalar(id, v, default as u8),
+        Scalar::Short(v) => builder.add_scalar(id, v, default as i16),
+        Scalar::UShort(v) => builder.add_scalar(id, v, default as u16),
+        Scalar::Int(v) => builder.add_scalar(id, v, default as i32),
+        Scalar::UInt(v) => builder.add_scalar(id, v, default as u32),
+        Scalar::Long(v) => builder.add_scalar(id, v, default),
+        Scalar::ULong(v) => builder.add_scalar(id, v, default as u64),
+        Scalar::Float(v) => builder.add_scalar(id, v, field.default_real as f32),
+        Scalar::Double(v) => builder.add_scalar(id, v, field.default_real),
+    }
+}
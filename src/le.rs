@@ -1,9 +1,42 @@
-use std::mem::size_of;
-use std::mem::uninitialized;
-use std::ptr::copy_nonoverlapping;
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(target_endian = "little")]
+use core::mem::align_of;
+use core::mem::size_of;
+use core::ptr::{copy_nonoverlapping, read_unaligned};
+#[cfg(target_endian = "little")]
+use core::slice::from_raw_parts;
+use error::{Error, Result};
+
+/// The trait `FromLeBytes` decodes values from bytes in little endian form.
+///
+/// Unlike transmuting the bytes, the decoding rejects bit patterns which are not valid values of
+/// the type, such as a byte `2` for `bool` or an undefined enum discriminant.
+pub trait FromLeBytes: Sized {
+    /// Decodes the value from the first bytes of `bytes`.
+    ///
+    /// Fails if `bytes` is too short, or the bytes are not a valid value. The positions in the
+    /// errors are relative to `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::le::FromLeBytes;
+    /// use blockbuffers::Error;
+    ///
+    /// assert_eq!(Ok(1u16), u16::try_from_le_bytes(&[1u8, 0]));
+    /// assert_eq!(Ok(true), bool::try_from_le_bytes(&[1u8]));
+    /// assert_eq!(Err(Error::InvalidBool { pos: 0 }), bool::try_from_le_bytes(&[2u8]));
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 0, need: 2, buf_len: 1 }),
+    ///     u16::try_from_le_bytes(&[1u8])
+    /// );
+    /// ```
+    fn try_from_le_bytes(bytes: &[u8]) -> Result<Self>;
+}
 
 /// The trait `LE` converts between native endian and little endian.
-pub trait LE: Sized {
+pub trait LE: FromLeBytes {
     /// Converts a value in native endian to little endian.
     fn to_le(self) -> Self;
     /// Converts a value in little endian to native endian.
@@ -11,6 +44,9 @@ pub trait LE: Sized {
 
     /// Reads from slice in little endian form.
     ///
+    /// Panics if the slice is too short or does not contain a valid value, see
+    /// [`FromLeBytes::try_from_le_bytes`](trait.FromLeBytes.html#tymethod.try_from_le_bytes).
+    ///
     /// # Examples
     ///
     /// ```
@@ -19,68 +55,249 @@ pub trait LE: Sized {
     /// assert_eq!(1u16, u16::from_le_slice(&[1u8, 0]));
     /// ```
     fn from_le_slice(bytes: &[u8]) -> Self {
+        match Self::try_from_le_bytes(bytes) {
+            Ok(x) => x,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Writes to slice in little endian form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::le::LE;
+    ///
+    /// let mut bytes = [0u8; 2];
+    /// 1u16.write_le_slice(&mut bytes);
+    /// assert_eq!([1u8, 0], bytes);
+    /// ```
+    fn write_le_slice(self, bytes: &mut [u8]) {
         assert!(bytes.len() >= size_of::<Self>());
-        let mut x: Self = unsafe { uninitialized() };
-        let ptr = bytes.as_ptr() as *const Self;
-        unsafe { copy_nonoverlapping(ptr, &mut x as *mut Self, size_of::<Self>()) };
-        x.to_le()
+        let x = self.to_le();
+        let ptr = &x as *const Self as *const u8;
+        unsafe { copy_nonoverlapping(ptr, bytes.as_mut_ptr(), size_of::<Self>()) };
+    }
+}
+
+/// Checks that `bytes` is long enough for `T`.
+fn check_len<T>(bytes: &[u8]) -> Result<()> {
+    if bytes.len() < size_of::<T>() {
+        return Err(Error::OutOfBounds {
+            pos: 0,
+            need: size_of::<T>(),
+            buf_len: bytes.len(),
+        });
     }
+    Ok(())
 }
 
-/// The macro `impl_le_for_enum` implements trait `LE` for enum.
+/// The macro `impl_le_for_enum` implements traits `LE` and `FromLeBytes` for enum.
 ///
-/// The enum must specify a integer type via repr.
+/// The enum must specify a integer type via repr, and all the variants must be listed after it,
+/// so decoding can reject undefined discriminants. Enums are always decoded and encoded through
+/// the integer type, so `to_le` and `from_le` keep the value unchanged.
 ///
 /// # Examples
 ///
 /// ```
 /// #[macro_use] extern crate blockbuffers;
-/// use blockbuffers::le::LE;
+/// use blockbuffers::le::{FromLeBytes, LE};
+/// use blockbuffers::Error;
 ///
 /// #[repr(u16)]
-/// #[derive(Copy, Clone)]
+/// #[derive(Copy, Clone, Debug, PartialEq)]
 /// enum Side {
 ///   Left = 1,
 ///   Right = 2,
 /// }
-/// impl_le_for_enum!(Side, u16);
 ///
-/// assert_eq!(1u16, Side::from_le(Side::Left.to_le()) as u16);
-/// assert_eq!(2u16, Side::from_le(Side::Right.to_le()) as u16);
+/// # fn main() {
+/// impl_le_for_enum!(Side, u16, Left, Right);
+///
+/// let mut bytes = [0u8; 2];
+/// Side::Right.write_le_slice(&mut bytes);
+/// assert_eq!(Side::Right, Side::from_le_slice(&bytes));
+/// assert_eq!(
+///     Err(Error::UnknownEnumValue { pos: 0, value: 3 }),
+///     Side::try_from_le_bytes(&[3u8, 0])
+/// );
+/// # }
 /// ```
 #[macro_export]
 macro_rules! impl_le_for_enum {
-    ($ty:ident, $repr:ident) => {{
-        use blockbuffers::le::LE;
-        use std::mem::transmute;
+    ($ty:ident, $repr:ident, $($variant:ident),+) => {{
+        use $crate::le::{FromLeBytes, LE};
+        use $crate::Error;
+
+        impl FromLeBytes for $ty {
+            fn try_from_le_bytes(bytes: &[u8]) -> $crate::error::Result<Self> {
+                let value = <$repr as FromLeBytes>::try_from_le_bytes(bytes)?;
+                $(
+                    if value == $ty::$variant as $repr {
+                        return Ok($ty::$variant);
+                    }
+                )+
+                Err(Error::UnknownEnumValue {
+                    pos: 0,
+                    value: value as i64,
+                })
+            }
+        }
 
         impl LE for $ty {
             fn to_le(self) -> Self {
-                #[cfg(target_endian = "little")]
-                {
-                    self
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    unsafe { transmute((self as $repr).swap_bytes()) }
-                }
+                self
             }
             fn from_le(x: Self) -> Self {
-                #[cfg(target_endian = "little")]
-                {
-                    x
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    unsafe { transmute((x as $repr).swap_bytes()) }
-                }
+                x
+            }
+            fn write_le_slice(self, bytes: &mut [u8]) {
+                (self as $repr).write_le_slice(bytes)
             }
         }
     }};
 }
 
+/// The macro `open_enum` declares an open enum, which keeps values unknown to the reader.
+///
+/// A closed enum implemented with [`impl_le_for_enum!`](macro.impl_le_for_enum.html) cannot
+/// represent variants added by newer peers, so decoding them fails. An open enum is a newtype of
+/// the integer type with an associated const for each variant, and every value can be decoded and
+/// written back unchanged. The method `known` converts it to the closed enum of the known
+/// variants, which is declared as well.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate blockbuffers;
+/// use blockbuffers::le::LE;
+///
+/// open_enum! {
+///     /// enum Language: short { Rust, Python }
+///     pub struct Language(i16): LanguageKnown {
+///         Rust = 0,
+///         Python = 1,
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Some(LanguageKnown::Python), Language::Python.known());
+/// assert_eq!(Language::Python, Language::from(LanguageKnown::Python));
+///
+/// // A variant added by a newer peer.
+/// let go = Language::from_le_slice(&[2u8, 0]);
+/// assert_eq!(Language(2), go);
+/// assert_eq!(None, go.known());
+///
+/// let mut bytes = [0u8; 2];
+/// go.write_le_slice(&mut bytes);
+/// assert_eq!([2u8, 0], bytes);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! open_enum {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident($repr:ident): $known:ident {
+            $($(#[$variant_attr:meta])* $variant:ident = $value:expr),+ $(,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub $repr);
+
+        #[allow(non_upper_case_globals)]
+        impl $name {
+            $(
+                $(#[$variant_attr])*
+                pub const $variant: $name = $name($value);
+            )+
+
+            /// Converts to the known variant, `None` if the value is unknown.
+            pub fn known(self) -> Option<$known> {
+                $(
+                    if self.0 == $value {
+                        return Some($known::$variant);
+                    }
+                )+
+                None
+            }
+        }
+
+        /// The variants known to this reader.
+        #[repr($repr)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $known {
+            $(
+                $(#[$variant_attr])*
+                $variant = $value,
+            )+
+        }
+
+        impl From<$known> for $name {
+            fn from(known: $known) -> $name {
+                $name(known as $repr)
+            }
+        }
+
+        impl $crate::le::FromLeBytes for $name {
+            fn try_from_le_bytes(bytes: &[u8]) -> $crate::error::Result<Self> {
+                <$repr as $crate::le::FromLeBytes>::try_from_le_bytes(bytes).map($name)
+            }
+        }
+
+        impl $crate::le::LE for $name {
+            fn to_le(self) -> Self {
+                $name(<$repr as $crate::le::LE>::to_le(self.0))
+            }
+            fn from_le(x: Self) -> Self {
+                $name(<$repr as $crate::le::LE>::from_le(x.0))
+            }
+        }
+
+        unsafe impl $crate::le::Plain for $name {}
+    };
+}
+
+impl FromLeBytes for bool {
+    fn try_from_le_bytes(bytes: &[u8]) -> Result<Self> {
+        check_len::<bool>(bytes)?;
+        match bytes[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidBool { pos: 0 }),
+        }
+    }
+}
+
+impl LE for bool {
+    fn to_le(self) -> Self {
+        self
+    }
+    fn from_le(x: Self) -> Self {
+        x
+    }
+}
+
+/// Implements `FromLeBytes` for types which every bit pattern is a valid value of.
+macro_rules! impl_from_le_bytes {
+    ($ty:ident) => {
+        impl FromLeBytes for $ty {
+            fn try_from_le_bytes(bytes: &[u8]) -> Result<Self> {
+                check_len::<$ty>(bytes)?;
+                let x = unsafe { read_unaligned(bytes.as_ptr() as *const $ty) };
+                Ok(<$ty as LE>::from_le(x))
+            }
+        }
+    };
+}
+
 macro_rules! impl_le_no_op {
     ($ty:ident) => {
+        impl_from_le_bytes!($ty);
+
         impl LE for $ty {
             fn to_le(self) -> Self {
                 self
@@ -92,12 +309,13 @@ macro_rules! impl_le_no_op {
     };
 }
 
-impl_le_no_op!(bool);
 impl_le_no_op!(i8);
 impl_le_no_op!(u8);
 
 macro_rules! impl_le_for_int {
     ($ty:ident) => {
+        impl_from_le_bytes!($ty);
+
         impl LE for $ty {
             fn to_le(self) -> Self {
                 self.to_le()
@@ -118,6 +336,8 @@ impl_le_for_int!(u64);
 
 macro_rules! impl_le_for_float {
     ($ty:ident) => {
+        impl_from_le_bytes!($ty);
+
         impl LE for $ty {
             fn to_le(self) -> Self {
                 #[cfg(target_endian = "little")]
@@ -146,6 +366,188 @@ macro_rules! impl_le_for_float {
 impl_le_for_float!(f32);
 impl_le_for_float!(f64);
 
+/// The trait `Plain` marks scalars which every bit pattern is a valid value of.
+///
+/// # Safety
+///
+/// Every bit pattern must be a valid value of the type. So it must not be implemented for types
+/// such as `bool` and enums, because the buffer may contain invalid values for them.
+pub unsafe trait Plain: LE + Copy {}
+
+unsafe impl Plain for i8 {}
+unsafe impl Plain for u8 {}
+unsafe impl Plain for i16 {}
+unsafe impl Plain for u16 {}
+unsafe impl Plain for i32 {}
+unsafe impl Plain for u32 {}
+unsafe impl Plain for i64 {}
+unsafe impl Plain for u64 {}
+unsafe impl Plain for f32 {}
+unsafe impl Plain for f64 {}
+
+/// LeSlice is a view of scalars stored in little endian in the buffer.
+///
+/// Unlike `&[T]`, the bytes can be misaligned, and items are converted to native endian when
+/// they are read.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::le::LeSlice;
+///
+/// let bytes = &[0u8, 1, 0, 2, 0][1..];
+/// let items = LeSlice::<u16>::new(bytes);
+///
+/// assert_eq!(2, items.len());
+/// assert_eq!(2u16, items.get(1));
+/// assert_eq!(vec![1u16, 2], items.to_vec());
+/// ```
+pub struct LeSlice<'a, T> {
+    bytes: &'a [u8],
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: LE> LeSlice<'a, T> {
+    /// Views `bytes` as items of `T`.
+    ///
+    /// Panics if the length of `bytes` is not a multiple of the size of `T`.
+    pub fn new(bytes: &'a [u8]) -> LeSlice<'a, T> {
+        assert_eq!(0, bytes.len() % size_of::<T>());
+        LeSlice {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / size_of::<T>()
+    }
+
+    /// Returns true if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Reads the item at `index` in native endian.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> T {
+        assert!(index < self.len());
+        T::from_le_slice(&self.bytes[index * size_of::<T>()..])
+    }
+
+    /// Iterates the items in native endian.
+    pub fn iter(&self) -> LeIter<'a, T> {
+        LeIter {
+            slice: *self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Copies the items to a vector in native endian.
+    #[cfg(feature = "std")]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    /// Gets the raw bytes in little endian.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+#[cfg(target_endian = "little")]
+impl<'a, T: Plain> LeSlice<'a, T> {
+    /// Borrows the items as a native slice without copying.
+    ///
+    /// It is only available on little endian targets, where items have the same representation
+    /// in the buffer and in memory. Returns `None` if the bytes are not aligned for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::le::LeSlice;
+    ///
+    /// let items = [1u16, 2];
+    /// let bytes = unsafe { std::slice::from_raw_parts(items.as_ptr() as *const u8, 4) };
+    ///
+    /// assert_eq!(Some(&[1u16, 2][..]), LeSlice::<u16>::new(bytes).as_native_slice());
+    /// assert_eq!(None, LeSlice::<u16>::new(&bytes[1..3]).as_native_slice());
+    /// ```
+    pub fn as_native_slice(&self) -> Option<&'a [T]> {
+        let ptr = self.bytes.as_ptr();
+        if (ptr as usize) % align_of::<T>() != 0 {
+            return None;
+        }
+        Some(unsafe { from_raw_parts(ptr as *const T, self.len()) })
+    }
+}
+
+impl<'a, T> Clone for LeSlice<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for LeSlice<'a, T> {}
+
+impl<'a, T: LE + fmt::Debug> fmt::Debug for LeSlice<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: LE> IntoIterator for LeSlice<'a, T> {
+    type Item = T;
+    type IntoIter = LeIter<'a, T>;
+
+    fn into_iter(self) -> LeIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// LeIter is the iterator returned by [`LeSlice::iter`](struct.LeSlice.html#method.iter).
+#[derive(Clone)]
+pub struct LeIter<'a, T> {
+    slice: LeSlice<'a, T>,
+    /// The range of the remaining items.
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T: LE> Iterator for LeIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.slice.get(self.front - 1))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: LE> DoubleEndedIterator for LeIter<'a, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.slice.get(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: LE> ExactSizeIterator for LeIter<'a, T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +561,13 @@ mod tests {
         assert_eq!(1f32, f32::from_le(1f32.to_le()));
 
         assert_eq!(1u8, 1u8.to_le());
+        assert_eq!(-2i16, i16::from_le_slice(&[0xfe, 0xff]));
+        assert_eq!(0.5f32, f32::from_le_slice(&[0, 0, 0, 0x3f]));
+        assert_eq!(Ok(false), bool::try_from_le_bytes(&[0]));
+        assert_eq!(
+            Err(Error::InvalidBool { pos: 0 }),
+            bool::try_from_le_bytes(&[2])
+        );
 
         #[cfg(target_endian = "little")]
         {
@@ -171,4 +580,16 @@ mod tests {
             assert_eq!(1u32.swap_bytes(), 1u32.to_le());
         }
     }
+
+    #[test]
+    fn test_le_slice() {
+        let bytes = [0u8, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        let items = LeSlice::<i32>::new(&bytes[1..]);
+        assert_eq!(2, items.len());
+        assert_eq!(1, items.get(0));
+        assert_eq!(vec![-1, 1], items.iter().rev().collect::<Vec<_>>());
+        assert_eq!(2, items.iter().len());
+        assert_eq!("[1, -1]", format!("{:?}", items));
+        assert!(LeSlice::<u64>::new(&[]).is_empty());
+    }
 }
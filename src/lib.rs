@@ -1,4 +1,39 @@
+//! Blockbuffers builds and reads strict Flatbuffers binaries.
+//!
+//! The reader, the verifier and the structural hashing only depend on `core` and never allocate.
+//! They are available in `no_std` environments by disabling the default feature `std`, which
+//! enables the modules requiring an allocator: `builder`, `dynamic`, `json` and `reflection`.
+//! The `alloc` crate is not stable in the supported toolchain, so these modules require `std`.
+//!
+//! The optional feature `serde` enables the module `wire`, which serializes Rust types as
+//! Flatbuffers tables with serde. It also requires `std`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(any(feature = "std", test))]
+extern crate core;
+#[cfg(all(feature = "serde", feature = "std"))]
+#[macro_use]
+extern crate serde;
+
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod dynamic;
+pub mod error;
+pub mod hash;
+#[cfg(feature = "std")]
+pub mod json;
 pub mod le;
 pub mod position;
+#[cfg(feature = "std")]
+pub mod reflection;
+pub mod root;
+pub mod schema;
 pub mod seek;
 pub mod types;
+pub mod verify;
+pub mod version;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod wire;
+
+pub use error::Error;
@@ -1,9 +1,16 @@
-use le::LE;
-use seek::seek_soffset;
-use std::mem::size_of;
-use std::slice::from_raw_parts;
-use std::str::from_utf8_unchecked;
-use types::{Len, VOffset, SIZE_OF_LEN, SIZE_OF_VOFFSET};
+use core::cmp::{max, min, Ordering};
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
+use core::ptr::read_unaligned;
+use core::slice::{from_raw_parts, from_raw_parts_mut};
+use core::str::{from_utf8, from_utf8_unchecked};
+use error::{Error, Result};
+use le::{LeSlice, Plain, LE};
+use seek::{
+    align_up, check_alignment, check_bounds, seek_soffset, seek_uoffset, try_read,
+    try_seek_soffset, try_seek_uoffset,
+};
+use types::{Len, VOffset, SIZE_OF_LEN, SIZE_OF_SOFFSET, SIZE_OF_UOFFSET, SIZE_OF_VOFFSET};
 
 /// VectorPosition wrappers a position which points to a vector in the buffer.
 ///
@@ -18,7 +25,7 @@ use types::{Len, VOffset, SIZE_OF_LEN, SIZE_OF_VOFFSET};
 /// let pos = VectorPosition(0);
 ///
 /// assert_eq!(2, pos.len(buf));
-/// assert_eq!(&[1u16.to_le(), 2u16.to_le()], pos.as_slice::<u16>(buf));
+/// assert_eq!(vec![1u16, 2], pos.as_le_slice::<u16>(buf).to_vec());
 /// ```
 #[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
 pub struct VectorPosition(pub usize);
@@ -26,12 +33,95 @@ pub struct VectorPosition(pub usize);
 impl VectorPosition {
     /// Reads the length of the vector.
     pub fn len(self, buf: &[u8]) -> usize {
-        Len::from_le_slice(buf) as usize
+        Len::from_le_slice(&buf[self.0..]) as usize
+    }
+
+    /// Checked version of [`len`](#method.len).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VectorPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[02u8, 0, 0][..];
+    ///
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 0, need: 4, buf_len: 3 }),
+    ///     VectorPosition(0).try_len(buf)
+    /// );
+    /// ```
+    pub fn try_len(self, buf: &[u8]) -> Result<usize> {
+        try_read::<Len>(buf, self.0).map(|len| len as usize)
+    }
+
+    /// Copies the scalar items out of the buffer, checking each one, e.g., enums must have known
+    /// values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VectorPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[02u8, 0, 0, 0, 1, 2][..];
+    ///
+    /// assert_eq!(Ok(vec![1u8, 2]), VectorPosition(0).try_to_vec(buf));
+    /// assert_eq!(
+    ///     Err(Error::InvalidBool { pos: 5 }),
+    ///     VectorPosition(0).try_to_vec::<bool>(buf)
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_to_vec<T: LE>(self, buf: &[u8]) -> Result<Vec<T>> {
+        let len = self.try_len(buf)?;
+        let start_pos = self.0 + SIZE_OF_LEN;
+        (0..len)
+            .map(|i| try_read(buf, start_pos + i * size_of::<T>()))
+            .collect()
+    }
+
+    /// Gets the view of the items, which reads scalars in native endian.
+    pub fn as_le_slice<T: LE>(self, buf: &[u8]) -> LeSlice<'_, T> {
+        let len = self.len(buf);
+        let start_pos = self.0 + SIZE_OF_LEN;
+        LeSlice::new(&buf[start_pos..start_pos + len * size_of::<T>()])
+    }
+
+    /// Checked version of [`as_le_slice`](#method.as_le_slice).
+    ///
+    /// Fails if the items are not inside the buffer. The items are not required to be aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VectorPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[02u8, 0, 0, 0, 1, 0, 2][..];
+    ///
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 4, need: 4, buf_len: 7 }),
+    ///     VectorPosition(0).try_as_le_slice::<u16>(buf).map(|items| items.to_vec())
+    /// );
+    /// ```
+    pub fn try_as_le_slice<T: LE>(self, buf: &[u8]) -> Result<LeSlice<'_, T>> {
+        let len = self.try_len(buf)?;
+        let start_pos = self.0 + SIZE_OF_LEN;
+        let bytes_len = len.checked_mul(size_of::<T>()).ok_or(Error::OutOfBounds {
+            pos: start_pos,
+            need: usize::max_value(),
+            buf_len: buf.len(),
+        })?;
+        check_bounds(buf, start_pos, bytes_len)?;
+        Ok(LeSlice::new(&buf[start_pos..start_pos + bytes_len]))
     }
 
     /// Gets the reference to the items slice.
     ///
-    /// The slice attaches to the buffer directly, so all scalars are in little endian form.
+    /// The slice attaches to the buffer directly, so all scalars are in little endian form, and
+    /// they may be misaligned for `T`. Use [`as_le_slice`](#method.as_le_slice) instead.
+    #[deprecated(note = "items may be misaligned or in foreign endian, use `as_le_slice`")]
     pub fn as_slice<T>(self, buf: &[u8]) -> &[T] {
         let len = self.len(buf);
         let start_pos = self.0 + SIZE_OF_LEN;
@@ -40,6 +130,826 @@ impl VectorPosition {
 
         unsafe { from_raw_parts(ptr, len) }
     }
+
+    /// Checked version of [`as_slice`](#method.as_slice).
+    ///
+    /// Fails if the items are not inside the buffer, or the items are not aligned in memory for
+    /// `T`. The items are still in little endian form, use
+    /// [`try_as_le_slice`](#method.try_as_le_slice) instead.
+    #[deprecated(note = "items may be in foreign endian, use `try_as_le_slice`")]
+    pub fn try_as_slice<T>(self, buf: &[u8]) -> Result<&[T]> {
+        let len = self.try_len(buf)?;
+        let start_pos = self.0 + SIZE_OF_LEN;
+        let bytes_len = len.checked_mul(size_of::<T>()).ok_or(Error::OutOfBounds {
+            pos: start_pos,
+            need: usize::max_value(),
+            buf_len: buf.len(),
+        })?;
+        check_bounds(buf, start_pos, bytes_len)?;
+
+        let ptr = buf[start_pos..start_pos + bytes_len].as_ptr();
+        if (ptr as usize) % align_of::<T>() != 0 {
+            return Err(Error::Misaligned {
+                pos: start_pos,
+                align: align_of::<T>(),
+            });
+        }
+
+        Ok(unsafe { from_raw_parts(ptr as *const T, len) })
+    }
+
+    /// Iterates the tables in a vector of tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::{TablePosition, VectorPosition};
+    /// use blockbuffers::seek::seek_uoffset;
+    ///
+    /// let mut parent = TableBuilder::new();
+    /// parent.add_table_vector(0, vec![TableBuilder::new(), TableBuilder::new()]);
+    /// let buf = parent.finish();
+    ///
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    /// let children = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    ///
+    /// let tables = children.tables(&buf);
+    /// assert_eq!(2, tables.len());
+    /// assert_eq!(tables.get(1), tables.rev().next());
+    /// ```
+    pub fn tables(self, buf: &[u8]) -> Elements<'_, TablePosition> {
+        Elements::new(buf, self.0 + SIZE_OF_LEN, SIZE_OF_UOFFSET, self.len(buf))
+    }
+
+    /// Checked version of [`tables`](#method.tables).
+    ///
+    /// Fails if the offsets to the tables are not inside the buffer or are misaligned. The tables
+    /// themselves are not checked.
+    pub fn try_tables(self, buf: &[u8]) -> Result<Elements<'_, TablePosition>> {
+        self.try_elements(buf, SIZE_OF_UOFFSET, SIZE_OF_UOFFSET)
+    }
+
+    /// Iterates the strings in a vector of strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::{TablePosition, VectorPosition};
+    /// use blockbuffers::seek::seek_uoffset;
+    ///
+    /// let mut table = TableBuilder::new();
+    /// table.add_string_vector(0, &["a", "b"]);
+    /// let buf = table.finish();
+    ///
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    /// let names = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    ///
+    /// let names: Vec<&str> = names.strings(&buf).map(|s| s.as_str(&buf)).collect();
+    /// assert_eq!(vec!["a", "b"], names);
+    /// ```
+    pub fn strings(self, buf: &[u8]) -> Elements<'_, StringPosition> {
+        Elements::new(buf, self.0 + SIZE_OF_LEN, SIZE_OF_UOFFSET, self.len(buf))
+    }
+
+    /// Checked version of [`strings`](#method.strings).
+    ///
+    /// Fails if the offsets to the strings are not inside the buffer or are misaligned. The
+    /// strings themselves are not checked.
+    pub fn try_strings(self, buf: &[u8]) -> Result<Elements<'_, StringPosition>> {
+        self.try_elements(buf, SIZE_OF_UOFFSET, SIZE_OF_UOFFSET)
+    }
+
+    /// Iterates the structs in a vector of structs, which size is `stride` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VectorPosition;
+    ///
+    /// // [Point], struct Point { x: short; y: short; }
+    /// let buf = &[2u8, 0, 0, 0, 1, 0, 2, 0, 3, 0, 4, 0][..];
+    ///
+    /// let xs: Vec<i16> = VectorPosition(0).structs(buf, 4).map(|p| p.read(buf, 0)).collect();
+    /// assert_eq!(vec![1, 3], xs);
+    /// ```
+    pub fn structs(self, buf: &[u8], stride: usize) -> Elements<'_, StructPosition> {
+        Elements::new(buf, self.0 + SIZE_OF_LEN, stride, self.len(buf))
+    }
+
+    /// Checked version of [`structs`](#method.structs).
+    ///
+    /// Fails if the structs are not inside the buffer, or are not aligned to `layout.minalign`.
+    pub fn try_structs(
+        self,
+        buf: &[u8],
+        layout: StructLayout,
+    ) -> Result<Elements<'_, StructPosition>> {
+        self.try_elements(buf, layout.bytesize, layout.minalign)
+    }
+
+    /// Iterates the elements in the vector as `T`, which size is `stride` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::{Elements, TablePosition, VectorPosition};
+    /// use blockbuffers::seek::seek_uoffset;
+    ///
+    /// let mut table = TableBuilder::new();
+    /// table.add_string_vector(0, &["a", "b"]);
+    /// let buf = table.finish();
+    ///
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    /// let names = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    ///
+    /// let names: Elements<&str> = names.elements(&buf, 4);
+    /// assert_eq!(vec!["a", "b"], names.collect::<Vec<_>>());
+    /// ```
+    pub fn elements<'a, T: VectorElement<'a>>(
+        self,
+        buf: &'a [u8],
+        stride: usize,
+    ) -> Elements<'a, T> {
+        Elements::new(buf, self.0 + SIZE_OF_LEN, stride, self.len(buf))
+    }
+
+    /// Finds the table which key field equals `key` by binary search, in a vector of tables
+    /// sorted by the key field.
+    ///
+    /// The key field is specified using `key_field_slot`, the offset inside vtable bytes, see
+    /// [`TablePosition::field_position`](struct.TablePosition.html#method.field_position). An
+    /// absent key field is read as `default`, which must be the default value of the key field in
+    /// the schema, or `""` for strings, so the order matches the one checked by the verifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::{TablePosition, VectorPosition};
+    /// use blockbuffers::seek::seek_uoffset;
+    ///
+    /// // table Monster { name: string (key); hp: short; }
+    /// let monsters = ["goblin", "orc", "troll"]
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, name)| {
+    ///         let mut monster = TableBuilder::new();
+    ///         monster.add_string(0, name);
+    ///         monster.add_scalar(1, i as i16 + 1, 0);
+    ///         monster
+    ///     })
+    ///     .collect();
+    /// let mut table = TableBuilder::new();
+    /// table.add_table_vector(0, monsters);
+    /// let buf = table.finish();
+    ///
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    /// let monsters = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    ///
+    /// let orc = monsters.lookup_by_key(&buf, "orc", 4, "").unwrap();
+    /// assert_eq!(Ok(2i16), orc.try_read_field(&buf, 6, 0));
+    /// assert_eq!(None, monsters.lookup_by_key(&buf, "elf", 4, ""));
+    /// assert_eq!(Some(orc), monsters.lookup_by_key(&buf, 2i16, 6, 0));
+    /// ```
+    pub fn lookup_by_key<K: Key>(
+        self,
+        buf: &[u8],
+        key: K,
+        key_field_slot: usize,
+        default: K,
+    ) -> Option<TablePosition> {
+        let tables = self.tables(buf);
+        let mut low = 0;
+        let mut high = tables.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let table = tables.get(mid)?;
+            match key.cmp_field(buf, table, key_field_slot, &default) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Some(table),
+            }
+        }
+        None
+    }
+
+    /// Checked version of [`lookup_by_key`](#method.lookup_by_key).
+    ///
+    /// Fails if the offsets to the tables or the key fields of the visited tables are malformed.
+    pub fn try_lookup_by_key<K: Key>(
+        self,
+        buf: &[u8],
+        key: K,
+        key_field_slot: usize,
+        default: K,
+    ) -> Result<Option<TablePosition>> {
+        let tables = self.try_tables(buf)?;
+        let mut low = 0;
+        let mut high = tables.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let table = match tables.get(mid) {
+                Some(table) => table,
+                None => return Ok(None),
+            };
+            match key.try_cmp_field(buf, table, key_field_slot, &default)? {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Some(table)),
+            }
+        }
+        Ok(None)
+    }
+
+    fn try_elements<'a, T: VectorElement<'a>>(
+        self,
+        buf: &'a [u8],
+        stride: usize,
+        align: usize,
+    ) -> Result<Elements<'a, T>> {
+        check_alignment(self.0, SIZE_OF_LEN)?;
+        let len = self.try_len(buf)?;
+        let start_pos = self.0 + SIZE_OF_LEN;
+        check_alignment(start_pos, align)?;
+        let bytes_len = len.checked_mul(stride).ok_or(Error::OutOfBounds {
+            pos: start_pos,
+            need: usize::max_value(),
+            buf_len: buf.len(),
+        })?;
+        check_bounds(buf, start_pos, bytes_len)?;
+        Ok(Elements::new(buf, start_pos, stride, len))
+    }
+}
+
+/// Values which can be read from the elements of a vector.
+///
+/// Tables and strings are stored as `UOffset`s to them, while structs are stored inline. Readers
+/// generated by `blockc` implement it as well.
+pub trait VectorElement<'a>: Copy {
+    /// Reads the value from the element at `pos`.
+    fn from_element(buf: &'a [u8], pos: usize) -> Self;
+}
+
+impl<'a> VectorElement<'a> for TablePosition {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        TablePosition(seek_uoffset(buf, pos))
+    }
+}
+
+impl<'a> VectorElement<'a> for StringPosition {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        StringPosition(seek_uoffset(buf, pos))
+    }
+}
+
+impl<'a> VectorElement<'a> for &'a str {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        StringPosition(seek_uoffset(buf, pos)).as_str(buf)
+    }
+}
+
+impl<'a> VectorElement<'a> for StructPosition {
+    fn from_element(_buf: &'a [u8], pos: usize) -> Self {
+        StructPosition(pos)
+    }
+}
+
+impl<'a, A: FixedArray> VectorElement<'a> for A {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        StructPosition(pos).read_array(buf)
+    }
+}
+
+/// Keys which vectors of tables are sorted by, marked by the attribute `key` in the schema.
+///
+/// Strings are compared by bytes and scalars by values. An absent key field is read as the
+/// `default` passed in, the default value of the key field in the schema.
+pub trait Key {
+    /// Compares the key field of the table with this key.
+    ///
+    /// Returns the ordering of the field relative to the key, as the comparator in
+    /// `slice::binary_search_by`.
+    fn cmp_field(
+        &self,
+        buf: &[u8],
+        table: TablePosition,
+        key_field_slot: usize,
+        default: &Self,
+    ) -> Ordering;
+
+    /// Checked version of [`cmp_field`](#tymethod.cmp_field).
+    fn try_cmp_field(
+        &self,
+        buf: &[u8],
+        table: TablePosition,
+        key_field_slot: usize,
+        default: &Self,
+    ) -> Result<Ordering>;
+}
+
+impl<'k> Key for &'k str {
+    fn cmp_field(
+        &self,
+        buf: &[u8],
+        table: TablePosition,
+        key_field_slot: usize,
+        default: &Self,
+    ) -> Ordering {
+        let field = match table.field_position(buf, key_field_slot) {
+            Some(pos) => StringPosition(seek_uoffset(buf, pos)).as_str(buf),
+            None => default,
+        };
+        field.cmp(self)
+    }
+
+    fn try_cmp_field(
+        &self,
+        buf: &[u8],
+        table: TablePosition,
+        key_field_slot: usize,
+        default: &Self,
+    ) -> Result<Ordering> {
+        let field = match table.try_field_position(buf, key_field_slot)? {
+            Some(pos) => StringPosition(try_seek_uoffset(buf, pos)?).try_as_str(buf)?,
+            None => default,
+        };
+        Ok(field.cmp(self))
+    }
+}
+
+/// Implements `Key` for scalars. Comparisons involving NaN are treated as less, so NaN is never
+/// found.
+macro_rules! impl_key_for_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl Key for $ty {
+                fn cmp_field(
+                    &self,
+                    buf: &[u8],
+                    table: TablePosition,
+                    key_field_slot: usize,
+                    default: &Self,
+                ) -> Ordering {
+                    let field = match table.field_position(buf, key_field_slot) {
+                        Some(pos) => <$ty>::from_le_slice(&buf[pos..]),
+                        None => *default,
+                    };
+                    field.partial_cmp(self).unwrap_or(Ordering::Less)
+                }
+
+                fn try_cmp_field(
+                    &self,
+                    buf: &[u8],
+                    table: TablePosition,
+                    key_field_slot: usize,
+                    default: &Self,
+                ) -> Result<Ordering> {
+                    let field = table.try_read_field(buf, key_field_slot, *default)?;
+                    Ok(field.partial_cmp(self).unwrap_or(Ordering::Less))
+                }
+            }
+        )*
+    };
+}
+
+impl_key_for_scalar!(bool, i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+/// Elements is the iterator over the elements in a vector, returned by
+/// [`VectorPosition::tables`](struct.VectorPosition.html#method.tables),
+/// [`strings`](struct.VectorPosition.html#method.strings),
+/// [`structs`](struct.VectorPosition.html#method.structs) and
+/// [`elements`](struct.VectorPosition.html#method.elements).
+#[derive(Clone, Debug)]
+pub struct Elements<'a, T> {
+    buf: &'a [u8],
+    /// The position of the first element.
+    start: usize,
+    stride: usize,
+    /// The range of the remaining elements.
+    front: usize,
+    back: usize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: VectorElement<'a>> Elements<'a, T> {
+    fn new(buf: &'a [u8], start: usize, stride: usize, len: usize) -> Elements<'a, T> {
+        Elements {
+            buf,
+            start,
+            stride,
+            front: 0,
+            back: len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Gets the element `index` among the remaining elements, `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.back - self.front {
+            Some(self.element(self.front + index))
+        } else {
+            None
+        }
+    }
+
+    fn element(&self, index: usize) -> T {
+        T::from_element(self.buf, self.start + index * self.stride)
+    }
+}
+
+impl<'a, T: VectorElement<'a>> Iterator for Elements<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.element(self.front - 1))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.front = min(self.front.saturating_add(n), self.back);
+        self.next()
+    }
+}
+
+impl<'a, T: VectorElement<'a>> DoubleEndedIterator for Elements<'a, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.element(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: VectorElement<'a>> ExactSizeIterator for Elements<'a, T> {}
+
+/// NestedBufferPosition wrappers a vector of `u8` which holds a nested Flatbuffers binary, such
+/// as a field tagged `nested_flatbuffer: "Example"`.
+///
+/// The nested buffer is a sub-slice of the outer buffer, and all the positions inside it,
+/// including the root table position, are relative to the sub-slice. So the nested buffer can be
+/// read, verified and hashed like a standalone buffer without copying.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::position::{NestedBufferPosition, VectorPosition};
+///
+/// let buf = &[
+///     12u8, 0, 0, 0,                          // vector length
+///     8, 0, 0, 0,                             // nested root offset
+///     4, 0, 4, 0,                             // nested vtable
+///     4, 0, 0, 0,                             // nested table
+/// ][..];
+/// let pos = NestedBufferPosition(VectorPosition(0));
+///
+/// let (nested, root) = pos.root(buf);
+/// assert_eq!(&buf[4..], nested);
+/// assert_eq!(8, root.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub struct NestedBufferPosition(pub VectorPosition);
+
+impl NestedBufferPosition {
+    /// Gets the nested buffer.
+    pub fn as_slice(self, buf: &[u8]) -> &[u8] {
+        self.0.as_le_slice::<u8>(buf).as_bytes()
+    }
+
+    /// Checked version of [`as_slice`](#method.as_slice).
+    ///
+    /// Fails if the nested buffer is not inside the buffer, or it does not start at a position
+    /// aligned to `UOffset`. Positions inside the nested buffer are checked relative to its start,
+    /// so it must be aligned in the outer buffer.
+    pub fn try_as_slice(self, buf: &[u8]) -> Result<&[u8]> {
+        check_alignment((self.0).0 + SIZE_OF_LEN, SIZE_OF_UOFFSET)?;
+        self.0
+            .try_as_le_slice::<u8>(buf)
+            .map(|bytes| bytes.as_bytes())
+    }
+
+    /// Gets the nested buffer and the position of its root table inside the nested buffer.
+    pub fn root(self, buf: &[u8]) -> (&[u8], TablePosition) {
+        let nested = self.as_slice(buf);
+        (nested, TablePosition(seek_uoffset(nested, 0)))
+    }
+
+    /// Checked version of [`root`](#method.root).
+    ///
+    /// Fails if the nested buffer is invalid, or the root offset is outside the nested buffer or
+    /// is misaligned. The positions in the errors are relative to the nested buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::{NestedBufferPosition, VectorPosition};
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[8u8, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0][..];
+    ///
+    /// assert_eq!(
+    ///     Err(Error::Misaligned { pos: 6, align: 4 }),
+    ///     NestedBufferPosition(VectorPosition(0)).try_root(buf)
+    /// );
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 8, need: 6, buf_len: 12 }),
+    ///     NestedBufferPosition(VectorPosition(4)).try_root(buf)
+    /// );
+    /// ```
+    pub fn try_root(self, buf: &[u8]) -> Result<(&[u8], TablePosition)> {
+        let nested = self.try_as_slice(buf)?;
+        let root = try_seek_uoffset(nested, 0)?;
+        check_alignment(root, SIZE_OF_SOFFSET)?;
+        Ok((nested, TablePosition(root)))
+    }
+}
+
+/// Fixed size arrays of scalars which can be copied from the buffer.
+///
+/// It is implemented for arrays of [`Plain`](../le/trait.Plain.html) scalars with lengths from 1
+/// to 32, 64, 128 and 256. Generated newtypes of structs tagged `fixed_array` implement it as
+/// well. Items cannot be `bool` or enums, because the buffer may contain invalid values for them.
+///
+/// # Safety
+///
+/// The type must have the same size and memory layout as `[Self::Item; Self::LEN]`.
+pub unsafe trait FixedArray: Copy {
+    /// The type of the items.
+    type Item: Plain;
+    /// The number of items.
+    const LEN: usize;
+}
+
+macro_rules! impl_fixed_array {
+    ($($len:expr),*) => {
+        $(unsafe impl<T: Plain> FixedArray for [T; $len] {
+            type Item = T;
+            const LEN: usize = $len;
+        })*
+    };
+}
+
+impl_fixed_array!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 64, 128, 256
+);
+
+/// StructPosition wrappers a position which points to a struct in the buffer.
+///
+/// Structs are stored inline in tables, vectors and other structs. The fields are laid out in the
+/// order of declaration, each aligned to its size, and the struct is padded to its alignment.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::position::StructPosition;
+///
+/// // struct U256 (fixed_array: "[ubyte:32]") { byte_0: ubyte; ...; byte_31: ubyte; }
+/// let mut buf = [0u8; 36];
+/// buf[4] = 1;
+/// let pos = StructPosition(4);
+///
+/// let bytes: [u8; 32] = pos.read_array(&buf);
+/// assert_eq!(1, bytes[0]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub struct StructPosition(pub usize);
+
+impl StructPosition {
+    /// Reads the scalar field at `field_offset` bytes from the start of the struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::StructPosition;
+    ///
+    /// // struct Point { x: short; y: short; }
+    /// let buf = &[0u8, 0, 0, 0, 1, 0, 2, 0][..];
+    /// let pos = StructPosition(4);
+    ///
+    /// assert_eq!(1i16, pos.read(buf, 0));
+    /// assert_eq!(2i16, pos.read(buf, 2));
+    /// ```
+    pub fn read<T: LE>(self, buf: &[u8], field_offset: usize) -> T {
+        T::from_le_slice(&buf[self.0 + field_offset..])
+    }
+
+    /// Checked version of [`read`](#method.read).
+    ///
+    /// Fails if the field is not inside the buffer, or it is not aligned to its size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::StructPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[0u8, 0, 0, 0, 1, 0, 2, 0][..];
+    ///
+    /// assert_eq!(Ok(2i16), StructPosition(4).try_read(buf, 2));
+    /// assert_eq!(
+    ///     Err(Error::Misaligned { pos: 5, align: 2 }),
+    ///     StructPosition(4).try_read::<i16>(buf, 1)
+    /// );
+    /// ```
+    pub fn try_read<T: LE>(self, buf: &[u8], field_offset: usize) -> Result<T> {
+        try_read(buf, self.0 + field_offset)
+    }
+
+    /// Gets the position of the nested struct field at `field_offset` bytes from the start of the
+    /// struct.
+    pub fn field_struct(self, field_offset: usize) -> StructPosition {
+        StructPosition(self.0 + field_offset)
+    }
+
+    /// Copies the struct as a fixed array, such as `[u8; 32]` for a struct tagged
+    /// `fixed_array: "[ubyte:32]"`.
+    ///
+    /// The items are converted to native endian. Panics if the struct is not inside the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::StructPosition;
+    ///
+    /// // struct Words (fixed_array: "[ushort:2]") { word_0: ushort; word_1: ushort; }
+    /// let buf = &[0u8, 1, 0, 2, 0][..];
+    ///
+    /// let words: [u16; 2] = StructPosition(1).read_array(buf);
+    /// assert_eq!([1, 2], words);
+    /// ```
+    pub fn read_array<A: FixedArray>(self, buf: &[u8]) -> A {
+        let bytes = &buf[self.0..self.0 + size_of::<A>()];
+        // Every bit pattern is a valid item, because items are `Plain`.
+        let mut array = unsafe { read_unaligned(bytes.as_ptr() as *const A) };
+        {
+            let items = unsafe { from_raw_parts_mut(&mut array as *mut A as *mut A::Item, A::LEN) };
+            for item in items {
+                *item = A::Item::from_le(*item);
+            }
+        }
+        array
+    }
+
+    /// Checked version of [`read_array`](#method.read_array).
+    ///
+    /// Fails if the struct is not inside the buffer, or it is not aligned to the item size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::StructPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = [0u8; 8];
+    ///
+    /// assert!(StructPosition(0).try_read_array::<[u8; 8]>(&buf).is_ok());
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 4, need: 8, buf_len: 8 }),
+    ///     StructPosition(4).try_read_array::<[u8; 8]>(&buf)
+    /// );
+    /// assert_eq!(
+    ///     Err(Error::Misaligned { pos: 1, align: 2 }),
+    ///     StructPosition(1).try_read_array::<[u16; 2]>(&buf)
+    /// );
+    /// ```
+    pub fn try_read_array<A: FixedArray>(self, buf: &[u8]) -> Result<A> {
+        check_bounds(buf, self.0, size_of::<A>())?;
+        check_alignment(self.0, size_of::<A::Item>())?;
+        Ok(self.read_array(buf))
+    }
+}
+
+/// StructLayout is the size and alignment of a struct, the same as `bytesize` and `minalign` in
+/// the reflection `Object`.
+///
+/// The layout can be computed from the fields. Each field is placed at the next position aligned
+/// to its alignment, and the struct size is padded to a multiple of its alignment.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::position::StructLayout;
+///
+/// // struct Vec3 { x: float; y: float; z: float; flag: bool; }
+/// let mut layout = StructLayout::new();
+/// assert_eq!(0, layout.add_field(4, 4));
+/// assert_eq!(4, layout.add_field(4, 4));
+/// assert_eq!(8, layout.add_field(4, 4));
+/// assert_eq!(12, layout.add_field(1, 1));
+///
+/// let layout = layout.finish();
+/// assert_eq!(StructLayout { bytesize: 16, minalign: 4 }, layout);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StructLayout {
+    pub bytesize: usize,
+    pub minalign: usize,
+}
+
+impl Default for StructLayout {
+    fn default() -> Self {
+        StructLayout::new()
+    }
+}
+
+impl StructLayout {
+    /// Creates the layout of an empty struct.
+    pub fn new() -> StructLayout {
+        StructLayout {
+            bytesize: 0,
+            minalign: 1,
+        }
+    }
+
+    /// Appends a field which has `size` bytes and is aligned to `align`, and returns the offset
+    /// of the field.
+    ///
+    /// Scalars are aligned to their sizes, and nested structs are aligned to their `minalign`.
+    pub fn add_field(&mut self, size: usize, align: usize) -> usize {
+        let offset = align_up(self.bytesize, align);
+        self.bytesize = offset + size;
+        self.minalign = max(self.minalign, align);
+        offset
+    }
+
+    /// Raises the alignment of the struct to `align`, as the attribute `force_align`.
+    pub fn force_align(&mut self, align: usize) {
+        self.minalign = max(self.minalign, align);
+    }
+
+    /// Pads the struct size to a multiple of the alignment.
+    pub fn finish(self) -> StructLayout {
+        StructLayout {
+            bytesize: align_up(self.bytesize, self.minalign),
+            minalign: self.minalign,
+        }
+    }
+
+    /// Checks that a struct of this layout at `pos` is inside the buffer and properly aligned.
+    pub fn try_struct(self, buf: &[u8], pos: usize) -> Result<StructPosition> {
+        check_alignment(pos, self.minalign)?;
+        check_bounds(buf, pos, self.bytesize)?;
+        Ok(StructPosition(pos))
+    }
+
+    /// Gets the position of the element `index` in a vector of structs of this layout.
+    pub fn element(self, vector: VectorPosition, index: usize) -> StructPosition {
+        StructPosition(vector.0 + SIZE_OF_LEN + index * self.bytesize)
+    }
+
+    /// Checked version of [`element`](#method.element).
+    ///
+    /// Fails if `index` is not less than the vector length, or the element is not inside the
+    /// buffer or is misaligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::{StructLayout, VectorPosition};
+    /// use blockbuffers::Error;
+    ///
+    /// // [Point], struct Point { x: short; y: short; }
+    /// let buf = &[2u8, 0, 0, 0, 1, 0, 2, 0, 3, 0, 4, 0][..];
+    /// let layout = StructLayout { bytesize: 4, minalign: 2 };
+    ///
+    /// let second = layout.try_element(buf, VectorPosition(0), 1).unwrap();
+    /// assert_eq!(Ok(4i16), second.try_read(buf, 2));
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 12, need: 4, buf_len: 12 }),
+    ///     layout.try_element(buf, VectorPosition(0), 2)
+    /// );
+    /// ```
+    pub fn try_element(
+        self,
+        buf: &[u8],
+        vector: VectorPosition,
+        index: usize,
+    ) -> Result<StructPosition> {
+        let len = vector.try_len(buf)?;
+        let pos = self.element(vector, index);
+        if index >= len {
+            return Err(Error::OutOfBounds {
+                pos: pos.0,
+                need: self.bytesize,
+                buf_len: buf.len(),
+            });
+        }
+        self.try_struct(buf, pos.0)
+    }
 }
 
 /// StringPosition wrappers a position which points to a string in the buffer.
@@ -64,7 +974,12 @@ pub struct StringPosition(pub usize);
 impl StringPosition {
     /// Reads the length of the string in bytes.
     pub fn len(self, buf: &[u8]) -> usize {
-        Len::from_le_slice(buf) as usize
+        Len::from_le_slice(&buf[self.0..]) as usize
+    }
+
+    /// Checked version of [`len`](#method.len).
+    pub fn try_len(self, buf: &[u8]) -> Result<usize> {
+        try_read::<Len>(buf, self.0).map(|len| len as usize)
     }
 
     /// Gets the reference to the string.
@@ -75,6 +990,32 @@ impl StringPosition {
 
         unsafe { from_utf8_unchecked(&buf[start_pos..end_pos]) }
     }
+
+    /// Checked version of [`as_str`](#method.as_str).
+    ///
+    /// Fails if the string bytes are not inside the buffer or are not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::StringPosition;
+    /// use blockbuffers::Error;
+    ///
+    /// let buf = &[03u8, 0, 0, 0, 'b' as u8, 0xff, 's' as u8, 0][..];
+    ///
+    /// assert_eq!(Err(Error::InvalidUtf8 { pos: 0 }), StringPosition(0).try_as_str(buf));
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 4, need: 3, buf_len: 6 }),
+    ///     StringPosition(0).try_as_str(&buf[..6])
+    /// );
+    /// ```
+    pub fn try_as_str(self, buf: &[u8]) -> Result<&str> {
+        let len = self.try_len(buf)?;
+        let start_pos = self.0 + SIZE_OF_LEN;
+        check_bounds(buf, start_pos, len)?;
+
+        from_utf8(&buf[start_pos..start_pos + len]).map_err(|_| Error::InvalidUtf8 { pos: self.0 })
+    }
 }
 
 /// VTablePosition wrappers a position which points to a vtable in the buffer.
@@ -105,6 +1046,35 @@ impl VTablePosition {
         VOffset::from_le_slice(&buf[self.0..]) as usize
     }
 
+    /// Checked version of [`vtable_bytes_len`](#method.vtable_bytes_len).
+    ///
+    /// Fails if the size is odd, less than 4, or the vtable is not inside the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VTablePosition;
+    /// use blockbuffers::Error;
+    ///
+    /// assert_eq!(Ok(4), VTablePosition(0).try_vtable_bytes_len(&[4u8, 0, 6, 0]));
+    /// assert_eq!(
+    ///     Err(Error::BadVTable { pos: 0 }),
+    ///     VTablePosition(0).try_vtable_bytes_len(&[5u8, 0, 6, 0, 0])
+    /// );
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 0, need: 6, buf_len: 4 }),
+    ///     VTablePosition(0).try_vtable_bytes_len(&[6u8, 0, 6, 0])
+    /// );
+    /// ```
+    pub fn try_vtable_bytes_len(self, buf: &[u8]) -> Result<usize> {
+        let len = try_read::<VOffset>(buf, self.0)? as usize;
+        if len % SIZE_OF_VOFFSET != 0 || len < 2 * SIZE_OF_VOFFSET {
+            return Err(Error::BadVTable { pos: self.0 });
+        }
+        check_bounds(buf, self.0, len)?;
+        Ok(len)
+    }
+
     /// Reads the size of the table in bytes.
     ///
     /// # Examples
@@ -121,6 +1091,31 @@ impl VTablePosition {
         VOffset::from_le_slice(&buf[self.0 + SIZE_OF_VOFFSET..]) as usize
     }
 
+    /// Checked version of [`table_bytes_len`](#method.table_bytes_len).
+    ///
+    /// Fails if the size is too small to hold the `SOffset` to the vtable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VTablePosition;
+    /// use blockbuffers::Error;
+    ///
+    /// assert_eq!(Ok(6), VTablePosition(0).try_table_bytes_len(&[4u8, 0, 6, 0]));
+    /// assert_eq!(
+    ///     Err(Error::BadVTable { pos: 0 }),
+    ///     VTablePosition(0).try_table_bytes_len(&[4u8, 0, 2, 0])
+    /// );
+    /// ```
+    pub fn try_table_bytes_len(self, buf: &[u8]) -> Result<usize> {
+        self.try_vtable_bytes_len(buf)?;
+        let len = try_read::<VOffset>(buf, self.0 + SIZE_OF_VOFFSET)? as usize;
+        if len < SIZE_OF_SOFFSET {
+            return Err(Error::BadVTable { pos: self.0 });
+        }
+        Ok(len)
+    }
+
     /// Reads the field offset.
     ///
     /// Parameter `voffset_offset` is the position of the field voffset inside vtable bytes. For
@@ -145,11 +1140,57 @@ impl VTablePosition {
     /// ```
     pub fn field_offset(self, buf: &[u8], voffset_offset: usize) -> VOffset {
         if voffset_offset < self.vtable_bytes_len(&buf) {
-            VOffset::from_le_slice(&buf[voffset_offset..])
+            VOffset::from_le_slice(&buf[self.0 + voffset_offset..])
         } else {
             0
         }
     }
+
+    /// Checked version of [`field_offset`](#method.field_offset).
+    ///
+    /// Fails if the vtable is malformed, `voffset_offset` overflows, or the field offset points
+    /// into the `SOffset` or beyond the table bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::VTablePosition;
+    /// use blockbuffers::Error;
+    ///
+    /// // Field offsets are 20, 0, 2
+    /// let buf = &[10u8, 0, 40, 0, 20, 0, 0, 0, 2, 0][..];
+    /// let pos = VTablePosition(0);
+    ///
+    /// assert_eq!(Ok(20), pos.try_field_offset(&buf, 4));
+    /// assert_eq!(Ok(0), pos.try_field_offset(&buf, 6));
+    /// assert_eq!(Err(Error::BadVTable { pos: 0 }), pos.try_field_offset(&buf, 8));
+    /// assert_eq!(Ok(0), pos.try_field_offset(&buf, 10));
+    /// assert_eq!(Err(Error::BadVTable { pos: 0 }), pos.try_field_offset(&buf, usize::max_value()));
+    /// ```
+    pub fn try_field_offset(self, buf: &[u8], voffset_offset: usize) -> Result<VOffset> {
+        let vtable_len = self.try_vtable_bytes_len(buf)?;
+        let table_len = self.try_table_bytes_len(buf)?;
+        let end = voffset_offset
+            .checked_add(SIZE_OF_VOFFSET)
+            .ok_or(Error::BadVTable { pos: self.0 })?;
+        if end > vtable_len {
+            return Ok(0);
+        }
+
+        let pos = self
+            .0
+            .checked_add(voffset_offset)
+            .ok_or(Error::OutOfBounds {
+                pos: self.0,
+                need: end,
+                buf_len: buf.len(),
+            })?;
+        let offset = try_read::<VOffset>(buf, pos)?;
+        if offset != 0 && ((offset as usize) < SIZE_OF_SOFFSET || offset as usize >= table_len) {
+            return Err(Error::BadVTable { pos: self.0 });
+        }
+        Ok(offset)
+    }
 }
 
 /// TablePosition wrappers a position which points to a table in the buffer.
@@ -184,6 +1225,34 @@ impl TablePosition {
         VTablePosition(seek_soffset(buf, self.0))
     }
 
+    /// Checked version of [`vtable`](#method.vtable).
+    ///
+    /// Fails if the table or the vtable is misaligned, the vtable is malformed, or the table bytes
+    /// declared in the vtable are not inside the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::TablePosition;
+    /// use blockbuffers::Error;
+    /// //         | -4               | vtable      | 4         |
+    /// let buf = &[252, 255, 255, 255, 4u8, 0, 4, 0, 4, 0, 0, 0][..];
+    ///
+    /// assert_eq!(Ok(4), TablePosition(0).try_vtable(&buf).map(|v| v.0));
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 8, need: 4, buf_len: 10 }),
+    ///     TablePosition(8).try_vtable(&buf[..10]).map(|v| v.0)
+    /// );
+    /// ```
+    pub fn try_vtable(self, buf: &[u8]) -> Result<VTablePosition> {
+        check_alignment(self.0, SIZE_OF_SOFFSET)?;
+        let vtable = VTablePosition(try_seek_soffset(buf, self.0)?);
+        check_alignment(vtable.0, SIZE_OF_VOFFSET)?;
+        let table_len = vtable.try_table_bytes_len(buf)?;
+        check_bounds(buf, self.0, table_len)?;
+        Ok(vtable)
+    }
+
     /// Seeks the position for a field.
     ///
     /// The field index is specified using `pos_in_vtable`, which is the offset inside vtable
@@ -211,4 +1280,220 @@ impl TablePosition {
             None
         }
     }
+
+    /// Checked version of [`field_position`](#method.field_position).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::position::TablePosition;
+    /// use blockbuffers::Error;
+    /// //       [vtable 8|    12|     4|    8] [table      8|    1 |    2      ]
+    /// let buf = &[8u8, 0, 12, 0, 4, 0, 8, 0, 8, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0][..];
+    /// let pos = TablePosition(8);
+    ///
+    /// assert_eq!(Ok(Some(4 + 8)), pos.try_field_position(&buf, 4));
+    /// assert_eq!(Ok(Some(8 + 8)), pos.try_field_position(&buf, 6));
+    /// assert_eq!(Ok(None), pos.try_field_position(&buf, 8));
+    /// assert_eq!(
+    ///     Err(Error::OutOfBounds { pos: 8, need: 12, buf_len: 16 }),
+    ///     pos.try_field_position(&buf[..16], 4)
+    /// );
+    /// ```
+    pub fn try_field_position(self, buf: &[u8], pos_in_vtable: usize) -> Result<Option<usize>> {
+        let vtable = self.try_vtable(buf)?;
+        let offset = vtable.try_field_offset(buf, pos_in_vtable)?;
+        if offset != 0 {
+            Ok(Some(self.0 + offset as usize))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads the scalar field, or returns `default` if the field is absent.
+    ///
+    /// The value is checked, e.g., enums must have known values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::TablePosition;
+    /// use blockbuffers::seek::seek_uoffset;
+    /// use blockbuffers::Error;
+    ///
+    /// let mut table = TableBuilder::new();
+    /// table.add_scalar(0, 2u8, 0);
+    /// let buf = table.finish();
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    ///
+    /// assert_eq!(Ok(2u8), root.try_read_field(&buf, 4, 0));
+    /// assert_eq!(Ok(7u32), root.try_read_field(&buf, 6, 7));
+    /// assert!(root.try_read_field(&buf, 4, false).is_err());
+    /// ```
+    pub fn try_read_field<T: LE>(self, buf: &[u8], pos_in_vtable: usize, default: T) -> Result<T> {
+        match self.try_field_position(buf, pos_in_vtable)? {
+            Some(pos) => try_read(buf, pos),
+            None => Ok(default),
+        }
+    }
+}
+
+/// UnionPosition wrappers the two fields of a union in a table.
+///
+/// A union is stored as a `u8` type tag field, and an offset field to the value table, which id
+/// is one more than the type tag field. Tag 0 is `NONE`, which means the union has no value.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate blockbuffers;
+/// use blockbuffers::builder::TableBuilder;
+/// use blockbuffers::position::{TablePosition, UnionPosition};
+/// use blockbuffers::seek::seek_uoffset;
+///
+/// #[repr(u8)]
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum CodeOption {
+///     NONE = 0,
+///     Code = 1,
+/// }
+/// const ENUM_MAX_CODE_OPTION: u8 = 1;
+///
+/// # fn main() {
+/// impl_le_for_enum!(CodeOption, u8, NONE, Code);
+///
+/// // table Block { code: CodeOption; }
+/// let mut block = TableBuilder::new();
+/// block.add_union(1, CodeOption::Code as u8, TableBuilder::new());
+/// let buf = block.finish();
+///
+/// let code = UnionPosition::new(TablePosition(seek_uoffset(&buf, 0)), 6);
+/// assert_eq!(Ok(CodeOption::Code), code.try_tag(&buf, ENUM_MAX_CODE_OPTION));
+/// assert!(code.try_value(&buf, ENUM_MAX_CODE_OPTION).unwrap().is_some());
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub struct UnionPosition {
+    /// The table which has the union.
+    pub table: TablePosition,
+    /// The position of the value field voffset inside vtable bytes. The type tag field is right
+    /// before it.
+    pub value_slot: usize,
+}
+
+impl UnionPosition {
+    /// Pairs the union fields in `table`. The value field voffset is at `value_slot` inside the
+    /// vtable bytes, and the type tag field voffset is at `value_slot - 2`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value_slot` is less than 6, since the union value cannot be the field 0.
+    pub fn new(table: TablePosition, value_slot: usize) -> UnionPosition {
+        assert!(
+            value_slot >= 3 * SIZE_OF_VOFFSET,
+            "union value cannot be the field 0"
+        );
+        UnionPosition { table, value_slot }
+    }
+
+    /// The tag field voffset position, fails if the value is the field 0.
+    fn tag_slot(self) -> Result<usize> {
+        if self.value_slot < 3 * SIZE_OF_VOFFSET {
+            return Err(Error::BadUnion { pos: self.table.0 });
+        }
+        Ok(self.value_slot - SIZE_OF_VOFFSET)
+    }
+
+    /// Reads the type tag, 0 if the tag field is absent.
+    pub fn tag_value(self, buf: &[u8]) -> u8 {
+        let tag_slot = self.tag_slot().expect("union value cannot be the field 0");
+        self.table
+            .field_position(buf, tag_slot)
+            .map_or(0, |pos| buf[pos])
+    }
+
+    /// Checked version of [`tag_value`](#method.tag_value).
+    pub fn try_tag_value(self, buf: &[u8]) -> Result<u8> {
+        match self.table.try_field_position(buf, self.tag_slot()?)? {
+            Some(pos) => try_read::<u8>(buf, pos),
+            None => Ok(0),
+        }
+    }
+
+    /// Reads the type tag as the enum `T`, which implements `LE` using
+    /// [`impl_le_for_enum!`](../macro.impl_le_for_enum.html) with `repr(u8)`.
+    ///
+    /// Panics if `T` is not a single byte or the tag is not a variant of `T`, use
+    /// [`try_tag`](#method.try_tag) if the buffer is not trusted.
+    pub fn tag<T: LE>(self, buf: &[u8]) -> T {
+        assert_eq!(1, size_of::<T>());
+        T::from_le_slice(&[self.tag_value(buf)])
+    }
+
+    /// Checked version of [`tag`](#method.tag).
+    ///
+    /// The variants of `T` must be `0..=max_tag`, which is `ENUM_MAX_*` generated by flatc. Fails
+    /// if the tag is greater than `max_tag` or is not a variant of `T`, which includes the case
+    /// that `T` is not a single byte.
+    pub fn try_tag<T: LE>(self, buf: &[u8], max_tag: u8) -> Result<T> {
+        let tag = self.try_tag_value(buf)?;
+        let unknown = Error::UnknownUnionType {
+            pos: self.table.0,
+            tag,
+        };
+        if tag > max_tag || size_of::<T>() != 1 {
+            return Err(unknown);
+        }
+        T::try_from_le_bytes(&[tag]).map_err(|_| unknown)
+    }
+
+    /// Seeks the union value table, `None` if the union is `NONE`.
+    pub fn value(self, buf: &[u8]) -> Option<TablePosition> {
+        if self.tag_value(buf) == 0 {
+            return None;
+        }
+        self.table
+            .field_position(buf, self.value_slot)
+            .map(|pos| TablePosition(seek_uoffset(buf, pos)))
+    }
+
+    /// Checked version of [`value`](#method.value).
+    ///
+    /// Resolves the value only when the tag is known, that is, not greater than `max_tag`.
+    /// Fails if the tag is unknown, the tag is set without the value, or the value is set
+    /// with tag `NONE`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::{TablePosition, UnionPosition};
+    /// use blockbuffers::seek::seek_uoffset;
+    /// use blockbuffers::Error;
+    ///
+    /// let mut block = TableBuilder::new();
+    /// block.add_scalar(0, 1u8, 0);
+    /// let buf = block.finish();
+    /// let root = TablePosition(seek_uoffset(&buf, 0));
+    /// let code = UnionPosition::new(root, 6);
+    ///
+    /// assert_eq!(Err(Error::BadUnion { pos: root.0 }), code.try_value(&buf, 1));
+    /// assert_eq!(
+    ///     Err(Error::UnknownUnionType { pos: root.0, tag: 1 }),
+    ///     code.try_value(&buf, 0)
+    /// );
+    /// ```
+    pub fn try_value(self, buf: &[u8], max_tag: u8) -> Result<Option<TablePosition>> {
+        let tag = self.try_tag::<u8>(buf, max_tag)?;
+        let value = self.table.try_field_position(buf, self.value_slot)?;
+        match (tag, value) {
+            (0, None) => Ok(None),
+            (0, Some(_)) | (_, None) => Err(Error::BadUnion { pos: self.table.0 }),
+            (_, Some(pos)) => {
+                check_alignment(pos, SIZE_OF_UOFFSET)?;
+                try_seek_uoffset(buf, pos).map(|target| Some(TablePosition(target)))
+            }
+        }
+    }
 }
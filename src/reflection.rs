@@ -0,0 +1,772 @@
+//! Reads `.bfbs` files, the binary schemas generated by `flatc -b --schema`.
+//!
+//! The types in this module mirror the tables in `reflection.fbs`. The buffer is verified
+//! against [`REFLECTION_SCHEMA`](static.REFLECTION_SCHEMA.html) and then copied into the owned
+//! types.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::reflection::Schema;
+//!
+//! let bfbs = include_bytes!("../tests/common/example.bfbs");
+//! let schema = Schema::from_slice(&bfbs[..]).unwrap();
+//!
+//! let example = schema.root_table.as_ref().unwrap();
+//! assert_eq!("example.Example", example.name);
+//! let lines = example.field("lines").unwrap();
+//! assert_eq!(Some("2"), lines.attribute("since_version"));
+//! ```
+
+use error::{Error, Result};
+use le::LE;
+use position::{StringPosition, StructLayout, TablePosition, VectorPosition};
+use schema;
+pub use schema::BaseType;
+use schema::Type::{Scalar, String as Str, Table, Vector};
+use schema::{Element, ScalarType};
+use seek::seek_uoffset;
+use types::{SIZE_OF_LEN, SIZE_OF_UOFFSET};
+use verify::verify;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Type {
+    pub base_type: BaseType,
+    /// Only if `base_type == Vector`.
+    pub element: BaseType,
+    /// If `base_type == Obj`, index into `Schema::objects`. If `base_type == Union`, `UType`, or
+    /// integral derived from an enum, index into `Schema::enums`. Otherwise -1.
+    pub index: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumVal {
+    pub name: String,
+    pub value: i64,
+    pub object: Option<Object>,
+    pub union_type: Option<Type>,
+    pub documentation: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Enum {
+    pub name: String,
+    /// In order of their values.
+    pub values: Vec<EnumVal>,
+    pub is_union: bool,
+    pub underlying_type: Type,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+    pub id: u16,
+    /// Offset into the vtable for tables, or into the struct.
+    pub offset: u16,
+    pub default_integer: i64,
+    pub default_real: f64,
+    pub deprecated: bool,
+    pub required: bool,
+    pub key: bool,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+/// Used for both tables and structs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+    pub name: String,
+    /// Sorted by name.
+    pub fields: Vec<Field>,
+    pub is_struct: bool,
+    pub minalign: i32,
+    /// For structs.
+    pub bytesize: i32,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RPCCall {
+    pub name: String,
+    /// Must be a table.
+    pub request: Object,
+    /// Must be a table.
+    pub response: Object,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Service {
+    pub name: String,
+    pub calls: Vec<RPCCall>,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    /// Sorted by name.
+    pub objects: Vec<Object>,
+    /// Sorted by name.
+    pub enums: Vec<Enum>,
+    pub file_ident: Option<String>,
+    pub file_ext: Option<String>,
+    pub root_table: Option<Object>,
+    pub services: Vec<Service>,
+}
+
+fn find_attribute<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|kv| kv.key == key)
+        .map(|kv| kv.value.as_ref().map_or("", |v| v.as_str()))
+}
+
+impl Field {
+    /// Gets the value of the custom attribute `key`.
+    ///
+    /// Returns an empty string if the attribute has no value.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        find_attribute(&self.attributes, key)
+    }
+
+    /// The position of the field voffset inside vtable bytes.
+    pub fn slot(&self) -> usize {
+        4 + 2 * self.id as usize
+    }
+
+    /// The first protocol version which has the field, read from the attribute `since_version`.
+    ///
+    /// Returns 0 if the attribute is absent or is not a number.
+    pub fn since_version(&self) -> u32 {
+        self.attribute("since_version")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// The protocol version since which the field is deprecated, read from the attribute
+    /// `deprecated_version`.
+    ///
+    /// A field marked `deprecated` without the attribute is deprecated since version 0, that is,
+    /// in all the versions.
+    pub fn deprecated_version(&self) -> Option<u32> {
+        let version = self
+            .attribute("deprecated_version")
+            .and_then(|v| v.parse().ok());
+        if version.is_none() && self.deprecated {
+            Some(0)
+        } else {
+            version
+        }
+    }
+}
+
+impl Object {
+    /// Gets the value of the custom attribute `key`.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        find_attribute(&self.attributes, key)
+    }
+
+    /// Finds the field by name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Returns the fields ordered by id.
+    pub fn fields_by_id(&self) -> Vec<&Field> {
+        let mut fields: Vec<&Field> = self.fields.iter().collect();
+        fields.sort_by_key(|f| f.id);
+        fields
+    }
+
+    /// The size and alignment of the struct.
+    pub fn layout(&self) -> StructLayout {
+        StructLayout {
+            bytesize: self.bytesize as usize,
+            minalign: self.minalign as usize,
+        }
+    }
+}
+
+impl Enum {
+    /// Gets the value of the custom attribute `key`.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        find_attribute(&self.attributes, key)
+    }
+
+    /// Finds the enum value by value.
+    pub fn value(&self, value: i64) -> Option<&EnumVal> {
+        self.values.iter().find(|v| v.value == value)
+    }
+}
+
+impl Schema {
+    /// Reads the schema from a `.bfbs` buffer.
+    pub fn from_slice(buf: &[u8]) -> Result<Schema> {
+        let root = verify(&REFLECTION_SCHEMA, SCHEMA, buf)?;
+        Reader { buf }.schema(root)
+    }
+
+    /// Finds the object by the fully qualified name.
+    pub fn object(&self, name: &str) -> Option<&Object> {
+        self.objects.iter().find(|o| o.name == name)
+    }
+
+    /// Finds the index of the object by the fully qualified name.
+    pub fn object_index(&self, name: &str) -> Option<usize> {
+        self.objects.iter().position(|o| o.name == name)
+    }
+
+    /// Finds the enum by the fully qualified name.
+    pub fn enum_(&self, name: &str) -> Option<&Enum> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+
+    /// Describes the schema for the verifier and the other schema driven APIs, and passes the
+    /// description to `f`.
+    ///
+    /// Both `tables` and `structs` in the description are indexed by the index into
+    /// `self.objects`, and `unions` are indexed by the index into `self.enums`.
+    ///
+    /// Fails if a field has a type which refers to a missing object or union, or which is not
+    /// allowed in the object, or if a union variant is not a table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::reflection::Schema;
+    /// use blockbuffers::verify::verify_canonical;
+    ///
+    /// let bfbs = include_bytes!("../tests/common/example.bfbs");
+    /// let schema = Schema::from_slice(&bfbs[..]).unwrap();
+    /// let example = schema.object_index("example.Example").unwrap();
+    ///
+    /// let mut builder = TableBuilder::new();
+    /// builder.add_scalar(0, 1u32, 0);
+    /// let buf = builder.finish();
+    ///
+    /// let verified = schema.with_description(|desc| verify_canonical(desc, example, &buf));
+    /// assert!(verified.unwrap().is_ok());
+    /// ```
+    pub fn with_description<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&schema::Schema) -> R,
+    {
+        let table_fields = self
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(index, object)| {
+                if object.is_struct {
+                    return Ok(Vec::new());
+                }
+                object
+                    .fields_by_id()
+                    .into_iter()
+                    .map(|field| {
+                        Ok(schema::Field {
+                            name: &field.name,
+                            id: field.id,
+                            ty: self.describe_type(index, field)?,
+                            required: field.required,
+                            default_integer: field.default_integer,
+                            default_real: field.default_real,
+                            since_version: field.since_version(),
+                            deprecated_version: field.deprecated_version(),
+                            nested_flatbuffer: field
+                                .attribute("nested_flatbuffer")
+                                .and_then(|name| self.table_index(object, name)),
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<schema::Field>>>>()?;
+        let struct_fields = self
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(index, object)| {
+                if !object.is_struct {
+                    return Ok(Vec::new());
+                }
+                let mut fields = object
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        Ok(schema::StructField {
+                            name: &field.name,
+                            offset: field.offset as usize,
+                            ty: self.describe_type(index, field)?,
+                        })
+                    })
+                    .collect::<Result<Vec<schema::StructField>>>()?;
+                fields.sort_by_key(|f| f.offset);
+                Ok(fields)
+            })
+            .collect::<Result<Vec<Vec<schema::StructField>>>>()?;
+        let variants = self
+            .enums
+            .iter()
+            .enumerate()
+            .map(|(index, e)| {
+                if !e.is_union {
+                    return Ok(Vec::new());
+                }
+                e.values
+                    .iter()
+                    .filter(|v| v.value != 0)
+                    .filter_map(|v| v.union_type.as_ref().map(|ty| (v, ty)))
+                    .map(|(v, ty)| {
+                        let table = ty.index as usize;
+                        let is_table = ty.base_type == BaseType::Obj
+                            && table < self.objects.len()
+                            && !self.objects[table].is_struct;
+                        if !is_table || i64::from(v.value as u8) != v.value {
+                            return Err(Error::BadUnionVariant {
+                                index,
+                                value: v.value,
+                            });
+                        }
+                        Ok(schema::UnionVariant {
+                            name: &v.name,
+                            tag: v.value as u8,
+                            table,
+                        })
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<schema::UnionVariant>>>>()?;
+
+        let tables: Vec<schema::Table> = self
+            .objects
+            .iter()
+            .zip(table_fields.iter())
+            .map(|(object, fields)| schema::Table {
+                name: &object.name,
+                fields,
+                key: object.fields.iter().find(|f| f.key).map(|f| f.id),
+            })
+            .collect();
+        let structs: Vec<schema::Struct> = self
+            .objects
+            .iter()
+            .zip(struct_fields.iter())
+            .map(|(object, fields)| schema::Struct {
+                name: &object.name,
+                bytesize: object.bytesize as usize,
+                minalign: object.minalign as usize,
+                fields,
+            })
+            .collect();
+        let unions: Vec<schema::Union> = self
+            .enums
+            .iter()
+            .zip(variants.iter())
+            .map(|(e, variants)| schema::Union {
+                name: &e.name,
+                variants,
+            })
+            .collect();
+
+        Ok(f(&schema::Schema {
+            tables: &tables,
+            structs: &structs,
+            unions: &unions,
+        }))
+    }
+
+    /// Finds the index of the table by the name, which is relative to the namespace of `object`
+    /// or fully qualified.
+    fn table_index(&self, object: &Object, name: &str) -> Option<usize> {
+        let qualified = match object.name.rfind('.') {
+            Some(end) => format!("{}.{}", &object.name[..end], name),
+            None => name.to_string(),
+        };
+        [qualified.as_str(), name]
+            .iter()
+            .filter_map(|name| self.object_index(name))
+            .find(|&index| !self.objects[index].is_struct)
+    }
+
+    /// Describes the type of `field` in the object `object`, an index into `self.objects`.
+    ///
+    /// Struct fields can only be scalars or structs.
+    fn describe_type(&self, object: usize, field: &Field) -> Result<schema::Type> {
+        let bad_type = Error::BadFieldType {
+            object,
+            id: field.id,
+        };
+        let ty = &field.ty;
+        let index = ty.index as usize;
+        let is_union = index < self.enums.len() && self.enums[index].is_union;
+        let is_struct = self.objects.get(index).map(|o| o.is_struct);
+
+        let described = match ty.base_type {
+            BaseType::UType if is_union => schema::Type::UnionType(index),
+            BaseType::Union if is_union && field.id > 0 => schema::Type::Union(index),
+            BaseType::String => schema::Type::String,
+            BaseType::Obj => match is_struct {
+                Some(true) => schema::Type::Struct(index),
+                Some(false) => schema::Type::Table(index),
+                None => return Err(bad_type),
+            },
+            BaseType::Vector => schema::Type::Vector(match ty.element {
+                BaseType::String => Element::String,
+                BaseType::Obj => match is_struct {
+                    Some(true) => Element::Struct(index),
+                    Some(false) => Element::Table(index),
+                    None => return Err(bad_type),
+                },
+                BaseType::UType => Element::Scalar(ScalarType::UByte),
+                element => Element::Scalar(element.scalar().ok_or(bad_type)?),
+            }),
+            base_type => schema::Type::Scalar(base_type.scalar().ok_or(bad_type)?),
+        };
+
+        match described {
+            schema::Type::Scalar(_) | schema::Type::Struct(_) => Ok(described),
+            _ if self.objects[object].is_struct => Err(bad_type),
+            _ => Ok(described),
+        }
+    }
+}
+
+/// Reads the verified buffer.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn field(&self, table: TablePosition, id: usize) -> Option<usize> {
+        table.field_position(self.buf, 4 + 2 * id)
+    }
+
+    fn scalar<T: LE>(&self, table: TablePosition, id: usize, default: T) -> T {
+        match self.field(table, id) {
+            Some(pos) => T::from_le_slice(&self.buf[pos..]),
+            None => default,
+        }
+    }
+
+    fn bool(&self, table: TablePosition, id: usize) -> bool {
+        self.scalar::<u8>(table, id, 0) != 0
+    }
+
+    fn base_type(&self, table: TablePosition, id: usize) -> Result<BaseType> {
+        let value = self.scalar::<i8>(table, id, 0);
+        BaseType::from_i8(value).ok_or(Error::UnknownEnumValue {
+            pos: self.field(table, id).unwrap_or(table.0),
+            value: i64::from(value),
+        })
+    }
+
+    fn string(&self, table: TablePosition, id: usize) -> Option<String> {
+        self.field(table, id).map(|pos| {
+            StringPosition(seek_uoffset(self.buf, pos))
+                .as_str(self.buf)
+                .to_string()
+        })
+    }
+
+    fn table(&self, table: TablePosition, id: usize) -> Option<TablePosition> {
+        self.field(table, id)
+            .map(|pos| TablePosition(seek_uoffset(self.buf, pos)))
+    }
+
+    /// Returns the positions of the offsets in the vector.
+    fn vector(&self, table: TablePosition, id: usize) -> Vec<usize> {
+        match self.field(table, id) {
+            Some(pos) => {
+                let vector = VectorPosition(seek_uoffset(self.buf, pos));
+                let start = vector.0 + SIZE_OF_LEN;
+                (0..vector.len(self.buf))
+                    .map(|i| seek_uoffset(self.buf, start + i * SIZE_OF_UOFFSET))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn tables<T, F>(&self, table: TablePosition, id: usize, f: F) -> Result<Vec<T>>
+    where
+        F: Fn(&Self, TablePosition) -> Result<T>,
+    {
+        self.vector(table, id)
+            .into_iter()
+            .map(|pos| f(self, TablePosition(pos)))
+            .collect()
+    }
+
+    fn strings(&self, table: TablePosition, id: usize) -> Vec<String> {
+        self.vector(table, id)
+            .into_iter()
+            .map(|pos| StringPosition(pos).as_str(self.buf).to_string())
+            .collect()
+    }
+
+    fn required_string(&self, table: TablePosition, id: usize) -> String {
+        self.string(table, id).expect("verified required field")
+    }
+
+    fn required_table(&self, table: TablePosition, id: usize) -> TablePosition {
+        self.table(table, id).expect("verified required field")
+    }
+
+    fn ty(&self, table: TablePosition) -> Result<Type> {
+        Ok(Type {
+            base_type: self.base_type(table, 0)?,
+            element: self.base_type(table, 1)?,
+            index: self.scalar(table, 2, -1i32),
+        })
+    }
+
+    fn key_value(&self, table: TablePosition) -> Result<KeyValue> {
+        Ok(KeyValue {
+            key: self.required_string(table, 0),
+            value: self.string(table, 1),
+        })
+    }
+
+    fn enum_val(&self, table: TablePosition) -> Result<EnumVal> {
+        Ok(EnumVal {
+            name: self.required_string(table, 0),
+            value: self.scalar(table, 1, 0i64),
+            object: match self.table(table, 2) {
+                Some(object) => Some(self.object(object)?),
+                None => None,
+            },
+            union_type: match self.table(table, 3) {
+                Some(ty) => Some(self.ty(ty)?),
+                None => None,
+            },
+            documentation: self.strings(table, 4),
+        })
+    }
+
+    fn enum_(&self, table: TablePosition) -> Result<Enum> {
+        Ok(Enum {
+            name: self.required_string(table, 0),
+            values: self.tables(table, 1, Self::enum_val)?,
+            is_union: self.bool(table, 2),
+            underlying_type: self.ty(self.required_table(table, 3))?,
+            attributes: self.tables(table, 4, Self::key_value)?,
+            documentation: self.strings(table, 5),
+        })
+    }
+
+    fn field_(&self, table: TablePosition) -> Result<Field> {
+        Ok(Field {
+            name: self.required_string(table, 0),
+            ty: self.ty(self.required_table(table, 1))?,
+            id: self.scalar(table, 2, 0u16),
+            offset: self.scalar(table, 3, 0u16),
+            default_integer: self.scalar(table, 4, 0i64),
+            default_real: self.scalar(table, 5, 0f64),
+            deprecated: self.bool(table, 6),
+            required: self.bool(table, 7),
+            key: self.bool(table, 8),
+            attributes: self.tables(table, 9, Self::key_value)?,
+            documentation: self.strings(table, 10),
+        })
+    }
+
+    fn object(&self, table: TablePosition) -> Result<Object> {
+        Ok(Object {
+            name: self.required_string(table, 0),
+            fields: self.tables(table, 1, Self::field_)?,
+            is_struct: self.bool(table, 2),
+            minalign: self.scalar(table, 3, 0i32),
+            bytesize: self.scalar(table, 4, 0i32),
+            attributes: self.tables(table, 5, Self::key_value)?,
+            documentation: self.strings(table, 6),
+        })
+    }
+
+    fn rpc_call(&self, table: TablePosition) -> Result<RPCCall> {
+        Ok(RPCCall {
+            name: self.required_string(table, 0),
+            request: self.object(self.required_table(table, 1))?,
+            response: self.object(self.required_table(table, 2))?,
+            attributes: self.tables(table, 3, Self::key_value)?,
+            documentation: self.strings(table, 4),
+        })
+    }
+
+    fn service(&self, table: TablePosition) -> Result<Service> {
+        Ok(Service {
+            name: self.required_string(table, 0),
+            calls: self.tables(table, 1, Self::rpc_call)?,
+            attributes: self.tables(table, 2, Self::key_value)?,
+            documentation: self.strings(table, 3),
+        })
+    }
+
+    fn schema(&self, table: TablePosition) -> Result<Schema> {
+        Ok(Schema {
+            objects: self.tables(table, 0, Self::object)?,
+            enums: self.tables(table, 1, Self::enum_)?,
+            file_ident: self.string(table, 2),
+            file_ext: self.string(table, 3),
+            root_table: match self.table(table, 4) {
+                Some(object) => Some(self.object(object)?),
+                None => None,
+            },
+            services: self.tables(table, 5, Self::service)?,
+        })
+    }
+}
+
+const TYPE: usize = 0;
+const KEY_VALUE: usize = 1;
+const ENUM_VAL: usize = 2;
+const ENUM: usize = 3;
+const FIELD: usize = 4;
+const OBJECT: usize = 5;
+const RPC_CALL: usize = 6;
+const SERVICE: usize = 7;
+const SCHEMA: usize = 8;
+
+macro_rules! field {
+    ($name:expr, $id:expr, $ty:expr) => {
+        field!($name, $id, $ty, false, 0)
+    };
+    ($name:expr, $id:expr, $ty:expr, $required:expr) => {
+        field!($name, $id, $ty, $required, 0)
+    };
+    ($name:expr, $id:expr, $ty:expr, $required:expr, $default:expr) => {
+        schema::Field {
+            name: $name,
+            id: $id,
+            ty: $ty,
+            required: $required,
+            default_integer: $default,
+            default_real: 0.0,
+            since_version: 0,
+            deprecated_version: None,
+            nested_flatbuffer: None,
+        }
+    };
+}
+
+/// Describes `reflection.fbs`.
+pub static REFLECTION_SCHEMA: schema::Schema<'static> = schema::Schema {
+    tables: &[
+        schema::Table {
+            name: "reflection.Type",
+            fields: &[
+                field!("base_type", 0, Scalar(ScalarType::Byte)),
+                field!("element", 1, Scalar(ScalarType::Byte)),
+                field!("index", 2, Scalar(ScalarType::Int), false, -1),
+            ],
+            key: None,
+        },
+        schema::Table {
+            name: "reflection.KeyValue",
+            fields: &[field!("key", 0, Str, true), field!("value", 1, Str)],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.EnumVal",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("value", 1, Scalar(ScalarType::Long)),
+                field!("object", 2, Table(OBJECT)),
+                field!("union_type", 3, Table(TYPE)),
+                field!("documentation", 4, Vector(Element::String)),
+            ],
+            key: Some(1),
+        },
+        schema::Table {
+            name: "reflection.Enum",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("values", 1, Vector(Element::Table(ENUM_VAL)), true),
+                field!("is_union", 2, Scalar(ScalarType::Bool)),
+                field!("underlying_type", 3, Table(TYPE), true),
+                field!("attributes", 4, Vector(Element::Table(KEY_VALUE))),
+                field!("documentation", 5, Vector(Element::String)),
+            ],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.Field",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("type", 1, Table(TYPE), true),
+                field!("id", 2, Scalar(ScalarType::UShort)),
+                field!("offset", 3, Scalar(ScalarType::UShort)),
+                field!("default_integer", 4, Scalar(ScalarType::Long)),
+                field!("default_real", 5, Scalar(ScalarType::Double)),
+                field!("deprecated", 6, Scalar(ScalarType::Bool)),
+                field!("required", 7, Scalar(ScalarType::Bool)),
+                field!("key", 8, Scalar(ScalarType::Bool)),
+                field!("attributes", 9, Vector(Element::Table(KEY_VALUE))),
+                field!("documentation", 10, Vector(Element::String)),
+            ],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.Object",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("fields", 1, Vector(Element::Table(FIELD)), true),
+                field!("is_struct", 2, Scalar(ScalarType::Bool)),
+                field!("minalign", 3, Scalar(ScalarType::Int)),
+                field!("bytesize", 4, Scalar(ScalarType::Int)),
+                field!("attributes", 5, Vector(Element::Table(KEY_VALUE))),
+                field!("documentation", 6, Vector(Element::String)),
+            ],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.RPCCall",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("request", 1, Table(OBJECT), true),
+                field!("response", 2, Table(OBJECT), true),
+                field!("attributes", 3, Vector(Element::Table(KEY_VALUE))),
+                field!("documentation", 4, Vector(Element::String)),
+            ],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.Service",
+            fields: &[
+                field!("name", 0, Str, true),
+                field!("calls", 1, Vector(Element::Table(RPC_CALL))),
+                field!("attributes", 2, Vector(Element::Table(KEY_VALUE))),
+                field!("documentation", 3, Vector(Element::String)),
+            ],
+            key: Some(0),
+        },
+        schema::Table {
+            name: "reflection.Schema",
+            fields: &[
+                field!("objects", 0, Vector(Element::Table(OBJECT)), true),
+                field!("enums", 1, Vector(Element::Table(ENUM)), true),
+                field!("file_ident", 2, Str),
+                field!("file_ext", 3, Str),
+                field!("root_table", 4, Table(OBJECT)),
+                field!("services", 5, Vector(Element::Table(SERVICE))),
+            ],
+            key: None,
+        },
+    ],
+    structs: &[],
+    unions: &[],
+};
+
+/// The index of `reflection.Schema` in [`REFLECTION_SCHEMA`](static.REFLECTION_SCHEMA.html).
+pub const REFLECTION_ROOT: usize = SCHEMA;
@@ -0,0 +1,175 @@
+//! Finds the root table of a buffer.
+//!
+//! A buffer starts with a `UOffset` to its root table, optionally followed by a 4-byte file
+//! identifier, e.g., `.bfbs` files are identified by `BFBS`. Size prefixed buffers put an extra
+//! `u32` length before that, so several buffers can be framed in one stream.
+//!
+//! Functions for size prefixed buffers return the buffer without the prefix, since all positions
+//! are relative to it.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::root::{size_prefixed_root_table, try_root_table};
+//!
+//! let mut table = TableBuilder::new();
+//! table.add_scalar(0, 1u32, 0);
+//! let buf = table.finish();
+//!
+//! let root = try_root_table(&buf).unwrap();
+//! assert_eq!(Some(1u32), root.field_position(&buf, 4).map(|pos| buf[pos] as u32));
+//!
+//! let mut framed = vec![buf.len() as u8, 0, 0, 0];
+//! framed.extend_from_slice(&buf);
+//! assert_eq!((&buf[..], root), size_prefixed_root_table(&framed));
+//! ```
+
+use error::{Error, Result};
+use le::LE;
+use position::TablePosition;
+use seek::{check_alignment, check_bounds, seek_uoffset, try_read, try_seek_uoffset};
+use types::{
+    SizePrefix, FILE_IDENTIFIER_LENGTH, SIZE_OF_SIZE_PREFIX, SIZE_OF_SOFFSET, SIZE_OF_UOFFSET,
+};
+
+/// Gets the root table of the buffer.
+pub fn root_table(buf: &[u8]) -> TablePosition {
+    TablePosition(seek_uoffset(buf, 0))
+}
+
+/// Checked version of [`root_table`](fn.root_table.html).
+///
+/// Fails if the root offset is outside the buffer or is misaligned.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::root::try_root_table;
+/// use blockbuffers::Error;
+///
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 0, need: 8, buf_len: 8 }),
+///     try_root_table(&[8u8, 0, 0, 0, 0, 0, 0, 0])
+/// );
+/// ```
+pub fn try_root_table(buf: &[u8]) -> Result<TablePosition> {
+    let root = try_seek_uoffset(buf, 0)?;
+    check_alignment(root, SIZE_OF_SOFFSET)?;
+    Ok(TablePosition(root))
+}
+
+/// Checks whether the buffer has the file identifier `identifier`.
+///
+/// Returns false if the buffer is too short to have one.
+pub fn has_identifier(buf: &[u8], identifier: &[u8; FILE_IDENTIFIER_LENGTH]) -> bool {
+    buf.get(SIZE_OF_UOFFSET..SIZE_OF_UOFFSET + FILE_IDENTIFIER_LENGTH) == Some(&identifier[..])
+}
+
+/// Gets the root table of the buffer if it has the file identifier `identifier`.
+pub fn root_table_with_identifier(
+    buf: &[u8],
+    identifier: &[u8; FILE_IDENTIFIER_LENGTH],
+) -> Option<TablePosition> {
+    if has_identifier(buf, identifier) {
+        Some(root_table(buf))
+    } else {
+        None
+    }
+}
+
+/// Checked version of [`root_table_with_identifier`](fn.root_table_with_identifier.html).
+///
+/// Fails if the file identifier does not match, or the root offset is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::root::try_root_table_with_identifier;
+/// use blockbuffers::Error;
+///
+/// let buf = &[8u8, 0, 0, 0, b'B', b'F', b'B', b'S', 0, 0, 0, 0][..];
+///
+/// assert_eq!(8, try_root_table_with_identifier(buf, b"BFBS").unwrap().0);
+/// assert_eq!(
+///     Err(Error::IdentifierMismatch { pos: 4 }),
+///     try_root_table_with_identifier(buf, b"BFBX")
+/// );
+/// ```
+pub fn try_root_table_with_identifier(
+    buf: &[u8],
+    identifier: &[u8; FILE_IDENTIFIER_LENGTH],
+) -> Result<TablePosition> {
+    check_bounds(buf, SIZE_OF_UOFFSET, FILE_IDENTIFIER_LENGTH)?;
+    if !has_identifier(buf, identifier) {
+        return Err(Error::IdentifierMismatch {
+            pos: SIZE_OF_UOFFSET,
+        });
+    }
+    try_root_table(buf)
+}
+
+/// Gets the buffer after the size prefix.
+///
+/// Bytes after the size are ignored, which may be the following buffers in the stream.
+pub fn size_prefixed_buffer(buf: &[u8]) -> &[u8] {
+    let size = SizePrefix::from_le_slice(buf) as usize;
+    &buf[SIZE_OF_SIZE_PREFIX..SIZE_OF_SIZE_PREFIX + size]
+}
+
+/// Checked version of [`size_prefixed_buffer`](fn.size_prefixed_buffer.html).
+///
+/// Fails if the size prefix cannot be read or the size exceeds the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::root::try_size_prefixed_buffer;
+/// use blockbuffers::Error;
+///
+/// assert_eq!(Ok(&[1u8][..]), try_size_prefixed_buffer(&[1u8, 0, 0, 0, 1, 2]));
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 4, need: 3, buf_len: 6 }),
+///     try_size_prefixed_buffer(&[3u8, 0, 0, 0, 1, 2])
+/// );
+/// ```
+pub fn try_size_prefixed_buffer(buf: &[u8]) -> Result<&[u8]> {
+    let size = try_read::<SizePrefix>(buf, 0)? as usize;
+    check_bounds(buf, SIZE_OF_SIZE_PREFIX, size)?;
+    Ok(&buf[SIZE_OF_SIZE_PREFIX..SIZE_OF_SIZE_PREFIX + size])
+}
+
+/// Gets the buffer after the size prefix and its root table.
+pub fn size_prefixed_root_table(buf: &[u8]) -> (&[u8], TablePosition) {
+    let buf = size_prefixed_buffer(buf);
+    (buf, root_table(buf))
+}
+
+/// Checked version of [`size_prefixed_root_table`](fn.size_prefixed_root_table.html).
+///
+/// The positions in the errors are relative to the buffer after the size prefix, except for the
+/// errors of the prefix itself.
+pub fn try_size_prefixed_root_table(buf: &[u8]) -> Result<(&[u8], TablePosition)> {
+    let buf = try_size_prefixed_buffer(buf)?;
+    Ok((buf, try_root_table(buf)?))
+}
+
+/// Gets the buffer after the size prefix and its root table if it has the file identifier
+/// `identifier`.
+pub fn size_prefixed_root_table_with_identifier<'a>(
+    buf: &'a [u8],
+    identifier: &[u8; FILE_IDENTIFIER_LENGTH],
+) -> Option<(&'a [u8], TablePosition)> {
+    let buf = size_prefixed_buffer(buf);
+    root_table_with_identifier(buf, identifier).map(|root| (buf, root))
+}
+
+/// Checked version of
+/// [`size_prefixed_root_table_with_identifier`](fn.size_prefixed_root_table_with_identifier.html).
+pub fn try_size_prefixed_root_table_with_identifier<'a>(
+    buf: &'a [u8],
+    identifier: &[u8; FILE_IDENTIFIER_LENGTH],
+) -> Result<(&'a [u8], TablePosition)> {
+    let buf = try_size_prefixed_buffer(buf)?;
+    Ok((buf, try_root_table_with_identifier(buf, identifier)?))
+}
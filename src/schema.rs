@@ -0,0 +1,300 @@
+//! Static description of a schema, used to walk buffers without generated code.
+//!
+//! All the descriptions borrow their children as slices, so a schema can be declared as `static`
+//! items and used without an allocator. Tables, structs and unions refer to each other by their
+//! index in [`Schema`](struct.Schema.html).
+
+use position::StructLayout;
+use types::SIZE_OF_UOFFSET;
+
+/// Base types of the fields, as stored in `.bfbs` files.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BaseType {
+    None,
+    UType,
+    Bool,
+    Byte,
+    UByte,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+    Vector,
+    /// Used for tables and structs.
+    Obj,
+    Union,
+}
+
+impl BaseType {
+    /// Converts from the value stored in the buffer.
+    pub fn from_i8(value: i8) -> Option<BaseType> {
+        let base_type = match value {
+            0 => BaseType::None,
+            1 => BaseType::UType,
+            2 => BaseType::Bool,
+            3 => BaseType::Byte,
+            4 => BaseType::UByte,
+            5 => BaseType::Short,
+            6 => BaseType::UShort,
+            7 => BaseType::Int,
+            8 => BaseType::UInt,
+            9 => BaseType::Long,
+            10 => BaseType::ULong,
+            11 => BaseType::Float,
+            12 => BaseType::Double,
+            13 => BaseType::String,
+            14 => BaseType::Vector,
+            15 => BaseType::Obj,
+            16 => BaseType::Union,
+            _ => return None,
+        };
+        Some(base_type)
+    }
+
+    /// Returns the scalar type if this is a scalar other than `UType`.
+    pub fn scalar(self) -> Option<ScalarType> {
+        match self {
+            BaseType::Bool => Some(ScalarType::Bool),
+            BaseType::Byte => Some(ScalarType::Byte),
+            BaseType::UByte => Some(ScalarType::UByte),
+            BaseType::Short => Some(ScalarType::Short),
+            BaseType::UShort => Some(ScalarType::UShort),
+            BaseType::Int => Some(ScalarType::Int),
+            BaseType::UInt => Some(ScalarType::UInt),
+            BaseType::Long => Some(ScalarType::Long),
+            BaseType::ULong => Some(ScalarType::ULong),
+            BaseType::Float => Some(ScalarType::Float),
+            BaseType::Double => Some(ScalarType::Double),
+            _ => None,
+        }
+    }
+}
+
+/// Scalar types which can be stored inline in tables, structs and vectors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScalarType {
+    Bool,
+    Byte,
+    UByte,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+}
+
+impl ScalarType {
+    /// Size of the scalar in bytes, which is also its alignment.
+    pub fn size(self) -> usize {
+        match self {
+            ScalarType::Bool | ScalarType::Byte | ScalarType::UByte => 1,
+            ScalarType::Short | ScalarType::UShort => 2,
+            ScalarType::Int | ScalarType::UInt | ScalarType::Float => 4,
+            ScalarType::Long | ScalarType::ULong | ScalarType::Double => 8,
+        }
+    }
+}
+
+/// Type of a field in a table or a struct.
+///
+/// Struct fields can only be `Scalar` or `Struct`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Scalar(ScalarType),
+    /// Inline struct, the index into `Schema::structs`.
+    Struct(usize),
+    String,
+    /// Offset to a table, the index into `Schema::tables`.
+    Table(usize),
+    Vector(Element),
+    /// The type tag of a union, the index into `Schema::unions`.
+    UnionType(usize),
+    /// Offset to the union value, the index into `Schema::unions`.
+    ///
+    /// The type tag is stored in the field which id is one less than this field.
+    Union(usize),
+}
+
+/// Type of the elements in a vector.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Element {
+    Scalar(ScalarType),
+    Struct(usize),
+    String,
+    Table(usize),
+}
+
+/// Describes a schema.
+#[derive(Copy, Clone, Debug)]
+pub struct Schema<'a> {
+    pub tables: &'a [Table<'a>],
+    pub structs: &'a [Struct<'a>],
+    pub unions: &'a [Union<'a>],
+}
+
+/// Describes a table.
+#[derive(Copy, Clone, Debug)]
+pub struct Table<'a> {
+    pub name: &'a str,
+    /// Fields ordered by id.
+    pub fields: &'a [Field<'a>],
+    /// The id of the field which has the attribute `key`. Vectors of this table must be sorted
+    /// by the key field strictly.
+    pub key: Option<u16>,
+}
+
+impl<'a> Table<'a> {
+    /// Gets the field which has the attribute `key`.
+    pub fn key_field(&self) -> Option<&Field<'a>> {
+        self.key
+            .and_then(|id| self.fields.iter().find(|field| field.id == id))
+    }
+}
+
+/// Describes a field in a table.
+#[derive(Copy, Clone, Debug)]
+pub struct Field<'a> {
+    pub name: &'a str,
+    pub id: u16,
+    pub ty: Type,
+    pub required: bool,
+    /// The default value for integer and bool scalars.
+    pub default_integer: i64,
+    /// The default value for float scalars.
+    pub default_real: f64,
+    /// The first protocol version which has the field, 0 if the field is in all the versions.
+    pub since_version: u32,
+    /// The protocol version since which the field is deprecated, `None` if the field is not
+    /// deprecated.
+    pub deprecated_version: Option<u32>,
+    /// The root table of the nested buffer stored in this `[ubyte]` field, the index into
+    /// `Schema::tables`, set by the attribute `nested_flatbuffer`.
+    pub nested_flatbuffer: Option<usize>,
+}
+
+impl<'a> Field<'a> {
+    /// The position of the field voffset inside vtable bytes.
+    pub fn slot(&self) -> usize {
+        4 + 2 * self.id as usize
+    }
+
+    /// Checks whether the field is valid in the protocol version `version`.
+    ///
+    /// A field is valid from `since_version` until the version it is deprecated in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::schema::{Field, ScalarType, Type};
+    ///
+    /// let lines = Field {
+    ///     name: "lines",
+    ///     id: 0,
+    ///     ty: Type::Scalar(ScalarType::UInt),
+    ///     required: false,
+    ///     default_integer: 0,
+    ///     default_real: 0.0,
+    ///     since_version: 2,
+    ///     deprecated_version: Some(4),
+    ///     nested_flatbuffer: None,
+    /// };
+    ///
+    /// assert!(!lines.is_in_version(1));
+    /// assert!(lines.is_in_version(2));
+    /// assert!(lines.is_in_version(3));
+    /// assert!(!lines.is_in_version(4));
+    /// ```
+    pub fn is_in_version(&self, version: u32) -> bool {
+        version >= self.since_version && self.deprecated_version.map_or(true, |v| version < v)
+    }
+}
+
+/// Describes a struct.
+#[derive(Copy, Clone, Debug)]
+pub struct Struct<'a> {
+    pub name: &'a str,
+    pub bytesize: usize,
+    pub minalign: usize,
+    /// Fields ordered by offset.
+    pub fields: &'a [StructField<'a>],
+}
+
+impl<'a> Struct<'a> {
+    /// The size and alignment of the struct.
+    pub fn layout(&self) -> StructLayout {
+        StructLayout {
+            bytesize: self.bytesize,
+            minalign: self.minalign,
+        }
+    }
+}
+
+/// Describes a field in a struct.
+#[derive(Copy, Clone, Debug)]
+pub struct StructField<'a> {
+    pub name: &'a str,
+    /// Offset in bytes from the start of the struct.
+    pub offset: usize,
+    pub ty: Type,
+}
+
+/// Describes a union.
+#[derive(Copy, Clone, Debug)]
+pub struct Union<'a> {
+    pub name: &'a str,
+    /// All the variants except `NONE`.
+    pub variants: &'a [UnionVariant<'a>],
+}
+
+/// Describes a union variant.
+#[derive(Copy, Clone, Debug)]
+pub struct UnionVariant<'a> {
+    pub name: &'a str,
+    pub tag: u8,
+    /// The index into `Schema::tables`.
+    pub table: usize,
+}
+
+impl<'a> Union<'a> {
+    /// Finds the variant by the type tag.
+    pub fn variant(&self, tag: u8) -> Option<&UnionVariant<'a>> {
+        self.variants.iter().find(|v| v.tag == tag)
+    }
+}
+
+impl<'a> Schema<'a> {
+    /// Returns the size and alignment of a field stored inline in a table or a struct.
+    pub fn inline_size_and_align(&self, ty: Type) -> (usize, usize) {
+        match ty {
+            Type::Scalar(scalar) => (scalar.size(), scalar.size()),
+            Type::UnionType(_) => (1, 1),
+            Type::Struct(index) => {
+                let st = &self.structs[index];
+                (st.bytesize, st.minalign)
+            }
+            Type::String | Type::Table(_) | Type::Vector(_) | Type::Union(_) => {
+                (SIZE_OF_UOFFSET, SIZE_OF_UOFFSET)
+            }
+        }
+    }
+
+    /// Returns the size and alignment of an element stored inline in a vector.
+    pub fn element_size_and_align(&self, element: Element) -> (usize, usize) {
+        match element {
+            Element::Scalar(scalar) => (scalar.size(), scalar.size()),
+            Element::Struct(index) => {
+                let st = &self.structs[index];
+                (st.bytesize, st.minalign)
+            }
+            Element::String | Element::Table(_) => (SIZE_OF_UOFFSET, SIZE_OF_UOFFSET),
+        }
+    }
+}
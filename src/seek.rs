@@ -1,3 +1,5 @@
+use core::mem::size_of;
+use error::{Error, Result};
 use le::LE;
 use types::{SOffset, UOffset};
 
@@ -31,3 +33,148 @@ pub fn seek_uoffset(buf: &[u8], pos: usize) -> usize {
     let offset = UOffset::from_le_slice(&buf[pos..]);
     pos + offset as usize
 }
+
+/// Checks that `need` bytes starting at `pos` are inside `buf`.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::seek::check_bounds;
+/// use blockbuffers::Error;
+///
+/// assert_eq!(Ok(()), check_bounds(&[0u8; 4], 2, 2));
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 2, need: 3, buf_len: 4 }),
+///     check_bounds(&[0u8; 4], 2, 3)
+/// );
+/// ```
+pub fn check_bounds(buf: &[u8], pos: usize, need: usize) -> Result<()> {
+    match pos.checked_add(need) {
+        Some(end) if end <= buf.len() => Ok(()),
+        _ => Err(Error::OutOfBounds {
+            pos,
+            need,
+            buf_len: buf.len(),
+        }),
+    }
+}
+
+/// Checks that `pos` is a multiple of `align`.
+///
+/// Positions are relative to the start of the buffer, the buffer itself is expected to be
+/// aligned by the caller.
+pub fn check_alignment(pos: usize, align: usize) -> Result<()> {
+    if pos % align == 0 {
+        Ok(())
+    } else {
+        Err(Error::Misaligned { pos, align })
+    }
+}
+
+/// Returns the smallest position which is not less than `pos` and is a multiple of `align`.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::seek::align_up;
+///
+/// assert_eq!(8, align_up(5, 4));
+/// assert_eq!(8, align_up(8, 4));
+/// ```
+pub fn align_up(pos: usize, align: usize) -> usize {
+    (pos + align - 1) / align * align
+}
+
+/// Reads a scalar from `buf` at `pos`, checking bounds, that `pos` is aligned to the size of
+/// the scalar, and that the value is valid for the type.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::seek::try_read;
+/// use blockbuffers::Error;
+///
+/// let buf = &[1u8, 0, 2, 0][..];
+/// assert_eq!(Ok(2u16), try_read::<u16>(buf, 2));
+/// assert_eq!(Err(Error::Misaligned { pos: 1, align: 2 }), try_read::<u16>(buf, 1));
+/// assert_eq!(Err(Error::InvalidBool { pos: 2 }), try_read::<bool>(buf, 2));
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 4, need: 2, buf_len: 4 }),
+///     try_read::<u16>(buf, 4)
+/// );
+/// ```
+pub fn try_read<T: LE>(buf: &[u8], pos: usize) -> Result<T> {
+    check_alignment(pos, size_of::<T>())?;
+    check_bounds(buf, pos, size_of::<T>())?;
+    // Bounds are checked, so only invalid values can fail, which positions are relative to `pos`.
+    T::try_from_le_bytes(&buf[pos..]).map_err(|err| match err {
+        Error::InvalidBool { .. } => Error::InvalidBool { pos },
+        Error::UnknownEnumValue { value, .. } => Error::UnknownEnumValue { pos, value },
+        err => err,
+    })
+}
+
+/// Checked version of [`seek_soffset`](fn.seek_soffset.html).
+///
+/// Fails if the `SOffset` cannot be read or the new position is outside the buffer. When the new
+/// position is past the end, the error reports the bytes from `pos` to the new position.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::seek::try_seek_soffset;
+/// use blockbuffers::Error;
+///
+/// assert_eq!(Ok(4), try_seek_soffset(&[252, 255, 255, 255, 0, 0, 0, 0], 0));
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 0, need: 5, buf_len: 4 }),
+///     try_seek_soffset(&[252, 255, 255, 255], 0)
+/// );
+/// assert_eq!(
+///     Err(Error::OffsetBeforeStart { pos: 0 }),
+///     try_seek_soffset(&[4, 0, 0, 0], 0)
+/// );
+/// ```
+pub fn try_seek_soffset(buf: &[u8], pos: usize) -> Result<usize> {
+    let offset = try_read::<SOffset>(buf, pos)?;
+    let new_pos = pos as i64 - i64::from(offset);
+    if new_pos < 0 {
+        return Err(Error::OffsetBeforeStart { pos });
+    }
+    if new_pos as u64 >= buf.len() as u64 {
+        return Err(Error::OutOfBounds {
+            pos,
+            need: (new_pos - pos as i64) as usize + 1,
+            buf_len: buf.len(),
+        });
+    }
+    Ok(new_pos as usize)
+}
+
+/// Checked version of [`seek_uoffset`](fn.seek_uoffset.html).
+///
+/// Fails if the `UOffset` cannot be read or the new position is outside the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::seek::try_seek_uoffset;
+/// use blockbuffers::Error;
+///
+/// assert_eq!(Ok(4), try_seek_uoffset(&[4u8, 0, 0, 0, 0], 0));
+/// assert_eq!(
+///     Err(Error::OutOfBounds { pos: 0, need: 8, buf_len: 5 }),
+///     try_seek_uoffset(&[8u8, 0, 0, 0, 0], 0)
+/// );
+/// ```
+pub fn try_seek_uoffset(buf: &[u8], pos: usize) -> Result<usize> {
+    let offset = try_read::<UOffset>(buf, pos)? as usize;
+    match pos.checked_add(offset) {
+        Some(new_pos) if new_pos < buf.len() => Ok(new_pos),
+        _ => Err(Error::OutOfBounds {
+            pos,
+            need: offset,
+            buf_len: buf.len(),
+        }),
+    }
+}
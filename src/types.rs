@@ -1,4 +1,4 @@
-use std::mem::size_of;
+use core::mem::size_of;
 
 /// Unsigned offset used for refernce to table, vector and string.
 pub type UOffset = u32;
@@ -8,11 +8,16 @@ pub type SOffset = i32;
 pub type VOffset = u16;
 /// Length of vector and string.
 pub type Len = u32;
+/// Length prefix of size prefixed buffers.
+pub type SizePrefix = u32;
 
 pub const SIZE_OF_UOFFSET: usize = size_of::<UOffset>();
 pub const SIZE_OF_SOFFSET: usize = size_of::<SOffset>();
 pub const SIZE_OF_VOFFSET: usize = size_of::<VOffset>();
 pub const SIZE_OF_LEN: usize = size_of::<Len>();
+pub const SIZE_OF_SIZE_PREFIX: usize = size_of::<SizePrefix>();
+/// Length of the file identifier which follows the root offset.
+pub const FILE_IDENTIFIER_LENGTH: usize = 4;
 
 #[cfg(test)]
 mod tests {
@@ -24,5 +29,6 @@ mod tests {
         assert_eq!(4, SIZE_OF_SOFFSET);
         assert_eq!(2, SIZE_OF_VOFFSET);
         assert_eq!(4, SIZE_OF_LEN);
+        assert_eq!(4, SIZE_OF_SIZE_PREFIX);
     }
 }
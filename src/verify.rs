@@ -0,0 +1,1210 @@
+//! Verifies that a buffer is a valid Flatbuffers binary for a schema.
+//!
+//! The verifier walks every table, vtable, vector, string, struct and union reachable from the
+//! root, and checks that all of them are inside the buffer and properly aligned, and that bools
+//! are 0 or 1. After a buffer passes the verification, the panicking accessors in
+//! [`position`](../position/index.html) can be used on any field described in the schema.
+//!
+//! The schema does not describe enums, which are stored as their underlying integers, so the
+//! verifier accepts any value for them. Closed enums decoded by `LE::from_le_slice` panic on
+//! values they do not define, so read them using the checked accessors, or use open enums.
+//!
+//! The vectors of tables which have a `key` field must be sorted by the key strictly, so that
+//! [`VectorPosition::lookup_by_key`](../position/struct.VectorPosition.html#method.lookup_by_key)
+//! can search them. The `[ubyte]` fields which have
+//! [`Field::nested_flatbuffer`](../schema/struct.Field.html#structfield.nested_flatbuffer) are
+//! verified as buffers of the nested root table, which count towards the same depth and table
+//! limits.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::schema::{Field, ScalarType, Schema, Table, Type};
+//! use blockbuffers::verify::verify;
+//! use blockbuffers::Error;
+//!
+//! // table Monster { hp: short; name: string (required); }
+//! static MONSTER_FIELDS: &[Field] = &[
+//!     Field {
+//!         name: "hp",
+//!         id: 0,
+//!         ty: Type::Scalar(ScalarType::Short),
+//!         required: false,
+//!         default_integer: 0,
+//!         default_real: 0.0,
+//!         since_version: 0,
+//!         deprecated_version: None,
+//!         nested_flatbuffer: None,
+//!     },
+//!     Field {
+//!         name: "name",
+//!         id: 1,
+//!         ty: Type::String,
+//!         required: true,
+//!         default_integer: 0,
+//!         default_real: 0.0,
+//!         since_version: 0,
+//!         deprecated_version: None,
+//!         nested_flatbuffer: None,
+//!     },
+//! ];
+//! let schema = Schema {
+//!     tables: &[Table { name: "Monster", fields: MONSTER_FIELDS, key: None }],
+//!     structs: &[],
+//!     unions: &[],
+//! };
+//!
+//! let buf = &[
+//!     12u8, 0, 0, 0,              // root offset
+//!     8, 0, 10, 0, 8, 0, 4, 0,    // vtable
+//!     8, 0, 0, 0,                 // table, offset to vtable
+//!     8, 0, 0, 0,                 // name
+//!     100, 0, 0, 0,               // hp and padding
+//!     3, 0, 0, 0, b'o', b'r', b'c', 0,
+//! ][..];
+//!
+//! assert_eq!(Ok(12), verify(&schema, 0, buf).map(|pos| pos.0));
+//! assert_eq!(Err(Error::MissingNul { pos: 24 }), verify(&schema, 0, &buf[..31]));
+//! ```
+//!
+//! # Canonical Form
+//!
+//! A value has many valid Flatbuffers encodings. [`verify_canonical`](fn.verify_canonical.html)
+//! accepts only the unique canonical one, which is defined by the following rules.
+//!
+//! - The buffer starts with the root `UOffset`. All the objects follow in depth-first pre-order
+//!   from the root table. Each object is placed at the first position after the previous object
+//!   which satisfies its alignment, and the gap is filled with zeros. The buffer ends right after
+//!   the last object.
+//! - Every table is preceded by its own vtable, aligned to 2. The table follows at the next
+//!   position aligned to 4.
+//! - The vtable has entries up to the last present field, so the last entry is never 0. The
+//!   table size in the vtable ends at the last field.
+//! - The table fields are laid out in the order of their ids after the `SOffset`. Each field is
+//!   placed at the next position aligned to its size, or the struct alignment.
+//! - The strings, vectors, tables and union values referenced by a table follow the table in the
+//!   order of field ids. Those referenced by a vector follow the vector in the order of elements.
+//! - Strings are aligned to 4. The length of a vector is placed at the first position aligned to
+//!   4 so that the elements are aligned as well.
+//! - Scalar fields equal to the default and union type `NONE` are omitted. Struct padding is
+//!   zero. Fields which are not in the schema are not allowed.
+//! - The nested buffer in a `[ubyte]` field which has `nested_flatbuffer` is in the canonical
+//!   form as a standalone buffer.
+
+use core::cmp::Ordering;
+use error::{Error, Result};
+use le::LE;
+use position::{
+    NestedBufferPosition, StringPosition, TablePosition, VTablePosition, VectorPosition,
+};
+use schema::ScalarType;
+use schema::{Element, Field, Schema, Type};
+use seek::{align_up, check_alignment, check_bounds, seek_uoffset, try_read, try_seek_uoffset};
+use types::{SIZE_OF_LEN, SIZE_OF_SOFFSET, SIZE_OF_UOFFSET, SIZE_OF_VOFFSET};
+
+/// The default limit of nested tables.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+/// The default limit of tables in a buffer.
+pub const DEFAULT_MAX_TABLES: usize = 1_000_000;
+
+/// Limits the resources used by the verifier.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// The max depth of nested tables.
+    pub max_depth: usize,
+    /// The max number of tables to visit.
+    pub max_tables: usize,
+    /// The protocol version which the buffer is written in.
+    ///
+    /// If set, fields which are not valid in the version, see
+    /// [`Field::is_in_version`](../schema/struct.Field.html#method.is_in_version), must be
+    /// absent.
+    pub version: Option<u32>,
+    /// If set, fields which are not in the schema must be absent.
+    pub deny_unknown_fields: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_tables: DEFAULT_MAX_TABLES,
+            version: None,
+            deny_unknown_fields: false,
+        }
+    }
+}
+
+/// Verifies the buffer which root is the table `root`, an index into `schema.tables`.
+///
+/// Returns the position of the root table.
+pub fn verify(schema: &Schema, root: usize, buf: &[u8]) -> Result<TablePosition> {
+    verify_with_options(schema, root, buf, Options::default())
+}
+
+/// Verifies the buffer with the specified limits.
+pub fn verify_with_options(
+    schema: &Schema,
+    root: usize,
+    buf: &[u8],
+    options: Options,
+) -> Result<TablePosition> {
+    let pos = TablePosition(try_seek_uoffset(buf, 0)?);
+    verify_table_with_options(schema, root, buf, pos, options)?;
+    Ok(pos)
+}
+
+/// Verifies the table at `pos` and all the objects reachable from it.
+///
+/// Parameter `table` is the index into `schema.tables`.
+pub fn verify_table(schema: &Schema, table: usize, buf: &[u8], pos: TablePosition) -> Result<()> {
+    verify_table_with_options(schema, table, buf, pos, Options::default())
+}
+
+/// Verifies the table at `pos` with the specified limits.
+pub fn verify_table_with_options(
+    schema: &Schema,
+    table: usize,
+    buf: &[u8],
+    pos: TablePosition,
+    options: Options,
+) -> Result<()> {
+    let mut verifier = Verifier {
+        schema,
+        buf,
+        options,
+        depth: 0,
+        tables: 0,
+    };
+    verifier.table(table, pos)
+}
+
+struct Verifier<'a> {
+    schema: &'a Schema<'a>,
+    buf: &'a [u8],
+    options: Options,
+    depth: usize,
+    tables: usize,
+}
+
+impl<'a> Verifier<'a> {
+    fn table(&mut self, index: usize, pos: TablePosition) -> Result<()> {
+        if self.depth >= self.options.max_depth {
+            return Err(Error::DepthLimitExceeded { pos: pos.0 });
+        }
+        self.tables += 1;
+        if self.tables > self.options.max_tables {
+            return Err(Error::TableLimitExceeded { pos: pos.0 });
+        }
+
+        let vtable = pos.try_vtable(self.buf)?;
+        let table_len = vtable.try_table_bytes_len(self.buf)?;
+        if self.options.deny_unknown_fields {
+            self.unknown_fields(index, pos, vtable)?;
+        }
+
+        let schema = self.schema;
+        self.depth += 1;
+        for field in schema.tables[index].fields {
+            self.field(pos, vtable, table_len, field)?;
+        }
+        self.depth -= 1;
+
+        Ok(())
+    }
+
+    /// Checks that the table has no fields which are not in the schema.
+    fn unknown_fields(
+        &self,
+        index: usize,
+        table: TablePosition,
+        vtable: VTablePosition,
+    ) -> Result<()> {
+        let fields = self.schema.tables[index].fields;
+        let vtable_len = vtable.try_vtable_bytes_len(self.buf)?;
+        for slot in (2 * SIZE_OF_VOFFSET..vtable_len).step_by(SIZE_OF_VOFFSET) {
+            let id = ((slot - 2 * SIZE_OF_VOFFSET) / SIZE_OF_VOFFSET) as u16;
+            if vtable.try_field_offset(self.buf, slot)? != 0 && fields.iter().all(|f| f.id != id) {
+                return Err(Error::UnknownField { pos: table.0, id });
+            }
+        }
+        Ok(())
+    }
+
+    fn field(
+        &mut self,
+        table: TablePosition,
+        vtable: VTablePosition,
+        table_len: usize,
+        field: &Field,
+    ) -> Result<()> {
+        let offset = vtable.try_field_offset(self.buf, field.slot())? as usize;
+        if let Some(version) = self.options.version {
+            if !field.is_in_version(version) {
+                return if offset == 0 {
+                    Ok(())
+                } else {
+                    Err(Error::FieldNotInVersion {
+                        pos: table.0,
+                        id: field.id,
+                        version,
+                    })
+                };
+            }
+        }
+        if let Type::Union(index) = field.ty {
+            return self.union(table, vtable, table_len, field, index, offset);
+        }
+        if offset == 0 {
+            return if field.required {
+                Err(Error::RequiredFieldMissing {
+                    pos: table.0,
+                    id: field.id,
+                })
+            } else {
+                Ok(())
+            };
+        }
+
+        let pos = self.inline_field(table, vtable, table_len, field.ty, offset)?;
+        match field.ty {
+            Type::Scalar(scalar) => self.scalar(scalar, pos),
+            Type::Struct(index) => self.struct_scalars(index, pos),
+            Type::UnionType(_) | Type::Union(_) => Ok(()),
+            Type::String => {
+                let target = try_seek_uoffset(self.buf, pos)?;
+                self.string(target)
+            }
+            Type::Table(index) => {
+                let target = try_seek_uoffset(self.buf, pos)?;
+                self.table(index, TablePosition(target))
+            }
+            Type::Vector(element) => {
+                let target = try_seek_uoffset(self.buf, pos)?;
+                self.vector(element, target)?;
+                match field.nested_flatbuffer {
+                    Some(index) => self.nested_flatbuffer(index, target),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Verifies the nested buffer in the `[ubyte]` vector at `pos`, which root is the table
+    /// `index`.
+    ///
+    /// The positions in the errors are relative to the nested buffer.
+    fn nested_flatbuffer(&mut self, index: usize, pos: usize) -> Result<()> {
+        let outer = self.buf;
+        let (nested, root) = NestedBufferPosition(VectorPosition(pos)).try_root(outer)?;
+        self.buf = nested;
+        let result = self.table(index, root);
+        self.buf = outer;
+        result
+    }
+
+    /// Checks the inline bytes of a field are inside the table, and returns the field position.
+    fn inline_field(
+        &self,
+        table: TablePosition,
+        vtable: VTablePosition,
+        table_len: usize,
+        ty: Type,
+        offset: usize,
+    ) -> Result<usize> {
+        let (size, align) = self.schema.inline_size_and_align(ty);
+        if offset + size > table_len {
+            return Err(Error::BadVTable { pos: vtable.0 });
+        }
+        let pos = table.0 + offset;
+        check_alignment(pos, align)?;
+        Ok(pos)
+    }
+
+    fn union(
+        &mut self,
+        table: TablePosition,
+        vtable: VTablePosition,
+        table_len: usize,
+        field: &Field,
+        index: usize,
+        offset: usize,
+    ) -> Result<()> {
+        let tag_offset = vtable.try_field_offset(self.buf, field.slot() - 2)? as usize;
+        let tag = if tag_offset != 0 {
+            try_read::<u8>(self.buf, table.0 + tag_offset)?
+        } else {
+            0
+        };
+
+        match (tag, offset) {
+            (0, 0) => {
+                if field.required {
+                    Err(Error::RequiredFieldMissing {
+                        pos: table.0,
+                        id: field.id,
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+            (0, _) | (_, 0) => Err(Error::BadUnion { pos: table.0 }),
+            (tag, offset) => {
+                let variant_table = match self.schema.unions[index].variant(tag) {
+                    Some(variant) => variant.table,
+                    None => return Err(Error::UnknownUnionType { pos: table.0, tag }),
+                };
+                let pos = self.inline_field(table, vtable, table_len, field.ty, offset)?;
+                let target = try_seek_uoffset(self.buf, pos)?;
+                self.table(variant_table, TablePosition(target))
+            }
+        }
+    }
+
+    fn vector(&mut self, element: Element, pos: usize) -> Result<()> {
+        check_alignment(pos, SIZE_OF_LEN)?;
+        let len = VectorPosition(pos).try_len(self.buf)?;
+        let (size, align) = self.schema.element_size_and_align(element);
+        let start = pos + SIZE_OF_LEN;
+        check_alignment(start, align)?;
+        let bytes_len = len.checked_mul(size).ok_or(Error::OutOfBounds {
+            pos: start,
+            need: usize::max_value(),
+            buf_len: self.buf.len(),
+        })?;
+        check_bounds(self.buf, start, bytes_len)?;
+
+        match element {
+            Element::Scalar(scalar) => {
+                if scalar == ScalarType::Bool {
+                    for i in 0..len {
+                        self.scalar(scalar, start + i)?;
+                    }
+                }
+                Ok(())
+            }
+            Element::Struct(index) => {
+                for i in 0..len {
+                    self.struct_scalars(index, start + i * size)?;
+                }
+                Ok(())
+            }
+            Element::String => {
+                for i in 0..len {
+                    let target = try_seek_uoffset(self.buf, start + i * size)?;
+                    self.string(target)?;
+                }
+                Ok(())
+            }
+            Element::Table(index) => {
+                let schema = self.schema;
+                let key = schema.tables[index].key_field();
+                let mut previous = None;
+                for i in 0..len {
+                    let target = TablePosition(try_seek_uoffset(self.buf, start + i * size)?);
+                    self.table(index, target)?;
+                    if let (Some(key), Some(previous)) = (key, previous) {
+                        self.key_order(key, previous, target)?;
+                    }
+                    previous = Some(target);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks the key field of the table `next` is greater than that of the table `previous`.
+    fn key_order(&self, key: &Field, previous: TablePosition, next: TablePosition) -> Result<()> {
+        let buf = self.buf;
+        let slot = key.slot();
+        macro_rules! compare {
+            ($ty:ty, $default:expr) => {{
+                let default: $ty = $default;
+                previous
+                    .try_read_field(buf, slot, default)?
+                    .partial_cmp(&next.try_read_field(buf, slot, default)?)
+            }};
+        }
+
+        let ordering = match key.ty {
+            Type::Scalar(ScalarType::Bool) => compare!(bool, key.default_integer != 0),
+            Type::Scalar(ScalarType::Byte) => compare!(i8, key.default_integer as i8),
+            Type::Scalar(ScalarType::UByte) => compare!(u8, key.default_integer as u8),
+            Type::Scalar(ScalarType::Short) => compare!(i16, key.default_integer as i16),
+            Type::Scalar(ScalarType::UShort) => compare!(u16, key.default_integer as u16),
+            Type::Scalar(ScalarType::Int) => compare!(i32, key.default_integer as i32),
+            Type::Scalar(ScalarType::UInt) => compare!(u32, key.default_integer as u32),
+            Type::Scalar(ScalarType::Long) => compare!(i64, key.default_integer),
+            Type::Scalar(ScalarType::ULong) => compare!(u64, key.default_integer as u64),
+            Type::Scalar(ScalarType::Float) => compare!(f32, key.default_real as f32),
+            Type::Scalar(ScalarType::Double) => compare!(f64, key.default_real),
+            Type::String => self
+                .key_str(previous, slot)?
+                .partial_cmp(self.key_str(next, slot)?),
+            _ => return Ok(()),
+        };
+        match ordering {
+            Some(Ordering::Less) => Ok(()),
+            _ => Err(Error::KeyNotSorted { pos: next.0 }),
+        }
+    }
+
+    /// Reads the string key field, which is empty if absent.
+    fn key_str(&self, table: TablePosition, slot: usize) -> Result<&'a str> {
+        match table.try_field_position(self.buf, slot)? {
+            Some(pos) => StringPosition(try_seek_uoffset(self.buf, pos)?).try_as_str(self.buf),
+            None => Ok(""),
+        }
+    }
+
+    /// Checks that the scalar at `pos` is a valid value, i.e., bools are 0 or 1.
+    fn scalar(&self, scalar: ScalarType, pos: usize) -> Result<()> {
+        if scalar == ScalarType::Bool && self.buf[pos] > 1 {
+            Err(Error::InvalidBool { pos })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the scalars in the struct at `pos`, including those in nested structs.
+    fn struct_scalars(&self, index: usize, pos: usize) -> Result<()> {
+        for field in self.schema.structs[index].fields {
+            match field.ty {
+                Type::Scalar(scalar) => self.scalar(scalar, pos + field.offset)?,
+                Type::Struct(index) => self.struct_scalars(index, pos + field.offset)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn string(&self, pos: usize) -> Result<()> {
+        check_alignment(pos, SIZE_OF_LEN)?;
+        let s = StringPosition(pos);
+        let len = s.try_as_str(self.buf)?.len();
+        let end = pos + SIZE_OF_LEN + len;
+        match self.buf.get(end) {
+            Some(&0) => Ok(()),
+            _ => Err(Error::MissingNul { pos }),
+        }
+    }
+}
+
+/// Verifies the buffer and checks that it is in the canonical form.
+///
+/// See the [canonical form](index.html#canonical-form) rules.
+///
+/// # Examples
+///
+/// ```
+/// use blockbuffers::schema::{Field, ScalarType, Schema, Table, Type};
+/// use blockbuffers::verify::{verify, verify_canonical};
+/// use blockbuffers::Error;
+///
+/// // table Counter { count: uint = 1; }
+/// static COUNTER_FIELDS: &[Field] = &[Field {
+///     name: "count",
+///     id: 0,
+///     ty: Type::Scalar(ScalarType::UInt),
+///     required: false,
+///     default_integer: 1,
+///     default_real: 0.0,
+///     since_version: 0,
+///     deprecated_version: None,
+///     nested_flatbuffer: None,
+/// }];
+/// let schema = Schema {
+///     tables: &[Table { name: "Counter", fields: COUNTER_FIELDS, key: None }],
+///     structs: &[],
+///     unions: &[],
+/// };
+///
+/// let buf = &mut [
+///     12u8, 0, 0, 0,           // root offset
+///     6, 0, 8, 0, 4, 0, 0, 0,  // vtable and padding
+///     8, 0, 0, 0,              // table
+///     2, 0, 0, 0,              // count
+/// ][..];
+/// assert_eq!(Ok(12), verify_canonical(&schema, 0, buf).map(|pos| pos.0));
+///
+/// // Padding is not zero
+/// buf[10] = 1;
+/// assert!(verify(&schema, 0, buf).is_ok());
+/// assert_eq!(Err(Error::NonCanonical { pos: 10 }), verify_canonical(&schema, 0, buf));
+/// buf[10] = 0;
+///
+/// // Default value is written
+/// buf[16] = 1;
+/// assert!(verify(&schema, 0, buf).is_ok());
+/// assert_eq!(Err(Error::NonCanonical { pos: 16 }), verify_canonical(&schema, 0, buf));
+/// ```
+pub fn verify_canonical(schema: &Schema, root: usize, buf: &[u8]) -> Result<TablePosition> {
+    verify_canonical_with_options(schema, root, buf, Options::default())
+}
+
+/// Checks that the buffer is in the canonical form with the specified limits.
+pub fn verify_canonical_with_options(
+    schema: &Schema,
+    root: usize,
+    buf: &[u8],
+    options: Options,
+) -> Result<TablePosition> {
+    let pos = verify_with_options(schema, root, buf, options)?;
+    Canonical::buffer(schema, root, buf, pos)?;
+    Ok(pos)
+}
+
+/// Walks a verified buffer in the canonical order and checks every byte.
+struct Canonical<'a> {
+    schema: &'a Schema<'a>,
+    buf: &'a [u8],
+    /// The end of the last object.
+    cursor: usize,
+}
+
+impl<'a> Canonical<'a> {
+    /// Checks the whole buffer which root table is `pos`, of the type `root`.
+    fn buffer(
+        schema: &'a Schema<'a>,
+        root: usize,
+        buf: &'a [u8],
+        pos: TablePosition,
+    ) -> Result<()> {
+        let mut canonical = Canonical {
+            schema,
+            buf,
+            cursor: SIZE_OF_UOFFSET,
+        };
+        canonical.table(root, pos)?;
+        if canonical.cursor != buf.len() {
+            return Err(Error::NonCanonical {
+                pos: canonical.cursor,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the next object is placed at `pos` and the gap is zeros.
+    fn place(&mut self, pos: usize, expected: usize) -> Result<()> {
+        if pos != expected {
+            return Err(Error::NonCanonical { pos });
+        }
+        self.zeros(self.cursor, pos)?;
+        self.cursor = pos;
+        Ok(())
+    }
+
+    fn zeros(&self, start: usize, end: usize) -> Result<()> {
+        match self.buf[start..end].iter().position(|&b| b != 0) {
+            Some(i) => Err(Error::NonCanonical { pos: start + i }),
+            None => Ok(()),
+        }
+    }
+
+    fn table(&mut self, index: usize, pos: TablePosition) -> Result<()> {
+        let buf = self.buf;
+        let schema = self.schema;
+        let fields = schema.tables[index].fields;
+
+        let vtable = pos.vtable(buf);
+        let expected = align_up(self.cursor, SIZE_OF_VOFFSET);
+        self.place(vtable.0, expected)?;
+        let vtable_len = vtable.vtable_bytes_len(buf);
+        let slots_count = (vtable_len - 2 * SIZE_OF_VOFFSET) / SIZE_OF_VOFFSET;
+        if slots_count > 0 && vtable.field_offset(buf, vtable_len - SIZE_OF_VOFFSET) == 0 {
+            return Err(Error::NonCanonical { pos: vtable.0 });
+        }
+        self.cursor = vtable.0 + vtable_len;
+
+        let expected = align_up(self.cursor, SIZE_OF_SOFFSET);
+        self.place(pos.0, expected)?;
+        self.cursor = pos.0 + SIZE_OF_SOFFSET;
+
+        // Inline fields in the order of ids.
+        for id in 0..slots_count {
+            let offset = vtable.field_offset(buf, 4 + 2 * id) as usize;
+            if offset == 0 {
+                continue;
+            }
+            let field = match fields.iter().find(|f| f.id as usize == id) {
+                Some(field) => field,
+                None => return Err(Error::NonCanonical { pos: vtable.0 }),
+            };
+            let (size, align) = schema.inline_size_and_align(field.ty);
+            let expected = align_up(self.cursor, align);
+            self.place(pos.0 + offset, expected)?;
+            self.inline_field(field, pos.0 + offset)?;
+            self.cursor = pos.0 + offset + size;
+        }
+        if self.cursor != pos.0 + vtable.table_bytes_len(buf) {
+            return Err(Error::NonCanonical { pos: vtable.0 });
+        }
+
+        // Referenced objects in the order of ids.
+        for field in fields {
+            let field_pos = match pos.field_position(buf, field.slot()) {
+                Some(field_pos) => field_pos,
+                None => continue,
+            };
+            match field.ty {
+                Type::String => self.string(seek_uoffset(buf, field_pos))?,
+                Type::Vector(element) => {
+                    let vector = seek_uoffset(buf, field_pos);
+                    self.vector(element, vector)?;
+                    // The nested buffer is checked as a standalone buffer, and the positions in
+                    // the errors are relative to it.
+                    if let Some(index) = field.nested_flatbuffer {
+                        let (nested, root) = NestedBufferPosition(VectorPosition(vector)).root(buf);
+                        Canonical::buffer(schema, index, nested, root)?;
+                    }
+                }
+                Type::Table(index) => {
+                    self.table(index, TablePosition(seek_uoffset(buf, field_pos)))?
+                }
+                Type::Union(index) => {
+                    let tag_pos = pos
+                        .field_position(buf, field.slot() - 2)
+                        .expect("verified union type");
+                    let tag = buf[tag_pos];
+                    let variant = schema.unions[index]
+                        .variant(tag)
+                        .expect("verified union type");
+                    self.table(variant.table, TablePosition(seek_uoffset(buf, field_pos)))?
+                }
+                Type::Scalar(_) | Type::Struct(_) | Type::UnionType(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inline_field(&self, field: &Field, pos: usize) -> Result<()> {
+        let is_default = match field.ty {
+            Type::Scalar(ScalarType::Float) => {
+                f32::from_le_slice(&self.buf[pos..]).to_bits()
+                    == (field.default_real as f32).to_bits()
+            }
+            Type::Scalar(ScalarType::Double) => {
+                f64::from_le_slice(&self.buf[pos..]).to_bits() == field.default_real.to_bits()
+            }
+            Type::Scalar(scalar) => read_integer(self.buf, pos, scalar) == field.default_integer,
+            Type::UnionType(_) => self.buf[pos] == 0,
+            Type::Struct(index) => return self.struct_padding(index, pos),
+            Type::String | Type::Table(_) | Type::Vector(_) | Type::Union(_) => false,
+        };
+
+        if is_default {
+            Err(Error::NonCanonical { pos })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn struct_padding(&self, index: usize, pos: usize) -> Result<()> {
+        let st = &self.schema.structs[index];
+        let mut end = pos;
+        for field in st.fields {
+            let field_pos = pos + field.offset;
+            self.zeros(end, field_pos)?;
+            let (size, _) = self.schema.inline_size_and_align(field.ty);
+            if let Type::Struct(index) = field.ty {
+                self.struct_padding(index, field_pos)?;
+            }
+            end = field_pos + size;
+        }
+        self.zeros(end, pos + st.bytesize)
+    }
+
+    fn vector(&mut self, element: Element, pos: usize) -> Result<()> {
+        let buf = self.buf;
+        let (size, align) = self.schema.element_size_and_align(element);
+        let align = if align > SIZE_OF_LEN {
+            align
+        } else {
+            SIZE_OF_LEN
+        };
+        let expected = align_up(self.cursor + SIZE_OF_LEN, align) - SIZE_OF_LEN;
+        self.place(pos, expected)?;
+
+        let len = VectorPosition(pos).len(buf);
+        let start = pos + SIZE_OF_LEN;
+        for i in 0..len {
+            if let Element::Struct(index) = element {
+                self.struct_padding(index, start + i * size)?;
+            }
+        }
+        self.cursor = start + len * size;
+
+        for i in 0..len {
+            match element {
+                Element::String => self.string(seek_uoffset(buf, start + i * size))?,
+                Element::Table(index) => {
+                    self.table(index, TablePosition(seek_uoffset(buf, start + i * size)))?
+                }
+                Element::Scalar(_) | Element::Struct(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn string(&mut self, pos: usize) -> Result<()> {
+        let expected = align_up(self.cursor, SIZE_OF_LEN);
+        self.place(pos, expected)?;
+        self.cursor = pos + SIZE_OF_LEN + StringPosition(pos).len(self.buf) + 1;
+        Ok(())
+    }
+}
+
+/// Reads an integer or bool scalar as `i64`.
+fn read_integer(buf: &[u8], pos: usize, scalar: ScalarType) -> i64 {
+    let bytes = &buf[pos..];
+    match scalar {
+        ScalarType::Bool | ScalarType::UByte => i64::from(u8::from_le_slice(bytes)),
+        ScalarType::Byte => i64::from(i8::from_le_slice(bytes)),
+        ScalarType::Short => i64::from(i16::from_le_slice(bytes)),
+        ScalarType::UShort => i64::from(u16::from_le_slice(bytes)),
+        ScalarType::Int => i64::from(i32::from_le_slice(bytes)),
+        ScalarType::UInt => i64::from(u32::from_le_slice(bytes)),
+        ScalarType::Long => i64::from_le_slice(bytes),
+        ScalarType::ULong => u64::from_le_slice(bytes) as i64,
+        ScalarType::Float | ScalarType::Double => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schema::{ScalarType, Table, Union, UnionVariant};
+
+    // table Monster { hp: short; name: string (required); }
+    static MONSTER_FIELDS: &[Field] = &[
+        Field {
+            name: "hp",
+            id: 0,
+            ty: Type::Scalar(ScalarType::Short),
+            required: false,
+            default_integer: 0,
+            default_real: 0.0,
+            since_version: 0,
+            deprecated_version: None,
+            nested_flatbuffer: None,
+        },
+        Field {
+            name: "name",
+            id: 1,
+            ty: Type::String,
+            required: true,
+            default_integer: 0,
+            default_real: 0.0,
+            since_version: 0,
+            deprecated_version: None,
+            nested_flatbuffer: None,
+        },
+    ];
+    // table Slot { item_type: ubyte; item: Item; }
+    static SLOT_FIELDS: &[Field] = &[
+        Field {
+            name: "item_type",
+            id: 0,
+            ty: Type::UnionType(0),
+            required: false,
+            default_integer: 0,
+            default_real: 0.0,
+            since_version: 0,
+            deprecated_version: None,
+            nested_flatbuffer: None,
+        },
+        Field {
+            name: "item",
+            id: 1,
+            ty: Type::Union(0),
+            required: false,
+            default_integer: 0,
+            default_real: 0.0,
+            since_version: 0,
+            deprecated_version: None,
+            nested_flatbuffer: None,
+        },
+    ];
+    // table Nest { child: Nest; }
+    static NEST_FIELDS: &[Field] = &[Field {
+        name: "child",
+        id: 0,
+        ty: Type::Table(2),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    }];
+    static SCHEMA: Schema<'static> = Schema {
+        tables: &[
+            Table {
+                name: "Monster",
+                fields: MONSTER_FIELDS,
+                key: None,
+            },
+            Table {
+                name: "Slot",
+                fields: SLOT_FIELDS,
+                key: None,
+            },
+            Table {
+                name: "Nest",
+                fields: NEST_FIELDS,
+                key: None,
+            },
+        ],
+        structs: &[],
+        unions: &[Union {
+            name: "Item",
+            variants: &[UnionVariant {
+                name: "Monster",
+                tag: 1,
+                table: 0,
+            }],
+        }],
+    };
+
+    fn monster() -> Vec<u8> {
+        vec![
+            12, 0, 0, 0, // root offset
+            8, 0, 10, 0, 8, 0, 4, 0, // vtable
+            8, 0, 0, 0, // table
+            8, 0, 0, 0, // name
+            100, 0, 0, 0, // hp
+            3, 0, 0, 0, b'o', b'r', b'c', 0,
+        ]
+    }
+
+    fn slot(tag: u8, value: bool) -> Vec<u8> {
+        let mut buf = vec![
+            12, 0, 0, 0, // root offset
+            8, 0, 12, 0, 8, 0, 4, 0, // vtable
+            8, 0, 0, 0, // table
+            16, 0, 0, 0, // item
+            tag, 0, 0, 0, // item_type
+        ];
+        if !value {
+            buf[10] = 0;
+        }
+        buf.extend(monster()[4..].iter());
+        buf
+    }
+
+    #[test]
+    fn test_verify_monster() {
+        assert_eq!(Ok(TablePosition(12)), verify(&SCHEMA, 0, &monster()));
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut buf = monster();
+        buf[0] = 32;
+        assert_eq!(
+            Err(Error::OutOfBounds {
+                pos: 0,
+                need: 32,
+                buf_len: 32
+            }),
+            verify(&SCHEMA, 0, &buf)
+        );
+
+        let mut buf = monster();
+        buf[24] = 5;
+        assert_eq!(
+            Err(Error::OutOfBounds {
+                pos: 28,
+                need: 5,
+                buf_len: 32
+            }),
+            verify(&SCHEMA, 0, &buf)
+        );
+    }
+
+    #[test]
+    fn test_misaligned() {
+        let mut buf = monster();
+        buf[16] = 6;
+        assert_eq!(
+            Err(Error::Misaligned { pos: 22, align: 4 }),
+            verify(&SCHEMA, 0, &buf)
+        );
+    }
+
+    #[test]
+    fn test_bad_vtable() {
+        let mut buf = monster();
+        buf[4] = 7;
+        assert_eq!(Err(Error::BadVTable { pos: 4 }), verify(&SCHEMA, 0, &buf));
+
+        // table size is too small to hold hp
+        let mut buf = monster();
+        buf[6] = 9;
+        assert_eq!(Err(Error::BadVTable { pos: 4 }), verify(&SCHEMA, 0, &buf));
+    }
+
+    #[test]
+    fn test_required_field_missing() {
+        let mut buf = monster();
+        buf[10] = 0;
+        assert_eq!(
+            Err(Error::RequiredFieldMissing { pos: 12, id: 1 }),
+            verify(&SCHEMA, 0, &buf)
+        );
+    }
+
+    #[test]
+    fn test_invalid_string() {
+        let mut buf = monster();
+        buf[30] = 0xff;
+        assert_eq!(
+            Err(Error::InvalidUtf8 { pos: 24 }),
+            verify(&SCHEMA, 0, &buf)
+        );
+
+        let mut buf = monster();
+        buf[31] = b's';
+        assert_eq!(Err(Error::MissingNul { pos: 24 }), verify(&SCHEMA, 0, &buf));
+    }
+
+    #[test]
+    fn test_invalid_bool() {
+        // table Flags { on: bool; flags: [bool]; }
+        static FLAGS_FIELDS: &[Field] = &[
+            Field {
+                name: "on",
+                id: 0,
+                ty: Type::Scalar(ScalarType::Bool),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            },
+            Field {
+                name: "flags",
+                id: 1,
+                ty: Type::Vector(Element::Scalar(ScalarType::Bool)),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            },
+        ];
+        let schema = Schema {
+            tables: &[Table {
+                name: "Flags",
+                fields: FLAGS_FIELDS,
+                key: None,
+            }],
+            structs: &[],
+            unions: &[],
+        };
+        let buf = [
+            12u8, 0, 0, 0, // root offset
+            8, 0, 12, 0, 8, 0, 4, 0, // vtable
+            8, 0, 0, 0, // table
+            8, 0, 0, 0, // flags
+            1, 0, 0, 0, // on and padding
+            2, 0, 0, 0, 0, 1, // flags
+        ];
+        assert!(verify(&schema, 0, &buf).is_ok());
+
+        let mut invalid = buf;
+        invalid[20] = 2;
+        assert_eq!(
+            Err(Error::InvalidBool { pos: 20 }),
+            verify(&schema, 0, &invalid)
+        );
+
+        let mut invalid = buf;
+        invalid[29] = 2;
+        assert_eq!(
+            Err(Error::InvalidBool { pos: 29 }),
+            verify(&schema, 0, &invalid)
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(Ok(TablePosition(12)), verify(&SCHEMA, 1, &slot(1, true)));
+        assert_eq!(Ok(TablePosition(12)), verify(&SCHEMA, 1, &slot(0, false)));
+        assert_eq!(
+            Err(Error::BadUnion { pos: 12 }),
+            verify(&SCHEMA, 1, &slot(1, false))
+        );
+        assert_eq!(
+            Err(Error::BadUnion { pos: 12 }),
+            verify(&SCHEMA, 1, &slot(0, true))
+        );
+        assert_eq!(
+            Err(Error::UnknownUnionType { pos: 12, tag: 2 }),
+            verify(&SCHEMA, 1, &slot(2, true))
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let buf = [
+            12u8, 0, 0, 0, // root offset
+            6, 0, 8, 0, 4, 0, 0, 0, // vtable and padding
+            8, 0, 0, 0, // table
+            8, 0, 0, 0, // child
+            4, 0, 4, 0, // child vtable
+            4, 0, 0, 0, // child table
+        ];
+        assert_eq!(Ok(TablePosition(12)), verify(&SCHEMA, 2, &buf));
+
+        let options = Options {
+            max_depth: 1,
+            ..Options::default()
+        };
+        assert_eq!(
+            Err(Error::DepthLimitExceeded { pos: 24 }),
+            verify_with_options(&SCHEMA, 2, &buf, options)
+        );
+    }
+
+    #[test]
+    fn test_deny_unknown_fields() {
+        let buf = [
+            12u8, 0, 0, 0, // root offset
+            8, 0, 8, 0, 0, 0, 4, 0, // vtable
+            8, 0, 0, 0, // table
+            7, 0, 0, 0, // field 1
+        ];
+        assert_eq!(Ok(TablePosition(12)), verify(&SCHEMA, 2, &buf));
+
+        let options = Options {
+            deny_unknown_fields: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            Err(Error::UnknownField { pos: 12, id: 1 }),
+            verify_with_options(&SCHEMA, 2, &buf, options)
+        );
+    }
+
+    fn canonical_monster() -> Vec<u8> {
+        vec![
+            12, 0, 0, 0, // root offset
+            8, 0, 12, 0, 4, 0, 8, 0, // vtable
+            8, 0, 0, 0, // table
+            100, 0, 0, 0, // hp and padding
+            4, 0, 0, 0, // name
+            3, 0, 0, 0, b'o', b'r', b'c', 0,
+        ]
+    }
+
+    #[test]
+    fn test_verify_canonical() {
+        assert_eq!(
+            Ok(TablePosition(12)),
+            verify_canonical(&SCHEMA, 0, &canonical_monster())
+        );
+    }
+
+    #[test]
+    fn test_non_canonical_layout() {
+        // Fields are not in the order of ids.
+        assert_eq!(
+            Err(Error::NonCanonical { pos: 20 }),
+            verify_canonical(&SCHEMA, 0, &monster())
+        );
+
+        // Trailing bytes
+        let mut buf = canonical_monster();
+        buf.push(0);
+        assert_eq!(
+            Err(Error::NonCanonical { pos: 32 }),
+            verify_canonical(&SCHEMA, 0, &buf)
+        );
+
+        // Gap between the table and the string
+        let mut buf = canonical_monster();
+        buf[20] = 8;
+        for _ in 0..4 {
+            buf.insert(24, 0);
+        }
+        assert!(verify(&SCHEMA, 0, &buf).is_ok());
+        assert_eq!(
+            Err(Error::NonCanonical { pos: 28 }),
+            verify_canonical(&SCHEMA, 0, &buf)
+        );
+    }
+
+    #[test]
+    fn test_non_canonical_vtable() {
+        // The vtable has a trailing zero entry.
+        let buf = [
+            16u8, 0, 0, 0, // root offset
+            10, 0, 12, 0, 4, 0, 8, 0, 0, 0, 0, 0, // vtable and padding
+            12, 0, 0, 0, // table
+            100, 0, 0, 0, // hp and padding
+            4, 0, 0, 0, // name
+            3, 0, 0, 0, b'o', b'r', b'c', 0,
+        ];
+        assert!(verify(&SCHEMA, 0, &buf).is_ok());
+        assert_eq!(
+            Err(Error::NonCanonical { pos: 4 }),
+            verify_canonical(&SCHEMA, 0, &buf)
+        );
+    }
+
+    #[test]
+    fn test_shared_string() {
+        // table Pair { first: string; second: string; }, both point to the same string.
+        static PAIR_FIELDS: &[Field] = &[
+            Field {
+                name: "first",
+                id: 0,
+                ty: Type::String,
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            },
+            Field {
+                name: "second",
+                id: 1,
+                ty: Type::String,
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            },
+        ];
+        let schema = Schema {
+            tables: &[Table {
+                name: "Pair",
+                fields: PAIR_FIELDS,
+                key: None,
+            }],
+            structs: &[],
+            unions: &[],
+        };
+        let buf = [
+            12u8, 0, 0, 0, // root offset
+            8, 0, 12, 0, 4, 0, 8, 0, // vtable
+            8, 0, 0, 0, // table
+            8, 0, 0, 0, // first
+            4, 0, 0, 0, // second
+            1, 0, 0, 0, b'a', 0, 0, 0,
+        ];
+        assert!(verify(&schema, 0, &buf).is_ok());
+        assert_eq!(
+            Err(Error::NonCanonical { pos: 24 }),
+            verify_canonical(&schema, 0, &buf)
+        );
+    }
+}
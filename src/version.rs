@@ -0,0 +1,126 @@
+//! Reads tables as a reader of a specific protocol version.
+//!
+//! Fields can be annotated with the attributes `since_version` and `deprecated_version`, which
+//! are kept in [`Field`](../schema/struct.Field.html). A reader built for version N should not
+//! see fields introduced after N, which are written by newer peers, nor fields deprecated at or
+//! before N.
+//!
+//! [`VersionedTable`](struct.VersionedTable.html) wraps a `TablePosition` and hides those fields.
+//! Use [`Options::version`](../verify/struct.Options.html#structfield.version) to reject buffers
+//! which carry fields not valid in the version they are written in.
+//!
+//! # Examples
+//!
+//! ```
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::position::TablePosition;
+//! use blockbuffers::schema::{Field, ScalarType, Type};
+//! use blockbuffers::seek::seek_uoffset;
+//! use blockbuffers::version::VersionedTable;
+//!
+//! // table Monster { hp: short; mana: short (since_version: 2); }
+//! let mana = Field {
+//!     name: "mana",
+//!     id: 1,
+//!     ty: Type::Scalar(ScalarType::Short),
+//!     required: false,
+//!     default_integer: 0,
+//!     default_real: 0.0,
+//!     since_version: 2,
+//!     deprecated_version: None,
+//!     nested_flatbuffer: None,
+//! };
+//!
+//! let mut monster = TableBuilder::new();
+//! monster.add_scalar(1, 20i16, 0);
+//! let buf = monster.finish();
+//! let pos = TablePosition(seek_uoffset(&buf, 0));
+//!
+//! assert_eq!(None, VersionedTable::new(pos, 1).field_position(&buf, &mana));
+//! assert!(VersionedTable::new(pos, 2).field_position(&buf, &mana).is_some());
+//! ```
+
+use error::{Error, Result};
+use position::TablePosition;
+use schema::Field;
+
+/// VersionedTable wrappers a table position read by a reader of the protocol version `version`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VersionedTable {
+    pub position: TablePosition,
+    pub version: u32,
+}
+
+impl VersionedTable {
+    /// Views the table at `position` as a reader of the protocol version `version`.
+    pub fn new(position: TablePosition, version: u32) -> VersionedTable {
+        VersionedTable { position, version }
+    }
+
+    /// Seeks the position for a field.
+    ///
+    /// Returns `None` if the field is absent, or it is not valid in the version, see
+    /// [`Field::is_in_version`](../schema/struct.Field.html#method.is_in_version).
+    pub fn field_position(self, buf: &[u8], field: &Field) -> Option<usize> {
+        if field.is_in_version(self.version) {
+            self.position.field_position(buf, field.slot())
+        } else {
+            None
+        }
+    }
+
+    /// Checked version of [`field_position`](#method.field_position).
+    ///
+    /// Fields introduced after the version are treated as absent, because newer peers may add
+    /// them. Fails if a field deprecated at or before the version is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use blockbuffers::builder::TableBuilder;
+    /// use blockbuffers::position::TablePosition;
+    /// use blockbuffers::schema::{Field, Type};
+    /// use blockbuffers::seek::seek_uoffset;
+    /// use blockbuffers::version::VersionedTable;
+    /// use blockbuffers::Error;
+    ///
+    /// // table Monster { name: string (deprecated_version: 2); }
+    /// let name = Field {
+    ///     name: "name",
+    ///     id: 0,
+    ///     ty: Type::String,
+    ///     required: false,
+    ///     default_integer: 0,
+    ///     default_real: 0.0,
+    ///     since_version: 0,
+    ///     deprecated_version: Some(2),
+    ///     nested_flatbuffer: None,
+    /// };
+    ///
+    /// let mut monster = TableBuilder::new();
+    /// monster.add_string(0, "orc");
+    /// let buf = monster.finish();
+    /// let pos = TablePosition(seek_uoffset(&buf, 0));
+    ///
+    /// assert!(VersionedTable::new(pos, 1).try_field_position(&buf, &name).unwrap().is_some());
+    /// assert_eq!(
+    ///     Err(Error::FieldNotInVersion { pos: pos.0, id: 0, version: 2 }),
+    ///     VersionedTable::new(pos, 2).try_field_position(&buf, &name)
+    /// );
+    /// ```
+    pub fn try_field_position(self, buf: &[u8], field: &Field) -> Result<Option<usize>> {
+        if self.version < field.since_version {
+            return Ok(None);
+        }
+
+        let pos = self.position.try_field_position(buf, field.slot())?;
+        if pos.is_some() && !field.is_in_version(self.version) {
+            return Err(Error::FieldNotInVersion {
+                pos: self.position.0,
+                id: field.id,
+                version: self.version,
+            });
+        }
+        Ok(pos)
+    }
+}
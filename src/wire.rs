@@ -0,0 +1,1086 @@
+//! Serializes Rust types as strict Flatbuffers with [serde](https://serde.rs).
+//!
+//! This module requires the feature `serde`. The format is not self-describing, the Rust types
+//! play the role of the schema by the following field-order contract.
+//!
+//! - A struct is a table. The fields get ids in the declaration order, i.e., the first field has
+//!   id 0. Fields skipped by `skip_serializing_if` keep their ids and are absent.
+//! - Bools, integers and floats are scalars which default is 0. Chars and unit enum variants are
+//!   `uint` scalars, the latter store the variant index.
+//! - Strings are strings. Byte slices and sequences are vectors of scalars, strings, tables or
+//!   structs.
+//! - Tuples, tuple structs and arrays are structs of scalars, laid out as
+//!   [`StructLayout`](../position/struct.StructLayout.html).
+//! - `None` and units are absent. Newtype structs are the same as the wrapped value.
+//!
+//! Maps and enum variants with data are not supported.
+//!
+//! The buffer is written by the [canonical builder](../builder/index.html), which omits scalars
+//! equal to the default, so `Some(0)` reads back as `None`. An empty sequence has no element type
+//! and is written as an empty vector of bytes.
+//!
+//! [`from_slice`](fn.from_slice.html) trusts the contract and only checks the bounds of the
+//! fields it reads. [`from_slice_with_schema`](fn.from_slice_with_schema.html) verifies the
+//! buffer using a schema first.
+//!
+//! # Examples
+//!
+//! ```
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate blockbuffers;
+//!
+//! use blockbuffers::builder::TableBuilder;
+//! use blockbuffers::wire::{from_slice, to_vec};
+//!
+//! // table Author { name: string; birth: int; }
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Author {
+//!     name: String,
+//!     birth: i32,
+//! }
+//!
+//! # fn main() {
+//! let author = Author {
+//!     name: "alice".to_string(),
+//!     birth: 1990,
+//! };
+//! let buf = to_vec(&author).unwrap();
+//!
+//! let mut builder = TableBuilder::new();
+//! builder.add_string(0, "alice");
+//! builder.add_scalar(1, 1990i32, 0);
+//! assert_eq!(builder.finish(), buf);
+//! assert_eq!(Ok(author), from_slice(&buf));
+//! # }
+//! ```
+
+use builder::{StructBuilder, TableBuilder};
+use dynamic::Scalar;
+use error;
+use le::LE;
+use position::{StringPosition, StructLayout, StructPosition, TablePosition, VectorPosition};
+use root::try_root_table;
+use schema::Schema;
+use seek::{try_read, try_seek_uoffset};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, SeqAccess, VariantAccess, Visitor,
+};
+use serde::ser::{self, Impossible, Serialize};
+use serde::Deserialize;
+use std::char;
+use std::fmt;
+use std::mem::size_of;
+use std::result;
+use types::{SIZE_OF_LEN, SIZE_OF_UOFFSET};
+use verify::verify;
+
+/// Errors returned by the serde format.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// The buffer is malformed.
+    Buffer(error::Error),
+    /// The value has a type which the format does not support.
+    Unsupported(&'static str),
+    /// The error reported by the `Serialize` or `Deserialize` implementation.
+    Message(String),
+}
+
+/// The result type returned by the serde format.
+pub type Result<T> = result::Result<T, Error>;
+
+impl From<error::Error> for Error {
+    fn from(err: error::Error) -> Error {
+        Error::Buffer(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Buffer(ref err) => fmt::Display::fmt(err, f),
+            Error::Unsupported(what) => write!(f, "{} is not supported", what),
+            Error::Message(ref msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Buffer(..) => "malformed buffer",
+            Error::Unsupported(..) => "unsupported type",
+            Error::Message(ref msg) => msg,
+        }
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error::Message(msg.to_string())
+    }
+}
+
+/// Serializes the struct into a table builder.
+pub fn to_builder<T: ?Sized + Serialize>(value: &T) -> Result<TableBuilder> {
+    match value.serialize(ValueSerializer)? {
+        Value::Table(builder) => Ok(builder),
+        _ => Err(Error::Unsupported("root which is not a struct")),
+    }
+}
+
+/// Serializes the struct into a buffer in the canonical form.
+pub fn to_vec<T: ?Sized + Serialize>(value: &T) -> Result<Vec<u8>> {
+    to_builder(value).map(|builder| builder.finish())
+}
+
+/// Deserializes the struct from the root table of the buffer.
+pub fn from_slice<'de, T: Deserialize<'de>>(buf: &'de [u8]) -> Result<T> {
+    T::deserialize(Deserializer::from_slice(buf)?)
+}
+
+/// Verifies the buffer using the schema and deserializes the struct from the root table.
+///
+/// Parameter `table` is the index of the root table in `schema.tables`.
+pub fn from_slice_with_schema<'de, T: Deserialize<'de>>(
+    schema: &Schema,
+    table: usize,
+    buf: &'de [u8],
+) -> Result<T> {
+    let root = verify(schema, table, buf)?;
+    T::deserialize(Deserializer::new(buf, root))
+}
+
+/// The serialized value before it is added to a table, vector or struct.
+enum Value {
+    Absent,
+    Scalar(Scalar),
+    String(String),
+    Bytes(Vec<u8>),
+    Vector(Vec<Value>),
+    Struct(StructBuilder),
+    Table(TableBuilder),
+}
+
+fn add_field(builder: &mut TableBuilder, id: u16, value: Value) -> Result<()> {
+    match value {
+        Value::Absent => {}
+        Value::Scalar(scalar) => add_scalar(builder, id, scalar),
+        Value::String(value) => builder.add_string(id, &value),
+        Value::Bytes(value) => builder.add_vector(id, &value),
+        Value::Vector(items) => add_vector(builder, id, items)?,
+        Value::Struct(value) => builder.add_struct(id, &value),
+        Value::Table(value) => builder.add_table(id, value),
+    }
+    Ok(())
+}
+
+fn add_scalar(builder: &mut TableBuilder, id: u16, scalar: Scalar) {
+    match scalar {
+        Scalar::Bool(v) => builder.add_scalar(id, v, false),
+        Scalar::Byte(v) => builder.add_scalar(id, v, 0),
+        Scalar::UByte(v) => builder.add_scalar(id, v, 0),
+        Scalar::Short(v) => builder.add_scalar(id, v, 0),
+        Scalar::UShort(v) => builder.add_scalar(id, v, 0),
+        Scalar::Int(v) => builder.add_scalar(id, v, 0),
+        Scalar::UInt(v) => builder.add_scalar(id, v, 0),
+        Scalar::Long(v) => builder.add_scalar(id, v, 0),
+        Scalar::ULong(v) => builder.add_scalar(id, v, 0),
+        Scalar::Float(v) => builder.add_scalar(id, v, 0.0),
+        Scalar::Double(v) => builder.add_scalar(id, v, 0.0),
+    }
+}
+
+/// Collects the vector elements matching the pattern, fails if any element does not match.
+macro_rules! collect_items {
+    ($items:expr, $pattern:pat => $item:expr) => {
+        $items
+            .into_iter()
+            .map(|value| match value {
+                $pattern => Ok($item),
+                _ => Err(Error::Unsupported("vector of mixed types")),
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+}
+
+/// The vector element type, which is decided by the first element.
+#[derive(Copy, Clone)]
+enum Element {
+    Scalar(Scalar),
+    String,
+    Table,
+    Struct { bytesize: usize, minalign: usize },
+}
+
+fn add_vector(builder: &mut TableBuilder, id: u16, items: Vec<Value>) -> Result<()> {
+    let element = match items.first() {
+        None => None,
+        Some(first) => Some(match *first {
+            Value::Scalar(scalar) => Element::Scalar(scalar),
+            Value::String(_) => Element::String,
+            Value::Table(_) => Element::Table,
+            Value::Struct(ref first) => Element::Struct {
+                bytesize: first.as_bytes().len(),
+                minalign: first.minalign(),
+            },
+            _ => return Err(Error::Unsupported("vector of vectors or absent values")),
+        }),
+    };
+
+    match element {
+        None => builder.add_vector::<u8>(id, &[]),
+        Some(Element::Scalar(first)) => add_scalar_vector(builder, id, first, items)?,
+        Some(Element::String) => {
+            let items = collect_items!(items, Value::String(item) => item);
+            builder.add_string_vector(id, &items);
+        }
+        Some(Element::Table) => {
+            let items = collect_items!(items, Value::Table(item) => item);
+            builder.add_table_vector(id, items);
+        }
+        Some(Element::Struct { bytesize, minalign }) => {
+            let items = collect_items!(items, Value::Struct(item) => item);
+            if items
+                .iter()
+                .any(|item| item.as_bytes().len() != bytesize || item.minalign() != minalign)
+            {
+                return Err(Error::Unsupported("vector of mixed types"));
+            }
+            builder.add_struct_vector(id, minalign, &items);
+        }
+    }
+    Ok(())
+}
+
+fn add_scalar_vector(
+    builder: &mut TableBuilder,
+    id: u16,
+    first: Scalar,
+    items: Vec<Value>,
+) -> Result<()> {
+    match first {
+        Scalar::Bool(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Bool(v)) => v),
+        ),
+        Scalar::Byte(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Byte(v)) => v),
+        ),
+        Scalar::UByte(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::UByte(v)) => v),
+        ),
+        Scalar::Short(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Short(v)) => v),
+        ),
+        Scalar::UShort(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::UShort(v)) => v),
+        ),
+        Scalar::Int(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Int(v)) => v),
+        ),
+        Scalar::UInt(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::UInt(v)) => v),
+        ),
+        Scalar::Long(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Long(v)) => v),
+        ),
+        Scalar::ULong(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::ULong(v)) => v),
+        ),
+        Scalar::Float(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Float(v)) => v),
+        ),
+        Scalar::Double(_) => builder.add_vector(
+            id,
+            &collect_items!(items, Value::Scalar(Scalar::Double(v)) => v),
+        ),
+    }
+    Ok(())
+}
+
+fn scalar_size(scalar: Scalar) -> usize {
+    match scalar {
+        Scalar::Bool(_) | Scalar::Byte(_) | Scalar::UByte(_) => 1,
+        Scalar::Short(_) | Scalar::UShort(_) => 2,
+        Scalar::Int(_) | Scalar::UInt(_) | Scalar::Float(_) => 4,
+        Scalar::Long(_) | Scalar::ULong(_) | Scalar::Double(_) => 8,
+    }
+}
+
+fn set_scalar(builder: &mut StructBuilder, offset: usize, scalar: Scalar) {
+    match scalar {
+        Scalar::Bool(v) => builder.set(offset, v),
+        Scalar::Byte(v) => builder.set(offset, v),
+        Scalar::UByte(v) => builder.set(offset, v),
+        Scalar::Short(v) => builder.set(offset, v),
+        Scalar::UShort(v) => builder.set(offset, v),
+        Scalar::Int(v) => builder.set(offset, v),
+        Scalar::UInt(v) => builder.set(offset, v),
+        Scalar::Long(v) => builder.set(offset, v),
+        Scalar::ULong(v) => builder.set(offset, v),
+        Scalar::Float(v) => builder.set(offset, v),
+        Scalar::Double(v) => builder.set(offset, v),
+    }
+}
+
+/// Serializes any supported value, the containers decide where to put it.
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = VectorSerializer;
+    type SerializeTuple = StructSerializer;
+    type SerializeTupleStruct = StructSerializer;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = TableSerializer;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Bool(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Byte(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Long(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::UByte(v)))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::UShort(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::UInt(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::ULong(v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::UInt(v as u32)))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Absent)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Absent)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Absent)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::Scalar(Scalar::UInt(variant_index)))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Value> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<VectorSerializer> {
+        Ok(VectorSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<StructSerializer> {
+        Ok(StructSerializer {
+            fields: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<StructSerializer> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<Value, Error>> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Impossible<Value, Error>> {
+        Err(Error::Unsupported("map"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<TableSerializer> {
+        Ok(TableSerializer {
+            builder: TableBuilder::new(),
+            id: 0,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Impossible<Value, Error>> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+}
+
+struct TableSerializer {
+    builder: TableBuilder,
+    id: u16,
+}
+
+impl ser::SerializeStruct for TableSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = value.serialize(ValueSerializer)?;
+        add_field(&mut self.builder, self.id, value)?;
+        self.id += 1;
+        Ok(())
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        self.id += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Table(self.builder))
+    }
+}
+
+struct VectorSerializer {
+    items: Vec<Value>,
+}
+
+impl ser::SerializeSeq for VectorSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Vector(self.items))
+    }
+}
+
+struct StructSerializer {
+    fields: Vec<Scalar>,
+}
+
+impl StructSerializer {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        match value.serialize(ValueSerializer)? {
+            Value::Scalar(scalar) => {
+                self.fields.push(scalar);
+                Ok(())
+            }
+            _ => Err(Error::Unsupported("struct field which is not a scalar")),
+        }
+    }
+
+    fn finish(self) -> Value {
+        let mut layout = StructLayout::new();
+        let offsets: Vec<usize> = self
+            .fields
+            .iter()
+            .map(|&scalar| layout.add_field(scalar_size(scalar), scalar_size(scalar)))
+            .collect();
+        let layout = layout.finish();
+
+        let mut builder = StructBuilder::new(layout.bytesize, layout.minalign);
+        for (&scalar, offset) in self.fields.iter().zip(offsets) {
+            set_scalar(&mut builder, offset, scalar);
+        }
+        Value::Struct(builder)
+    }
+}
+
+impl ser::SerializeTuple for StructSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for StructSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(self.finish())
+    }
+}
+
+/// Implements the deserializer methods for scalars, chars, unit enum variants and newtype
+/// structs using the method `scalar` which reads a scalar at the current position.
+macro_rules! deserialize_scalars {
+    ($($method:ident => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                visitor.$visit(self.scalar()?)
+            }
+        )*
+
+        fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            let value = self.scalar::<u32>()?;
+            match char::from_u32(value) {
+                Some(c) => visitor.visit_char(c),
+                None => Err(de::Error::invalid_value(
+                    de::Unexpected::Unsigned(u64::from(value)),
+                    &"a char",
+                )),
+            }
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            _variants: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value> {
+            visitor.visit_enum(UnitVariant(self.scalar()?))
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value> {
+            visitor.visit_newtype_struct(self)
+        }
+    };
+}
+
+/// Deserializes a struct from a table.
+#[derive(Copy, Clone, Debug)]
+pub struct Deserializer<'de> {
+    buf: &'de [u8],
+    table: TablePosition,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer reading the table at `table`.
+    pub fn new(buf: &'de [u8], table: TablePosition) -> Deserializer<'de> {
+        Deserializer { buf, table }
+    }
+
+    /// Creates a deserializer reading the root table.
+    pub fn from_slice(buf: &'de [u8]) -> Result<Deserializer<'de>> {
+        Ok(Deserializer::new(buf, try_root_table(buf)?))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::Unsupported("root which is not a struct"))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_seq(TableAccess {
+            buf: self.buf,
+            table: self.table,
+            fields,
+            index: 0,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option unit
+        unit_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Visits the table fields in the order of ids.
+struct TableAccess<'de> {
+    buf: &'de [u8],
+    table: TablePosition,
+    fields: &'static [&'static str],
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for TableAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index == self.fields.len() {
+            return Ok(None);
+        }
+        let field = FieldDeserializer {
+            buf: self.buf,
+            table: self.table,
+            slot: 4 + 2 * self.index,
+            name: self.fields[self.index],
+        };
+        self.index += 1;
+        seed.deserialize(field).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.index)
+    }
+}
+
+struct FieldDeserializer<'de> {
+    buf: &'de [u8],
+    table: TablePosition,
+    slot: usize,
+    name: &'static str,
+}
+
+impl<'de> FieldDeserializer<'de> {
+    fn scalar<T: LE + Default>(self) -> Result<T> {
+        Ok(self
+            .table
+            .try_read_field(self.buf, self.slot, T::default())?)
+    }
+
+    /// Gets the position of the field which must be present unless the type is an option.
+    fn required(&self) -> Result<usize> {
+        match self.table.try_field_position(self.buf, self.slot)? {
+            Some(pos) => Ok(pos),
+            None => Err(de::Error::missing_field(self.name)),
+        }
+    }
+
+    fn offset(&self) -> Result<usize> {
+        Ok(try_seek_uoffset(self.buf, self.required()?)?)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::Unsupported("self-describing deserialization"))
+    }
+
+    deserialize_scalars! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(StringPosition(self.offset()?).try_as_str(self.buf)?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = VectorPosition(self.offset()?).try_as_le_slice::<u8>(self.buf)?;
+        visitor.visit_borrowed_bytes(bytes.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.table.try_field_position(self.buf, self.slot)? {
+            Some(_) => visitor.visit_some(self),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let vector = VectorPosition(self.offset()?);
+        visitor.visit_seq(VectorAccess {
+            buf: self.buf,
+            start: vector.0 + SIZE_OF_LEN,
+            len: vector.try_len(self.buf)?,
+            index: 0,
+            layout: None,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(StructAccess {
+            buf: self.buf,
+            position: StructPosition(self.required()?),
+            layout: &mut StructLayout::new(),
+            len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let table = TablePosition(self.offset()?);
+        Deserializer::new(self.buf, table).deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        map identifier ignored_any
+    }
+}
+
+/// Visits the vector elements.
+///
+/// The struct size is unknown until the first element is deserialized, which is used to locate
+/// the following elements.
+struct VectorAccess<'de> {
+    buf: &'de [u8],
+    start: usize,
+    len: usize,
+    index: usize,
+    layout: Option<StructLayout>,
+}
+
+impl<'de> SeqAccess<'de> for VectorAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let element = ElementDeserializer {
+            buf: self.buf,
+            start: self.start,
+            index: self.index,
+            layout: &mut self.layout,
+        };
+        self.index += 1;
+        seed.deserialize(element).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct ElementDeserializer<'a, 'de> {
+    buf: &'de [u8],
+    start: usize,
+    index: usize,
+    layout: &'a mut Option<StructLayout>,
+}
+
+impl<'a, 'de> ElementDeserializer<'a, 'de> {
+    fn scalar<T: LE + Default>(self) -> Result<T> {
+        Ok(try_read(
+            self.buf,
+            self.start + self.index * size_of::<T>(),
+        )?)
+    }
+
+    fn offset(&self) -> Result<usize> {
+        let pos = self.start + self.index * SIZE_OF_UOFFSET;
+        Ok(try_seek_uoffset(self.buf, pos)?)
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for ElementDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::Unsupported("vector of vectors or absent values"))
+    }
+
+    deserialize_scalars! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(StringPosition(self.offset()?).try_as_str(self.buf)?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        let stride = self.layout.map_or(0, |layout| layout.bytesize);
+        let mut layout = StructLayout::new();
+        let value = visitor.visit_seq(StructAccess {
+            buf: self.buf,
+            position: StructPosition(self.start + self.index * stride),
+            layout: &mut layout,
+            len,
+        })?;
+        *self.layout = Some(layout.finish());
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let table = TablePosition(self.offset()?);
+        Deserializer::new(self.buf, table).deserialize_struct(name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bytes byte_buf option unit unit_struct seq map identifier ignored_any
+    }
+}
+
+/// Visits the struct fields, which offsets are computed while the fields are deserialized.
+struct StructAccess<'a, 'de> {
+    buf: &'de [u8],
+    position: StructPosition,
+    layout: &'a mut StructLayout,
+    len: usize,
+}
+
+impl<'a, 'de> SeqAccess<'de> for StructAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(StructFieldDeserializer {
+            buf: self.buf,
+            position: self.position,
+            layout: &mut *self.layout,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+struct StructFieldDeserializer<'a, 'de> {
+    buf: &'de [u8],
+    position: StructPosition,
+    layout: &'a mut StructLayout,
+}
+
+impl<'a, 'de> StructFieldDeserializer<'a, 'de> {
+    fn scalar<T: LE + Default>(self) -> Result<T> {
+        let offset = self.layout.add_field(size_of::<T>(), size_of::<T>());
+        Ok(self.position.try_read(self.buf, offset)?)
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for StructFieldDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::Unsupported("struct field which is not a scalar"))
+    }
+
+    deserialize_scalars! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf option unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Reads the unit variant from its index.
+struct UnitVariant(u32);
+
+impl<'de> EnumAccess<'de> for UnitVariant {
+    type Error = Error;
+    type Variant = UnitVariant;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, UnitVariant)> {
+        let variant = IntoDeserializer::<'de, Error>::into_deserializer(self.0);
+        Ok((seed.deserialize(variant)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for UnitVariant {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, _seed: T) -> Result<T::Value> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value> {
+        Err(Error::Unsupported("enum variant with data"))
+    }
+}
@@ -0,0 +1,237 @@
+#[macro_use]
+extern crate blockbuffers;
+
+pub mod common;
+
+use blockbuffers::builder::{copy_table, StructBuilder, TableBuilder};
+use blockbuffers::hash::{structural_hash, Blake2b256};
+use blockbuffers::le::LE;
+use blockbuffers::position::{
+    NestedBufferPosition, StringPosition, StructPosition, TablePosition, VectorPosition,
+};
+use blockbuffers::seek::seek_uoffset;
+use blockbuffers::verify::{verify, verify_canonical};
+use blockbuffers::Error;
+use common::example_schema::{BLOCK, CHILD_EXAMPLE, EXAMPLE, SCHEMA};
+
+fn hash() -> StructBuilder {
+    let mut hash = StructBuilder::new(32, 8);
+    for i in 0..32 {
+        hash.set(i, i as u8);
+    }
+    hash
+}
+
+fn scalars() -> StructBuilder {
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(0, -1i8);
+    scalars.set(1, 1u8);
+    scalars.set(2, true);
+    scalars.set(4, -2i16);
+    scalars.set(6, 2u16);
+    scalars.set(8, -3i32);
+    scalars.set(12, 3u32);
+    scalars.set(16, 0.5f32);
+    scalars.set(24, -4i64);
+    scalars.set(32, 4u64);
+    scalars.set(40, 0.25f64);
+    scalars
+}
+
+fn author(name: &str, birth: u32) -> TableBuilder {
+    let mut author = TableBuilder::new();
+    author.add_string(0, name);
+    author.add_scalar(1, birth, 0);
+    author
+}
+
+fn block(name: &str, bytes: &[u8]) -> TableBuilder {
+    let mut code = TableBuilder::new();
+    code.add_vector(0, bytes);
+
+    let mut block = TableBuilder::new();
+    block.add_table(0, author(name, 1984));
+    block.add_union(2, 1, code);
+    block
+}
+
+fn child(buffer: &[u8]) -> TableBuilder {
+    let mut child = TableBuilder::new();
+    child.add_vector(0, buffer);
+    child
+}
+
+fn nested_example() -> Vec<u8> {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    example.finish()
+}
+
+fn build_in_order() -> Vec<u8> {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    example.add_struct(1, &hash());
+    example.add_scalar(2, 1i16, 0);
+    example.add_struct(3, &scalars());
+    example.add_string(4, "info");
+    example.add_table_vector(5, vec![block("alice", &[1, 2]), block("bob", &[])]);
+    example.add_table_vector(6, vec![child(&nested_example())]);
+    example.add_scalar(7, 10u32, 0);
+    example.finish()
+}
+
+fn build_in_reverse_order() -> Vec<u8> {
+    let mut example = TableBuilder::new();
+    example.add_scalar(7, 10u32, 0);
+    example.add_table_vector(6, vec![child(&nested_example())]);
+    example.add_table_vector(5, vec![block("alice", &[1, 2]), block("bob", &[])]);
+    example.add_string(4, "info");
+    example.add_struct(3, &scalars());
+    example.add_scalar(2, 1i16, 0);
+    example.add_struct(1, &hash());
+    example.add_scalar(0, 100u32, 0);
+    example.add_scalar(0, 1u32, 0);
+    example.finish()
+}
+
+#[test]
+fn test_same_value_same_bytes() {
+    assert_eq!(build_in_order(), build_in_reverse_order());
+}
+
+#[test]
+fn test_output_is_canonical() {
+    let buf = build_in_order();
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_ok());
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &nested_example()).is_ok());
+}
+
+#[test]
+fn test_defaults_are_omitted() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 0u32, 0);
+    example.add_scalar(7, 0u32, 0);
+    assert_eq!(vec![8u8, 0, 0, 0, 4, 0, 4, 0, 4, 0, 0, 0], example.finish());
+}
+
+#[test]
+fn test_negative_zero_is_kept() {
+    let mut table = TableBuilder::new();
+    table.add_scalar(0, 0.0f64, 0.0);
+    assert!(!table.has_field(0));
+
+    table.add_scalar(0, -0.0f64, 0.0);
+    assert!(table.has_field(0));
+    let buf = table.finish();
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let pos = root.field_position(&buf, 4).unwrap();
+    assert_eq!(
+        (-0.0f64).to_bits(),
+        f64::from_le_slice(&buf[pos..]).to_bits()
+    );
+}
+
+#[test]
+fn test_read_back() {
+    let buf = build_in_order();
+    let root = TablePosition(seek_uoffset(&buf, 0));
+
+    let version_pos = root.field_position(&buf, 4).unwrap();
+    assert_eq!(1, u32::from_le_slice(&buf[version_pos..]));
+
+    let hash_pos = root.field_position(&buf, 6).unwrap();
+    assert_eq!(0, hash_pos % 8);
+    assert_eq!(31, buf[hash_pos + 31]);
+    let hash_bytes: [u8; 32] = StructPosition(hash_pos).read_array(&buf);
+    assert_eq!(hash().as_bytes(), &hash_bytes[..]);
+
+    let scalars_pos = root.field_position(&buf, 10).unwrap();
+    assert_eq!(0.25, f64::from_le_slice(&buf[scalars_pos + 40..]));
+
+    let info_pos = root.field_position(&buf, 12).unwrap();
+    assert_eq!(
+        "info",
+        StringPosition(seek_uoffset(&buf, info_pos)).as_str(&buf)
+    );
+
+    let blocks_pos = root.field_position(&buf, 14).unwrap();
+    assert_eq!(2, VectorPosition(seek_uoffset(&buf, blocks_pos)).len(&buf));
+
+    let lines_pos = root.field_position(&buf, 18).unwrap();
+    assert_eq!(10, u32::from_le_slice(&buf[lines_pos..]));
+}
+
+#[test]
+fn test_nested_buffer() {
+    let buf = build_in_order();
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let children_pos = root.field_position(&buf, 16).unwrap();
+    let children = VectorPosition(seek_uoffset(&buf, children_pos));
+    let child = TablePosition(seek_uoffset(&buf, children.0 + 4));
+
+    let buffer_pos = child.field_position(&buf, 4).unwrap();
+    let nested = NestedBufferPosition(VectorPosition(seek_uoffset(&buf, buffer_pos)));
+    let (nested_buf, nested_root) = nested.try_root(&buf).unwrap();
+    assert_eq!(&nested_example()[..], nested_buf);
+    assert_eq!(
+        Ok(nested_root),
+        verify_canonical(&SCHEMA, EXAMPLE, nested_buf)
+    );
+
+    let version_pos = nested_root.field_position(nested_buf, 4).unwrap();
+    assert_eq!(2, u32::from_le_slice(&nested_buf[version_pos..]));
+    assert_eq!(
+        structural_hash::<Blake2b256>(&SCHEMA, EXAMPLE, &nested_example()).unwrap(),
+        structural_hash::<Blake2b256>(&SCHEMA, EXAMPLE, nested_buf).unwrap()
+    );
+}
+
+#[test]
+fn test_nested_buffer_is_canonical() {
+    // Stores the default value of `lines` explicitly.
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    example.add_scalar(7, 0u32, 1);
+    let nested = example.finish();
+    let lines_pos = TablePosition(seek_uoffset(&nested, 0))
+        .field_position(&nested, 18)
+        .unwrap();
+
+    let buf = child(&nested).finish();
+    assert!(verify(&SCHEMA, CHILD_EXAMPLE, &buf).is_ok());
+    // The position is relative to the nested buffer.
+    assert_eq!(
+        Err(Error::NonCanonical { pos: lines_pos }),
+        verify_canonical(&SCHEMA, CHILD_EXAMPLE, &buf)
+    );
+
+    let buf = child(&nested_example()).finish();
+    assert!(verify_canonical(&SCHEMA, CHILD_EXAMPLE, &buf).is_ok());
+}
+
+#[test]
+fn test_copy_table() {
+    let buf = build_in_order();
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let copy = copy_table(&SCHEMA, EXAMPLE, &buf, root).unwrap();
+    assert_eq!(buf, copy.finish());
+
+    let blocks_pos = root.field_position(&buf, 14).unwrap();
+    let blocks = VectorPosition(seek_uoffset(&buf, blocks_pos));
+    let first_block = TablePosition(seek_uoffset(&buf, blocks.0 + 4));
+    let copy = copy_table(&SCHEMA, BLOCK, &buf, first_block).unwrap();
+    assert_eq!(block("alice", &[1, 2]).finish(), copy.finish());
+}
+
+#[test]
+fn test_copy_table_drops_unknown_fields() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    example.add_scalar(8, 1u32, 0);
+    let buf = example.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let copy = copy_table(&SCHEMA, EXAMPLE, &buf, root).unwrap();
+    example.add_scalar(8, 0u32, 0);
+    assert_eq!(example.finish(), copy.finish());
+}
@@ -0,0 +1,300 @@
+// automatically generated by blockc, do not modify
+
+use blockbuffers::error::Result;
+use blockbuffers::hash::{structural_hash_table_with_options, Hasher};
+use blockbuffers::position::TablePosition;
+#[allow(unused_imports)]
+use blockbuffers::schema::{
+    Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,
+};
+use blockbuffers::verify::Options;
+
+/// Describes the schema which the hashed tables are defined in.
+pub static SCHEMA: Schema<'static> = Schema {
+    tables: &[
+        Table {
+            name: "example.Author",
+            fields: &[
+                Field {
+                    name: "name",
+                    id: 0,
+                    ty: Type::String,
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "birth",
+                    id: 1,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+        Table {
+            name: "example.Block",
+            fields: &[
+                Field {
+                    name: "author",
+                    id: 0,
+                    ty: Type::Table(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "code_type",
+                    id: 1,
+                    ty: Type::UnionType(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "code",
+                    id: 2,
+                    ty: Type::Union(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+        Table {
+            name: "example.ChildExample",
+            fields: &[Field {
+                name: "buffer",
+                id: 0,
+                ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: Some(4),
+            }],
+            key: None,
+        },
+        Table {
+            name: "example.Code",
+            fields: &[Field {
+                name: "bytes",
+                id: 0,
+                ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            }],
+            key: None,
+        },
+        Table {
+            name: "example.Example",
+            fields: &[
+                Field {
+                    name: "version",
+                    id: 0,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "language",
+                    id: 1,
+                    ty: Type::Scalar(ScalarType::Short),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "scalars",
+                    id: 2,
+                    ty: Type::Struct(1),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "info",
+                    id: 3,
+                    ty: Type::String,
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: Some(0),
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "blocks",
+                    id: 4,
+                    ty: Type::Vector(Element::Table(1)),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "children",
+                    id: 5,
+                    ty: Type::Vector(Element::Table(2)),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "lines",
+                    id: 6,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 2,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+    ],
+    structs: &[
+        Struct {
+            name: "example.H256",
+            bytesize: 1,
+            minalign: 1,
+            fields: &[StructField {
+                name: "byte_0",
+                offset: 0,
+                ty: Type::Scalar(ScalarType::UByte),
+            }],
+        },
+        Struct {
+            name: "example.Scalars",
+            bytesize: 48,
+            minalign: 8,
+            fields: &[
+                StructField {
+                    name: "a_byte",
+                    offset: 0,
+                    ty: Type::Scalar(ScalarType::Byte),
+                },
+                StructField {
+                    name: "a_ubyte",
+                    offset: 1,
+                    ty: Type::Scalar(ScalarType::UByte),
+                },
+                StructField {
+                    name: "a_bool",
+                    offset: 2,
+                    ty: Type::Scalar(ScalarType::Bool),
+                },
+                StructField {
+                    name: "a_short",
+                    offset: 4,
+                    ty: Type::Scalar(ScalarType::Short),
+                },
+                StructField {
+                    name: "a_ushort",
+                    offset: 6,
+                    ty: Type::Scalar(ScalarType::UShort),
+                },
+                StructField {
+                    name: "a_int",
+                    offset: 8,
+                    ty: Type::Scalar(ScalarType::Int),
+                },
+                StructField {
+                    name: "a_uint",
+                    offset: 12,
+                    ty: Type::Scalar(ScalarType::UInt),
+                },
+                StructField {
+                    name: "a_float",
+                    offset: 16,
+                    ty: Type::Scalar(ScalarType::Float),
+                },
+                StructField {
+                    name: "a_long",
+                    offset: 24,
+                    ty: Type::Scalar(ScalarType::Long),
+                },
+                StructField {
+                    name: "a_ulong",
+                    offset: 32,
+                    ty: Type::Scalar(ScalarType::ULong),
+                },
+                StructField {
+                    name: "a_double",
+                    offset: 40,
+                    ty: Type::Scalar(ScalarType::Double),
+                },
+            ],
+        },
+    ],
+    unions: &[Union {
+        name: "example.CodeOption",
+        variants: &[UnionVariant {
+            name: "Code",
+            tag: 1,
+            table: 3,
+        }],
+    }],
+};
+
+/// Computes the structural hash of the table `example.Example` at `pos`.
+///
+/// The table is verified first, and fields which are not in the schema are rejected.
+pub fn example_example<H: Hasher>(buf: &[u8], pos: TablePosition) -> Result<H::Output> {
+    let options = Options {
+        deny_unknown_fields: true,
+        ..Options::default()
+    };
+    structural_hash_table_with_options::<H>(&SCHEMA, 4, buf, pos, options)
+}
+
+/// Computes the structural hash of the table `example.Block` at `pos`.
+///
+/// The table is verified first, and fields which are not in the schema are rejected.
+pub fn example_block<H: Hasher>(buf: &[u8], pos: TablePosition) -> Result<H::Output> {
+    let options = Options {
+        deny_unknown_fields: true,
+        ..Options::default()
+    };
+    structural_hash_table_with_options::<H>(&SCHEMA, 1, buf, pos, options)
+}
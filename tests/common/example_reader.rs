@@ -0,0 +1,1195 @@
+// automatically generated by blockc, do not modify
+
+#[allow(unused_imports)]
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+#[allow(unused_imports)]
+use blockbuffers::error::Result;
+#[allow(unused_imports)]
+use blockbuffers::le::{LeSlice, LE};
+#[allow(unused_imports)]
+use blockbuffers::position::{
+    Elements, FixedArray, NestedBufferPosition, StringPosition, StructPosition, TablePosition,
+    VectorElement, VectorPosition,
+};
+#[allow(unused_imports)]
+use blockbuffers::root::root_table;
+#[allow(unused_imports)]
+use blockbuffers::schema::{
+    Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,
+};
+#[allow(unused_imports)]
+use blockbuffers::seek::seek_uoffset;
+#[allow(unused_imports)]
+use blockbuffers::verify::verify;
+
+open_enum! {
+    /// The enum `example.CodeOption`, which keeps unknown values.
+    pub struct CodeOption(u8): CodeOptionKnown {
+        NONE = 0,
+        Code = 1,
+    }
+}
+
+open_enum! {
+    /// The enum `example.Language`, which keeps unknown values.
+    pub struct Language(i16): LanguageKnown {
+        Rust = 0,
+        Python = 1,
+    }
+}
+
+/// Reader of the table `example.Author`.
+#[derive(Copy, Clone, Debug)]
+pub struct AuthorReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> AuthorReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        AuthorReader { buf, position }
+    }
+
+    pub fn name(&self) -> Option<&'a str> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let string = StringPosition(seek_uoffset(self.buf, pos));
+        Some(string.as_str(self.buf))
+    }
+
+    pub fn birth(&self) -> u32 {
+        match self.position.field_position(self.buf, 6) {
+            Some(pos) => u32::from_le_slice(&self.buf[pos..]),
+            None => 0,
+        }
+    }
+}
+
+impl<'a> VectorElement<'a> for AuthorReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        AuthorReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Builder of the table `example.Author`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthorBuilder {
+    table: TableBuilder,
+}
+
+impl AuthorBuilder {
+    pub fn new() -> Self {
+        AuthorBuilder::default()
+    }
+
+    pub fn add_name(&mut self, value: &str) {
+        self.table.add_string(0, value);
+    }
+
+    pub fn set_birth(&mut self, value: u32) {
+        self.table.add_scalar(1, value, 0);
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+
+/// Reader of the table `example.Block`.
+#[derive(Copy, Clone, Debug)]
+pub struct BlockReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> BlockReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        BlockReader { buf, position }
+    }
+
+    pub fn author(&self) -> Option<AuthorReader<'a>> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let table = TablePosition(seek_uoffset(self.buf, pos));
+        Some(AuthorReader::new(self.buf, table))
+    }
+
+    pub fn code_type(&self) -> CodeOption {
+        match self.position.field_position(self.buf, 6) {
+            Some(pos) => CodeOption::from_le_slice(&self.buf[pos..]),
+            None => CodeOption(0),
+        }
+    }
+
+    pub fn code(&self) -> Option<TablePosition> {
+        let pos = self.position.field_position(self.buf, 8)?;
+        Some(TablePosition(seek_uoffset(self.buf, pos)))
+    }
+
+    pub fn code_as_code(&self) -> Option<CodeReader<'a>> {
+        if self.code_type() != CodeOption::Code {
+            return None;
+        }
+        let table = self.code()?;
+        Some(CodeReader::new(self.buf, table))
+    }
+}
+
+impl<'a> VectorElement<'a> for BlockReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        BlockReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Builder of the table `example.Block`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockBuilder {
+    table: TableBuilder,
+}
+
+impl BlockBuilder {
+    pub fn new() -> Self {
+        BlockBuilder::default()
+    }
+
+    pub fn set_author(&mut self, value: AuthorBuilder) {
+        self.table.add_table(0, value.into_table());
+    }
+
+    pub fn set_code_as_code(&mut self, value: CodeBuilder) {
+        let tag = CodeOption::Code.0;
+        self.table.add_union(2, tag, value.into_table());
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+
+/// Reader of the table `example.ChildExample`.
+#[derive(Copy, Clone, Debug)]
+pub struct ChildExampleReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> ChildExampleReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        ChildExampleReader { buf, position }
+    }
+
+    pub fn buffer(&self) -> Option<LeSlice<'a, u8>> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.as_le_slice(self.buf))
+    }
+
+    pub fn buffer_nested_flatbuffer(&self) -> Option<ExampleReader<'a>> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let nested = NestedBufferPosition(VectorPosition(seek_uoffset(self.buf, pos)));
+        let (buf, root) = nested.root(self.buf);
+        Some(ExampleReader::new(buf, root))
+    }
+}
+
+impl<'a> VectorElement<'a> for ChildExampleReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        ChildExampleReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Builder of the table `example.ChildExample`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChildExampleBuilder {
+    table: TableBuilder,
+}
+
+impl ChildExampleBuilder {
+    pub fn new() -> Self {
+        ChildExampleBuilder::default()
+    }
+
+    pub fn add_buffer(&mut self, items: &[u8]) {
+        self.table.add_vector(0, items);
+    }
+
+    pub fn set_buffer_nested_flatbuffer(&mut self, value: ExampleBuilder) {
+        self.table.add_vector(0, &value.finish());
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+
+/// Reader of the table `example.Code`.
+#[derive(Copy, Clone, Debug)]
+pub struct CodeReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> CodeReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        CodeReader { buf, position }
+    }
+
+    pub fn bytes(&self) -> Option<LeSlice<'a, u8>> {
+        let pos = self.position.field_position(self.buf, 4)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.as_le_slice(self.buf))
+    }
+}
+
+impl<'a> VectorElement<'a> for CodeReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        CodeReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Builder of the table `example.Code`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CodeBuilder {
+    table: TableBuilder,
+}
+
+impl CodeBuilder {
+    pub fn new() -> Self {
+        CodeBuilder::default()
+    }
+
+    pub fn add_bytes(&mut self, items: &[u8]) {
+        self.table.add_vector(0, items);
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+
+/// Reader of the table `example.Example`.
+#[derive(Copy, Clone, Debug)]
+pub struct ExampleReader<'a> {
+    pub buf: &'a [u8],
+    pub position: TablePosition,
+}
+
+impl<'a> ExampleReader<'a> {
+    /// Reads the table at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: TablePosition) -> Self {
+        ExampleReader { buf, position }
+    }
+
+    pub fn version(&self) -> u32 {
+        match self.position.field_position(self.buf, 4) {
+            Some(pos) => u32::from_le_slice(&self.buf[pos..]),
+            None => 0,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        match self.position.field_position(self.buf, 6) {
+            Some(pos) => Language::from_le_slice(&self.buf[pos..]),
+            None => Language(0),
+        }
+    }
+
+    pub fn scalars(&self) -> Option<ScalarsReader<'a>> {
+        let pos = self.position.field_position(self.buf, 8)?;
+        Some(ScalarsReader::new(self.buf, StructPosition(pos)))
+    }
+
+    pub fn blocks(&self) -> Option<Elements<'a, BlockReader<'a>>> {
+        let pos = self.position.field_position(self.buf, 12)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.elements(self.buf, 4))
+    }
+
+    pub fn children(&self) -> Option<Elements<'a, ChildExampleReader<'a>>> {
+        let pos = self.position.field_position(self.buf, 14)?;
+        let vector = VectorPosition(seek_uoffset(self.buf, pos));
+        Some(vector.elements(self.buf, 4))
+    }
+
+    pub fn lines(&self) -> u32 {
+        match self.position.field_position(self.buf, 16) {
+            Some(pos) => u32::from_le_slice(&self.buf[pos..]),
+            None => 0,
+        }
+    }
+}
+
+impl<'a> VectorElement<'a> for ExampleReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        ExampleReader::new(buf, TablePosition(seek_uoffset(buf, pos)))
+    }
+}
+
+/// Builder of the table `example.Example`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleBuilder {
+    table: TableBuilder,
+}
+
+impl ExampleBuilder {
+    pub fn new() -> Self {
+        ExampleBuilder::default()
+    }
+
+    pub fn set_version(&mut self, value: u32) {
+        self.table.add_scalar(0, value, 0);
+    }
+
+    pub fn set_language(&mut self, value: Language) {
+        self.table.add_scalar(1, value, Language(0));
+    }
+
+    pub fn set_scalars(&mut self, value: &ScalarsBuilder) {
+        self.table.add_struct(2, &value.st);
+    }
+
+    pub fn add_blocks(&mut self, items: Vec<BlockBuilder>) {
+        let items = items.into_iter().map(BlockBuilder::into_table).collect();
+        self.table.add_table_vector(4, items);
+    }
+
+    pub fn add_children(&mut self, items: Vec<ChildExampleBuilder>) {
+        let items = items
+            .into_iter()
+            .map(ChildExampleBuilder::into_table)
+            .collect();
+        self.table.add_table_vector(5, items);
+    }
+
+    pub fn set_lines(&mut self, value: u32) {
+        self.table.add_scalar(6, value, 0);
+    }
+
+    /// Gets the table, panics if a required field is missing.
+    pub fn into_table(self) -> TableBuilder {
+        self.table
+    }
+
+    /// Writes the buffer using this table as the root.
+    pub fn finish(self) -> Vec<u8> {
+        self.into_table().finish()
+    }
+}
+
+/// Reader of the struct `example.H256`.
+#[derive(Copy, Clone, Debug)]
+pub struct H256Reader<'a> {
+    pub buf: &'a [u8],
+    pub position: StructPosition,
+}
+
+impl<'a> H256Reader<'a> {
+    /// Reads the struct at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: StructPosition) -> Self {
+        H256Reader { buf, position }
+    }
+
+    pub fn byte_0(&self) -> u8 {
+        self.position.read(self.buf, 0)
+    }
+}
+
+impl<'a> VectorElement<'a> for H256Reader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        H256Reader::new(buf, StructPosition(pos))
+    }
+}
+
+/// Builder of the struct `example.H256`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct H256Builder {
+    st: StructBuilder,
+}
+
+impl H256Builder {
+    pub fn new() -> Self {
+        H256Builder {
+            st: StructBuilder::new(1, 1),
+        }
+    }
+
+    pub fn set_byte_0(&mut self, value: u8) {
+        self.st.set(0, value);
+    }
+
+    /// Gets the inline bytes of the struct.
+    pub fn into_struct(self) -> StructBuilder {
+        self.st
+    }
+}
+
+impl Default for H256Builder {
+    fn default() -> Self {
+        H256Builder::new()
+    }
+}
+
+/// Reader of the struct `example.Scalars`.
+#[derive(Copy, Clone, Debug)]
+pub struct ScalarsReader<'a> {
+    pub buf: &'a [u8],
+    pub position: StructPosition,
+}
+
+impl<'a> ScalarsReader<'a> {
+    /// Reads the struct at `position` in the verified buffer.
+    pub fn new(buf: &'a [u8], position: StructPosition) -> Self {
+        ScalarsReader { buf, position }
+    }
+
+    pub fn a_byte(&self) -> i8 {
+        self.position.read(self.buf, 0)
+    }
+
+    pub fn a_ubyte(&self) -> u8 {
+        self.position.read(self.buf, 1)
+    }
+
+    pub fn a_bool(&self) -> bool {
+        self.position.read(self.buf, 2)
+    }
+
+    pub fn a_short(&self) -> i16 {
+        self.position.read(self.buf, 4)
+    }
+
+    pub fn a_ushort(&self) -> u16 {
+        self.position.read(self.buf, 6)
+    }
+
+    pub fn a_int(&self) -> i32 {
+        self.position.read(self.buf, 8)
+    }
+
+    pub fn a_uint(&self) -> u32 {
+        self.position.read(self.buf, 12)
+    }
+
+    pub fn a_float(&self) -> f32 {
+        self.position.read(self.buf, 16)
+    }
+
+    pub fn a_long(&self) -> i64 {
+        self.position.read(self.buf, 24)
+    }
+
+    pub fn a_ulong(&self) -> u64 {
+        self.position.read(self.buf, 32)
+    }
+
+    pub fn a_double(&self) -> f64 {
+        self.position.read(self.buf, 40)
+    }
+}
+
+impl<'a> VectorElement<'a> for ScalarsReader<'a> {
+    fn from_element(buf: &'a [u8], pos: usize) -> Self {
+        ScalarsReader::new(buf, StructPosition(pos))
+    }
+}
+
+/// Builder of the struct `example.Scalars`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScalarsBuilder {
+    st: StructBuilder,
+}
+
+impl ScalarsBuilder {
+    pub fn new() -> Self {
+        ScalarsBuilder {
+            st: StructBuilder::new(48, 8),
+        }
+    }
+
+    pub fn set_a_byte(&mut self, value: i8) {
+        self.st.set(0, value);
+    }
+
+    pub fn set_a_ubyte(&mut self, value: u8) {
+        self.st.set(1, value);
+    }
+
+    pub fn set_a_bool(&mut self, value: bool) {
+        self.st.set(2, value);
+    }
+
+    pub fn set_a_short(&mut self, value: i16) {
+        self.st.set(4, value);
+    }
+
+    pub fn set_a_ushort(&mut self, value: u16) {
+        self.st.set(6, value);
+    }
+
+    pub fn set_a_int(&mut self, value: i32) {
+        self.st.set(8, value);
+    }
+
+    pub fn set_a_uint(&mut self, value: u32) {
+        self.st.set(12, value);
+    }
+
+    pub fn set_a_float(&mut self, value: f32) {
+        self.st.set(16, value);
+    }
+
+    pub fn set_a_long(&mut self, value: i64) {
+        self.st.set(24, value);
+    }
+
+    pub fn set_a_ulong(&mut self, value: u64) {
+        self.st.set(32, value);
+    }
+
+    pub fn set_a_double(&mut self, value: f64) {
+        self.st.set(40, value);
+    }
+
+    /// Gets the inline bytes of the struct.
+    pub fn into_struct(self) -> StructBuilder {
+        self.st
+    }
+}
+
+impl Default for ScalarsBuilder {
+    fn default() -> Self {
+        ScalarsBuilder::new()
+    }
+}
+
+/// Describes the schema, which is used to verify the buffers to decode.
+pub static SCHEMA: Schema<'static> = Schema {
+    tables: &[
+        Table {
+            name: "example.Author",
+            fields: &[
+                Field {
+                    name: "name",
+                    id: 0,
+                    ty: Type::String,
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "birth",
+                    id: 1,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+        Table {
+            name: "example.Block",
+            fields: &[
+                Field {
+                    name: "author",
+                    id: 0,
+                    ty: Type::Table(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "code_type",
+                    id: 1,
+                    ty: Type::UnionType(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "code",
+                    id: 2,
+                    ty: Type::Union(0),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+        Table {
+            name: "example.ChildExample",
+            fields: &[Field {
+                name: "buffer",
+                id: 0,
+                ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: Some(4),
+            }],
+            key: None,
+        },
+        Table {
+            name: "example.Code",
+            fields: &[Field {
+                name: "bytes",
+                id: 0,
+                ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+                required: false,
+                default_integer: 0,
+                default_real: 0.0,
+                since_version: 0,
+                deprecated_version: None,
+                nested_flatbuffer: None,
+            }],
+            key: None,
+        },
+        Table {
+            name: "example.Example",
+            fields: &[
+                Field {
+                    name: "version",
+                    id: 0,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "language",
+                    id: 1,
+                    ty: Type::Scalar(ScalarType::Short),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "scalars",
+                    id: 2,
+                    ty: Type::Struct(1),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "info",
+                    id: 3,
+                    ty: Type::String,
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: Some(0),
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "blocks",
+                    id: 4,
+                    ty: Type::Vector(Element::Table(1)),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "children",
+                    id: 5,
+                    ty: Type::Vector(Element::Table(2)),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 0,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+                Field {
+                    name: "lines",
+                    id: 6,
+                    ty: Type::Scalar(ScalarType::UInt),
+                    required: false,
+                    default_integer: 0,
+                    default_real: 0.0,
+                    since_version: 2,
+                    deprecated_version: None,
+                    nested_flatbuffer: None,
+                },
+            ],
+            key: None,
+        },
+    ],
+    structs: &[
+        Struct {
+            name: "example.H256",
+            bytesize: 1,
+            minalign: 1,
+            fields: &[StructField {
+                name: "byte_0",
+                offset: 0,
+                ty: Type::Scalar(ScalarType::UByte),
+            }],
+        },
+        Struct {
+            name: "example.Scalars",
+            bytesize: 48,
+            minalign: 8,
+            fields: &[
+                StructField {
+                    name: "a_byte",
+                    offset: 0,
+                    ty: Type::Scalar(ScalarType::Byte),
+                },
+                StructField {
+                    name: "a_ubyte",
+                    offset: 1,
+                    ty: Type::Scalar(ScalarType::UByte),
+                },
+                StructField {
+                    name: "a_bool",
+                    offset: 2,
+                    ty: Type::Scalar(ScalarType::Bool),
+                },
+                StructField {
+                    name: "a_short",
+                    offset: 4,
+                    ty: Type::Scalar(ScalarType::Short),
+                },
+                StructField {
+                    name: "a_ushort",
+                    offset: 6,
+                    ty: Type::Scalar(ScalarType::UShort),
+                },
+                StructField {
+                    name: "a_int",
+                    offset: 8,
+                    ty: Type::Scalar(ScalarType::Int),
+                },
+                StructField {
+                    name: "a_uint",
+                    offset: 12,
+                    ty: Type::Scalar(ScalarType::UInt),
+                },
+                StructField {
+                    name: "a_float",
+                    offset: 16,
+                    ty: Type::Scalar(ScalarType::Float),
+                },
+                StructField {
+                    name: "a_long",
+                    offset: 24,
+                    ty: Type::Scalar(ScalarType::Long),
+                },
+                StructField {
+                    name: "a_ulong",
+                    offset: 32,
+                    ty: Type::Scalar(ScalarType::ULong),
+                },
+                StructField {
+                    name: "a_double",
+                    offset: 40,
+                    ty: Type::Scalar(ScalarType::Double),
+                },
+            ],
+        },
+    ],
+    unions: &[Union {
+        name: "example.CodeOption",
+        variants: &[UnionVariant {
+            name: "Code",
+            tag: 1,
+            table: 3,
+        }],
+    }],
+};
+
+/// Owned value of the table `example.Author`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Author {
+    pub name: Option<String>,
+    pub birth: u32,
+}
+
+impl Author {
+    /// Verifies the buffer which root table is `example.Author`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 0, buf)?;
+        Author::from_reader(AuthorReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: AuthorReader) -> Result<Self> {
+        let (buf, position) = (reader.buf, reader.position);
+        let name = reader.name().map(String::from);
+        let birth = position.try_read_field(buf, 6, 0)?;
+        Ok(Author { name, birth })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> AuthorBuilder {
+        let mut builder = AuthorBuilder::new();
+        if let Some(ref value) = self.name {
+            builder.add_name(value);
+        }
+        builder.set_birth(self.birth);
+        builder
+    }
+}
+
+/// Owned value of the table `example.Block`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+    pub author: Option<Box<Author>>,
+    pub code: Option<CodeOptionValue>,
+}
+
+impl Block {
+    /// Verifies the buffer which root table is `example.Block`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 1, buf)?;
+        Block::from_reader(BlockReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: BlockReader) -> Result<Self> {
+        let author = match reader.author() {
+            Some(value) => Some(Box::new(Author::from_reader(value)?)),
+            None => None,
+        };
+        let mut code = None;
+        if let Some(value) = reader.code_as_code() {
+            let value = Code::from_reader(value)?;
+            code = Some(CodeOptionValue::Code(Box::new(value)));
+        }
+        Ok(Block { author, code })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> BlockBuilder {
+        let mut builder = BlockBuilder::new();
+        if let Some(ref value) = self.author {
+            builder.set_author(value.to_builder());
+        }
+        if let Some(ref value) = self.code {
+            match *value {
+                CodeOptionValue::Code(ref value) => {
+                    builder.set_code_as_code(value.to_builder());
+                }
+            }
+        }
+        builder
+    }
+}
+
+/// Owned value of the table `example.ChildExample`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChildExample {
+    pub buffer: Option<Vec<u8>>,
+}
+
+impl ChildExample {
+    /// Verifies the buffer which root table is `example.ChildExample`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 2, buf)?;
+        ChildExample::from_reader(ChildExampleReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: ChildExampleReader) -> Result<Self> {
+        let (buf, position) = (reader.buf, reader.position);
+        let buffer = match position.try_field_position(buf, 4)? {
+            Some(pos) => Some(VectorPosition(seek_uoffset(buf, pos)).try_to_vec(buf)?),
+            None => None,
+        };
+        Ok(ChildExample { buffer })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> ChildExampleBuilder {
+        let mut builder = ChildExampleBuilder::new();
+        if let Some(ref value) = self.buffer {
+            builder.add_buffer(value);
+        }
+        builder
+    }
+}
+
+/// Owned value of the table `example.Code`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Code {
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl Code {
+    /// Verifies the buffer which root table is `example.Code`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 3, buf)?;
+        Code::from_reader(CodeReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: CodeReader) -> Result<Self> {
+        let (buf, position) = (reader.buf, reader.position);
+        let bytes = match position.try_field_position(buf, 4)? {
+            Some(pos) => Some(VectorPosition(seek_uoffset(buf, pos)).try_to_vec(buf)?),
+            None => None,
+        };
+        Ok(Code { bytes })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> CodeBuilder {
+        let mut builder = CodeBuilder::new();
+        if let Some(ref value) = self.bytes {
+            builder.add_bytes(value);
+        }
+        builder
+    }
+}
+
+/// Owned value of the table `example.Example`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub version: u32,
+    pub language: Language,
+    pub scalars: Option<Scalars>,
+    pub blocks: Option<Vec<Block>>,
+    pub children: Option<Vec<ChildExample>>,
+    pub lines: u32,
+}
+
+impl Default for Example {
+    fn default() -> Self {
+        Example {
+            version: 0,
+            language: Language(0),
+            scalars: None,
+            blocks: None,
+            children: None,
+            lines: 0,
+        }
+    }
+}
+
+impl Example {
+    /// Verifies the buffer which root table is `example.Example`, and copies the table out.
+    pub fn decode(buf: &[u8]) -> Result<Self> {
+        let root = verify(&SCHEMA, 4, buf)?;
+        Example::from_reader(ExampleReader::new(buf, root))
+    }
+
+    /// Copies the table out of the verified buffer.
+    pub fn from_reader(reader: ExampleReader) -> Result<Self> {
+        let (buf, position) = (reader.buf, reader.position);
+        let version = position.try_read_field(buf, 4, 0)?;
+        let language = position.try_read_field(buf, 6, Language(0))?;
+        let scalars = match reader.scalars() {
+            Some(value) => Some(Scalars::from_reader(value)?),
+            None => None,
+        };
+        let blocks = match reader.blocks() {
+            Some(items) => Some(items.map(Block::from_reader).collect::<Result<Vec<_>>>()?),
+            None => None,
+        };
+        let children = match reader.children() {
+            Some(items) => Some(
+                items
+                    .map(ChildExample::from_reader)
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let lines = position.try_read_field(buf, 16, 0)?;
+        Ok(Example {
+            version,
+            language,
+            scalars,
+            blocks,
+            children,
+            lines,
+        })
+    }
+
+    /// Encodes the table as the root of a canonical buffer.
+    pub fn encode(&self) -> Vec<u8> {
+        self.to_builder().finish()
+    }
+
+    /// Gets the builder which encodes the table.
+    pub fn to_builder(&self) -> ExampleBuilder {
+        let mut builder = ExampleBuilder::new();
+        builder.set_version(self.version);
+        builder.set_language(self.language);
+        if let Some(ref value) = self.scalars {
+            builder.set_scalars(&value.to_builder());
+        }
+        if let Some(ref value) = self.blocks {
+            let items: Vec<_> = value.iter().map(Block::to_builder).collect();
+            builder.add_blocks(items);
+        }
+        if let Some(ref value) = self.children {
+            let items: Vec<_> = value.iter().map(ChildExample::to_builder).collect();
+            builder.add_children(items);
+        }
+        builder.set_lines(self.lines);
+        builder
+    }
+}
+
+/// Owned value of the struct `example.H256`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct H256 {
+    pub byte_0: u8,
+}
+
+impl H256 {
+    /// Copies the struct out of the verified buffer.
+    pub fn from_reader(reader: H256Reader) -> Result<Self> {
+        Ok(H256 {
+            byte_0: reader.position.try_read(reader.buf, 0)?,
+        })
+    }
+
+    /// Gets the builder which encodes the struct.
+    pub fn to_builder(self) -> H256Builder {
+        let mut builder = H256Builder::new();
+        builder.set_byte_0(self.byte_0);
+        builder
+    }
+}
+
+/// Owned value of the struct `example.Scalars`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Scalars {
+    pub a_byte: i8,
+    pub a_ubyte: u8,
+    pub a_bool: bool,
+    pub a_short: i16,
+    pub a_ushort: u16,
+    pub a_int: i32,
+    pub a_uint: u32,
+    pub a_float: f32,
+    pub a_long: i64,
+    pub a_ulong: u64,
+    pub a_double: f64,
+}
+
+impl Scalars {
+    /// Copies the struct out of the verified buffer.
+    pub fn from_reader(reader: ScalarsReader) -> Result<Self> {
+        Ok(Scalars {
+            a_byte: reader.position.try_read(reader.buf, 0)?,
+            a_ubyte: reader.position.try_read(reader.buf, 1)?,
+            a_bool: reader.position.try_read(reader.buf, 2)?,
+            a_short: reader.position.try_read(reader.buf, 4)?,
+            a_ushort: reader.position.try_read(reader.buf, 6)?,
+            a_int: reader.position.try_read(reader.buf, 8)?,
+            a_uint: reader.position.try_read(reader.buf, 12)?,
+            a_float: reader.position.try_read(reader.buf, 16)?,
+            a_long: reader.position.try_read(reader.buf, 24)?,
+            a_ulong: reader.position.try_read(reader.buf, 32)?,
+            a_double: reader.position.try_read(reader.buf, 40)?,
+        })
+    }
+
+    /// Gets the builder which encodes the struct.
+    pub fn to_builder(self) -> ScalarsBuilder {
+        let mut builder = ScalarsBuilder::new();
+        builder.set_a_byte(self.a_byte);
+        builder.set_a_ubyte(self.a_ubyte);
+        builder.set_a_bool(self.a_bool);
+        builder.set_a_short(self.a_short);
+        builder.set_a_ushort(self.a_ushort);
+        builder.set_a_int(self.a_int);
+        builder.set_a_uint(self.a_uint);
+        builder.set_a_float(self.a_float);
+        builder.set_a_long(self.a_long);
+        builder.set_a_ulong(self.a_ulong);
+        builder.set_a_double(self.a_double);
+        builder
+    }
+}
+
+/// Owned value of the union `example.CodeOption`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CodeOptionValue {
+    Code(Box<Code>),
+}
+
+/// Reads the root table `example.Example` of the verified buffer.
+pub fn get_root_as_example(buf: &[u8]) -> ExampleReader<'_> {
+    ExampleReader::new(buf, root_table(buf))
+}
@@ -0,0 +1,465 @@
+//! Describes `example.fbs` for the verifier and the builder.
+
+use blockbuffers::schema::{
+    Element, Field, ScalarType, Schema, Struct, StructField, Table, Type, Union, UnionVariant,
+};
+
+pub const AUTHOR: usize = 0;
+pub const BLOCK: usize = 1;
+pub const CHILD_EXAMPLE: usize = 2;
+pub const CODE: usize = 3;
+pub const EXAMPLE: usize = 4;
+
+pub const SCALARS: usize = 0;
+pub const U256: usize = 1;
+
+pub const CODE_OPTION: usize = 0;
+
+pub static SCHEMA: Schema<'static> = Schema {
+    tables: &[
+        Table {
+            name: "example.Author",
+            fields: AUTHOR_FIELDS,
+            key: None,
+        },
+        Table {
+            name: "example.Block",
+            fields: BLOCK_FIELDS,
+            key: None,
+        },
+        Table {
+            name: "example.ChildExample",
+            fields: CHILD_EXAMPLE_FIELDS,
+            key: None,
+        },
+        Table {
+            name: "example.Code",
+            fields: CODE_FIELDS,
+            key: None,
+        },
+        Table {
+            name: "example.Example",
+            fields: EXAMPLE_FIELDS,
+            key: None,
+        },
+    ],
+    structs: &[
+        Struct {
+            name: "example.Scalars",
+            bytesize: 48,
+            minalign: 8,
+            fields: SCALARS_FIELDS,
+        },
+        Struct {
+            name: "example.U256",
+            bytesize: 32,
+            minalign: 8,
+            fields: U256_FIELDS,
+        },
+    ],
+    unions: &[Union {
+        name: "example.CodeOption",
+        variants: &[UnionVariant {
+            name: "Code",
+            tag: 1,
+            table: CODE,
+        }],
+    }],
+};
+
+static AUTHOR_FIELDS: &[Field] = &[
+    Field {
+        name: "name",
+        id: 0,
+        ty: Type::String,
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "birth",
+        id: 1,
+        ty: Type::Scalar(ScalarType::UInt),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+];
+
+static BLOCK_FIELDS: &[Field] = &[
+    Field {
+        name: "author",
+        id: 0,
+        ty: Type::Table(AUTHOR),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "code_type",
+        id: 1,
+        ty: Type::UnionType(CODE_OPTION),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "code",
+        id: 2,
+        ty: Type::Union(CODE_OPTION),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+];
+
+static CHILD_EXAMPLE_FIELDS: &[Field] = &[Field {
+    name: "buffer",
+    id: 0,
+    ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+    required: false,
+    default_integer: 0,
+    default_real: 0.0,
+    since_version: 0,
+    deprecated_version: None,
+    nested_flatbuffer: Some(EXAMPLE),
+}];
+
+static CODE_FIELDS: &[Field] = &[Field {
+    name: "bytes",
+    id: 0,
+    ty: Type::Vector(Element::Scalar(ScalarType::UByte)),
+    required: false,
+    default_integer: 0,
+    default_real: 0.0,
+    since_version: 0,
+    deprecated_version: None,
+    nested_flatbuffer: None,
+}];
+
+static EXAMPLE_FIELDS: &[Field] = &[
+    Field {
+        name: "version",
+        id: 0,
+        ty: Type::Scalar(ScalarType::UInt),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "hash",
+        id: 1,
+        ty: Type::Struct(U256),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "language",
+        id: 2,
+        ty: Type::Scalar(ScalarType::Short),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "scalars",
+        id: 3,
+        ty: Type::Struct(SCALARS),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "info",
+        id: 4,
+        ty: Type::String,
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: Some(2),
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "blocks",
+        id: 5,
+        ty: Type::Vector(Element::Table(BLOCK)),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "children",
+        id: 6,
+        ty: Type::Vector(Element::Table(CHILD_EXAMPLE)),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+    Field {
+        name: "lines",
+        id: 7,
+        ty: Type::Scalar(ScalarType::UInt),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 2,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    },
+];
+
+static SCALARS_FIELDS: &[StructField] = &[
+    StructField {
+        name: "a_byte",
+        offset: 0,
+        ty: Type::Scalar(ScalarType::Byte),
+    },
+    StructField {
+        name: "a_ubyte",
+        offset: 1,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "a_bool",
+        offset: 2,
+        ty: Type::Scalar(ScalarType::Bool),
+    },
+    StructField {
+        name: "a_short",
+        offset: 4,
+        ty: Type::Scalar(ScalarType::Short),
+    },
+    StructField {
+        name: "a_ushort",
+        offset: 6,
+        ty: Type::Scalar(ScalarType::UShort),
+    },
+    StructField {
+        name: "a_int",
+        offset: 8,
+        ty: Type::Scalar(ScalarType::Int),
+    },
+    StructField {
+        name: "a_uint",
+        offset: 12,
+        ty: Type::Scalar(ScalarType::UInt),
+    },
+    StructField {
+        name: "a_float",
+        offset: 16,
+        ty: Type::Scalar(ScalarType::Float),
+    },
+    StructField {
+        name: "a_long",
+        offset: 24,
+        ty: Type::Scalar(ScalarType::Long),
+    },
+    StructField {
+        name: "a_ulong",
+        offset: 32,
+        ty: Type::Scalar(ScalarType::ULong),
+    },
+    StructField {
+        name: "a_double",
+        offset: 40,
+        ty: Type::Scalar(ScalarType::Double),
+    },
+];
+
+static U256_FIELDS: &[StructField] = &[
+    StructField {
+        name: "byte_0",
+        offset: 0,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_1",
+        offset: 1,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_2",
+        offset: 2,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_3",
+        offset: 3,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_4",
+        offset: 4,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_5",
+        offset: 5,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_6",
+        offset: 6,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_7",
+        offset: 7,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_8",
+        offset: 8,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_9",
+        offset: 9,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_10",
+        offset: 10,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_11",
+        offset: 11,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_12",
+        offset: 12,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_13",
+        offset: 13,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_14",
+        offset: 14,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_15",
+        offset: 15,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_16",
+        offset: 16,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_17",
+        offset: 17,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_18",
+        offset: 18,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_19",
+        offset: 19,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_20",
+        offset: 20,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_21",
+        offset: 21,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_22",
+        offset: 22,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_23",
+        offset: 23,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_24",
+        offset: 24,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_25",
+        offset: 25,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_26",
+        offset: 26,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_27",
+        offset: 27,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_28",
+        offset: 28,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_29",
+        offset: 29,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_30",
+        offset: 30,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+    StructField {
+        name: "byte_31",
+        offset: 31,
+        ty: Type::Scalar(ScalarType::UByte),
+    },
+];
@@ -1 +1,3 @@
-pub mod example_generated;
+pub mod example_hash;
+pub mod example_reader;
+pub mod example_schema;
@@ -0,0 +1,190 @@
+extern crate blockbuffers;
+
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+use blockbuffers::dynamic::{DynTable, DynValue, Scalar};
+use blockbuffers::reflection::Schema;
+use blockbuffers::Error;
+
+fn example_bfbs() -> Schema {
+    Schema::from_slice(&include_bytes!("common/example.bfbs")[..]).unwrap()
+}
+
+fn example_buf() -> Vec<u8> {
+    let mut author = TableBuilder::new();
+    author.add_string(0, "alice");
+    author.add_scalar(1, 1990u32, 0);
+
+    let mut code = TableBuilder::new();
+    code.add_vector(0, &[1u8, 2, 3]);
+
+    let mut block = TableBuilder::new();
+    block.add_table(0, author);
+    block.add_union(2, 1, code);
+
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(4, -2i16);
+    scalars.set(40, 0.25f64);
+
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    example.add_scalar(1, 1i16, 0);
+    example.add_struct(2, &scalars);
+    example.add_table_vector(4, vec![block, TableBuilder::new()]);
+    example.finish()
+}
+
+fn scalar(value: Option<DynValue>) -> Scalar {
+    match value {
+        Some(DynValue::Scalar(scalar)) => scalar,
+        other => panic!("not a scalar: {:?}", other),
+    }
+}
+
+#[test]
+fn test_scalars() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    let example = DynTable::root(&schema, &buf).unwrap();
+
+    assert_eq!(
+        Scalar::UInt(2),
+        scalar(example.get(&schema, &buf, "version").unwrap())
+    );
+    assert_eq!(
+        Scalar::Short(1),
+        scalar(example.get(&schema, &buf, "language").unwrap())
+    );
+    assert_eq!(
+        Scalar::UInt(0),
+        scalar(example.get(&schema, &buf, "lines").unwrap())
+    );
+    assert_eq!(None, example.get(&schema, &buf, "info").unwrap());
+    assert_eq!(None, example.get(&schema, &buf, "no_such_field").unwrap());
+}
+
+#[test]
+fn test_struct() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    let example = DynTable::root(&schema, &buf).unwrap();
+
+    let scalars = match example.get(&schema, &buf, "scalars").unwrap() {
+        Some(DynValue::Struct(scalars)) => scalars,
+        other => panic!("not a struct: {:?}", other),
+    };
+    assert_eq!(
+        Scalar::Short(-2),
+        scalar(scalars.get(&schema, &buf, "a_short").unwrap())
+    );
+    assert_eq!(
+        Scalar::Double(0.25),
+        scalar(scalars.get(&schema, &buf, "a_double").unwrap())
+    );
+    assert_eq!(
+        Scalar::Bool(false),
+        scalar(scalars.get(&schema, &buf, "a_bool").unwrap())
+    );
+}
+
+#[test]
+fn test_vector_of_tables_and_union() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    let example = DynTable::root(&schema, &buf).unwrap();
+
+    let blocks = match example.get(&schema, &buf, "blocks").unwrap() {
+        Some(DynValue::Vector(blocks)) => blocks,
+        other => panic!("not a vector: {:?}", other),
+    };
+    assert_eq!(2, blocks.len(&buf).unwrap());
+
+    let block = match blocks.get(&schema, &buf, 0).unwrap() {
+        DynValue::Table(block) => block,
+        other => panic!("not a table: {:?}", other),
+    };
+    let author = match block.get(&schema, &buf, "author").unwrap() {
+        Some(DynValue::Table(author)) => author,
+        other => panic!("not a table: {:?}", other),
+    };
+    assert_eq!(
+        Some(DynValue::String("alice")),
+        author.get(&schema, &buf, "name").unwrap()
+    );
+
+    let code = match block.get(&schema, &buf, "code").unwrap() {
+        Some(DynValue::Union(code)) => code,
+        other => panic!("not a union: {:?}", other),
+    };
+    assert_eq!(1, code.tag);
+    assert_eq!("Code", code.name);
+    let bytes = match code.table.get(&schema, &buf, "bytes").unwrap() {
+        Some(DynValue::Vector(bytes)) => bytes,
+        other => panic!("not a vector: {:?}", other),
+    };
+    assert_eq!(3, bytes.len(&buf).unwrap());
+    assert_eq!(
+        DynValue::Scalar(Scalar::UByte(3)),
+        bytes.get(&schema, &buf, 2).unwrap()
+    );
+
+    let empty = match blocks.get(&schema, &buf, 1).unwrap() {
+        DynValue::Table(block) => block,
+        other => panic!("not a table: {:?}", other),
+    };
+    assert_eq!(None, empty.get(&schema, &buf, "code").unwrap());
+}
+
+#[test]
+fn test_malformed() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    let example = DynTable::root(&schema, &buf).unwrap();
+
+    match example.get(&schema, &buf[..16], "version") {
+        Err(Error::OutOfBounds { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+/// Makes the type of the field refer to a missing object or union.
+fn break_index(schema: &mut Schema, object: &str, field: &str) {
+    let index = schema.object_index(object).unwrap();
+    let field = schema.objects[index]
+        .fields
+        .iter_mut()
+        .find(|f| f.name == field)
+        .unwrap();
+    field.ty.index = 99;
+}
+
+fn assert_bad_type(result: Result<Option<DynValue>, Error>) {
+    match result {
+        Err(Error::BadType { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_bad_schema_type() {
+    let mut schema = example_bfbs();
+    break_index(&mut schema, "example.Example", "scalars");
+    break_index(&mut schema, "example.Block", "code");
+    let buf = example_buf();
+
+    // The root table is a copy of the object, so it is not broken.
+    let root = DynTable::root(&schema, &buf).unwrap();
+    let example = DynTable {
+        object: schema.object("example.Example").unwrap(),
+        position: root.position,
+    };
+    assert_bad_type(example.get(&schema, &buf, "scalars"));
+
+    let block = match root.get(&schema, &buf, "blocks").unwrap() {
+        Some(DynValue::Vector(blocks)) => blocks.get(&schema, &buf, 0).unwrap(),
+        other => panic!("not a vector: {:?}", other),
+    };
+    match block {
+        DynValue::Table(block) => assert_bad_type(block.get(&schema, &buf, "code")),
+        other => panic!("not a table: {:?}", other),
+    }
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate blockbuffers;
+
+use blockbuffers::builder::TableBuilder;
+use blockbuffers::le::LeSlice;
+use blockbuffers::position::{TablePosition, VectorPosition};
+use blockbuffers::seek::{seek_uoffset, try_read};
+
+open_enum! {
+    /// enum Language: short { Rust, Python }
+    pub struct Language(i16): LanguageKnown {
+        Rust = 0,
+        Python = 1,
+    }
+}
+
+// table Example { language: Language; } written by a peer which knows the variant `Go = 2`.
+fn example(language: i16) -> Vec<u8> {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, language, 0);
+    example.finish()
+}
+
+#[test]
+fn test_open_enum() {
+    let buf = example(1);
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let pos = root.field_position(&buf, 4).unwrap();
+    let language = try_read::<Language>(&buf, pos).unwrap();
+    assert_eq!(Language::Python, language);
+    assert_eq!(Some(LanguageKnown::Python), language.known());
+}
+
+#[test]
+fn test_unknown_value_round_trip() {
+    let buf = example(2);
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let pos = root.field_position(&buf, 4).unwrap();
+    let go = try_read::<Language>(&buf, pos).unwrap();
+    assert_eq!(Language(2), go);
+    assert_eq!(None, go.known());
+
+    let mut copy = TableBuilder::new();
+    copy.add_scalar(0, go, Language::Rust);
+    assert_eq!(buf, copy.finish());
+}
+
+#[test]
+fn test_vector_of_open_enums() {
+    let mut table = TableBuilder::new();
+    table.add_vector(0, &[Language::Python, Language(7)]);
+    let buf = table.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let vector = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    let languages: LeSlice<Language> = vector.try_as_le_slice(&buf).unwrap();
+    let known: Vec<_> = languages.iter().map(Language::known).collect();
+    assert_eq!(vec![Some(LanguageKnown::Python), None], known);
+}
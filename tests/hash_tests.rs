@@ -0,0 +1,213 @@
+#[macro_use]
+extern crate blockbuffers;
+
+pub mod common;
+
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+use blockbuffers::error::Error;
+use blockbuffers::hash::{structural_hash, structural_hash_table, Blake2b256, Sha256};
+use blockbuffers::position::{TablePosition, VectorPosition};
+use blockbuffers::seek::seek_uoffset;
+use common::example_hash::{example_block, example_example};
+use common::example_schema::{BLOCK, CHILD_EXAMPLE, EXAMPLE, SCHEMA};
+
+fn block(name: &str) -> TableBuilder {
+    let mut author = TableBuilder::new();
+    author.add_string(0, name);
+    author.add_scalar(1, 1984u32, 0);
+
+    let mut code = TableBuilder::new();
+    code.add_vector(0, &[1u8, 2]);
+
+    let mut block = TableBuilder::new();
+    block.add_table(0, author);
+    block.add_union(2, 1, code);
+    block
+}
+
+fn root(buf: &[u8]) -> TablePosition {
+    TablePosition(seek_uoffset(buf, 0))
+}
+
+#[test]
+fn test_hash_structural_bytes() {
+    let buf = block("alice").finish();
+    assert_eq!(
+        structural_hash::<Vec<u8>>(&SCHEMA, BLOCK, &buf).unwrap(),
+        example_block::<Vec<u8>>(&buf, root(&buf)).unwrap()
+    );
+}
+
+#[test]
+fn test_hash_nested_table() {
+    let mut example = TableBuilder::new();
+    example.add_table_vector(5, vec![block("alice"), block("bob")]);
+    let buf = example.finish();
+
+    let blocks_pos = root(&buf).field_position(&buf, 14).unwrap();
+    let blocks = VectorPosition(seek_uoffset(&buf, blocks_pos));
+    let second_block = TablePosition(seek_uoffset(&buf, blocks.0 + 8));
+
+    let bob = block("bob").finish();
+    assert_eq!(
+        structural_hash::<Vec<u8>>(&SCHEMA, BLOCK, &bob).unwrap(),
+        example_block::<Vec<u8>>(&buf, second_block).unwrap()
+    );
+}
+
+#[test]
+fn test_hash_ignores_layout() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    let canonical = example.finish();
+
+    // Stores the default value of `lines` explicitly.
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    example.add_scalar(6, 0u32, 1);
+    let buf = example.finish();
+
+    assert_ne!(canonical, buf);
+    assert_eq!(
+        example_example::<Blake2b256>(&canonical, root(&canonical)).unwrap(),
+        example_example::<Blake2b256>(&buf, root(&buf)).unwrap()
+    );
+}
+
+#[test]
+fn test_hash_rejects_unknown_field() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    example.add_scalar(9, 1u32, 0);
+    let buf = example.finish();
+
+    let pos = root(&buf);
+    assert_eq!(
+        Err(Error::UnknownField { pos: pos.0, id: 9 }),
+        example_example::<Blake2b256>(&buf, pos)
+    );
+}
+
+#[test]
+fn test_hash_rejects_invalid_buffer() {
+    let buf = block("alice").finish();
+    assert!(example_block::<Vec<u8>>(&buf[..8], TablePosition(4)).is_err());
+}
+
+fn scalars(padding: u8) -> StructBuilder {
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(0, -1i8);
+    scalars.set(2, true);
+    scalars.set(3, padding);
+    scalars.set(8, -3i32);
+    scalars.set(40, 0.25f64);
+    scalars
+}
+
+#[test]
+fn test_structural_hash_encoding() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    example.add_string(4, "hi");
+    example.add_table_vector(5, vec![TableBuilder::new()]);
+    let buf = example.finish();
+
+    assert_eq!(
+        vec![
+            0u8, 0, 8, 1, 0, 0, 0, // version: UInt
+            4, 0, 13, 2, 0, 0, 0, b'h', b'i', // info: String
+            5, 0, 14, 1, 0, 0, 0, // blocks: Vector of one empty table
+        ],
+        structural_hash::<Vec<u8>>(&SCHEMA, EXAMPLE, &buf).unwrap()
+    );
+}
+
+#[test]
+fn test_structural_hash_ignores_layout() {
+    let mut example = TableBuilder::new();
+    example.add_struct(3, &scalars(0));
+    example.add_table_vector(5, vec![block("alice")]);
+    let canonical = example.finish();
+
+    // Non-zero struct padding, an explicit default value and an unknown field.
+    let mut example = TableBuilder::new();
+    example.add_struct(3, &scalars(0xff));
+    example.add_table_vector(5, vec![block("alice")]);
+    example.add_scalar(7, 0u32, 1);
+    example.add_scalar(9, 1u32, 0);
+    let buf = example.finish();
+
+    assert_ne!(canonical, buf);
+    assert_eq!(
+        structural_hash::<Vec<u8>>(&SCHEMA, EXAMPLE, &canonical).unwrap(),
+        structural_hash::<Vec<u8>>(&SCHEMA, EXAMPLE, &buf).unwrap()
+    );
+    assert_eq!(
+        structural_hash::<Blake2b256>(&SCHEMA, EXAMPLE, &canonical).unwrap(),
+        structural_hash::<Blake2b256>(&SCHEMA, EXAMPLE, &buf).unwrap()
+    );
+    assert_eq!(
+        structural_hash::<Sha256>(&SCHEMA, EXAMPLE, &canonical).unwrap(),
+        structural_hash::<Sha256>(&SCHEMA, EXAMPLE, &buf).unwrap()
+    );
+}
+
+#[test]
+fn test_structural_hash_nested_table() {
+    let mut example = TableBuilder::new();
+    example.add_table_vector(5, vec![block("alice"), block("bob")]);
+    let buf = example.finish();
+
+    let blocks_pos = root(&buf).field_position(&buf, 14).unwrap();
+    let blocks = VectorPosition(seek_uoffset(&buf, blocks_pos));
+    let second_block = TablePosition(seek_uoffset(&buf, blocks.0 + 8));
+
+    let standalone = block("bob").finish();
+    assert_eq!(
+        structural_hash::<Blake2b256>(&SCHEMA, BLOCK, &standalone).unwrap(),
+        structural_hash_table::<Blake2b256>(&SCHEMA, BLOCK, &buf, second_block).unwrap()
+    );
+
+    // The parent feeds the digest of each child table.
+    let recorded = structural_hash::<Vec<u8>>(&SCHEMA, EXAMPLE, &buf).unwrap();
+    let alice = structural_hash::<Vec<u8>>(&SCHEMA, BLOCK, &block("alice").finish()).unwrap();
+    assert_eq!(&[5u8, 0, 14, 2, 0, 0, 0][..], &recorded[..7]);
+    assert_eq!(&alice[..], &recorded[7..7 + alice.len()]);
+}
+
+fn child(buffer: &[u8]) -> Vec<u8> {
+    let mut child = TableBuilder::new();
+    child.add_vector(0, buffer);
+    child.finish()
+}
+
+#[test]
+fn test_structural_hash_nested_buffer() {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    let canonical = example.finish();
+
+    // Stores the default value of `lines` explicitly.
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    example.add_scalar(7, 0u32, 1);
+    let nested = example.finish();
+
+    assert_ne!(child(&canonical), child(&nested));
+    assert_eq!(
+        structural_hash::<Blake2b256>(&SCHEMA, CHILD_EXAMPLE, &child(&canonical)).unwrap(),
+        structural_hash::<Blake2b256>(&SCHEMA, CHILD_EXAMPLE, &child(&nested)).unwrap()
+    );
+
+    // The parent feeds the digest of the nested root table instead of the bytes.
+    let recorded = structural_hash::<Vec<u8>>(&SCHEMA, CHILD_EXAMPLE, &child(&nested)).unwrap();
+    let digest = structural_hash::<Vec<u8>>(&SCHEMA, EXAMPLE, &canonical).unwrap();
+    assert_eq!(&[0u8, 0, 14][..], &recorded[..3]);
+    assert_eq!(&digest[..], &recorded[3..]);
+}
+
+#[test]
+fn test_structural_hash_rejects_invalid_buffer() {
+    let buf = block("alice").finish();
+    assert!(structural_hash::<Sha256>(&SCHEMA, BLOCK, &buf[..8]).is_err());
+}
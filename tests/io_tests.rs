@@ -1,20 +1,187 @@
+#[macro_use]
 extern crate blockbuffers;
-extern crate flatbuffers;
 
 pub mod common;
 
-use common::example_generated::example::{get_root_as_example, Example};
-use flatbuffers::FlatBufferBuilder;
+use blockbuffers::builder::TableBuilder;
+use blockbuffers::verify::verify_canonical;
+use common::example_hash::SCHEMA;
+use common::example_reader::{
+    get_root_as_example, Author, AuthorBuilder, Block, BlockBuilder, ChildExampleBuilder, Code,
+    CodeBuilder, CodeOption, CodeOptionValue, Example, ExampleBuilder, Language, Scalars,
+    ScalarsBuilder,
+};
+
+const EXAMPLE: usize = 4;
+
+fn block(name: &str, code: Option<&[u8]>) -> BlockBuilder {
+    let mut author = AuthorBuilder::new();
+    author.add_name(name);
+    author.set_birth(1990);
+
+    let mut block = BlockBuilder::new();
+    block.set_author(author);
+    if let Some(bytes) = code {
+        let mut code = CodeBuilder::new();
+        code.add_bytes(bytes);
+        block.set_code_as_code(code);
+    }
+    block
+}
 
 #[test]
 fn io_happy_pass() {
-    let (buf, loc) = {
-        let mut builder = FlatBufferBuilder::new_with_capacity(1024);
-        let ex = Example::create(&mut builder, &Default::default());
-        builder.finish(ex, None);
-        builder.collapse()
-    };
+    let buf = ExampleBuilder::new().finish();
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_ok());
 
-    let ex = get_root_as_example(&buf[loc..]);
+    let ex = get_root_as_example(&buf);
     assert_eq!(0, ex.version());
+    assert_eq!(Language::Rust, ex.language());
+    assert!(ex.scalars().is_none());
+    assert!(ex.blocks().is_none());
+}
+
+#[test]
+fn io_read_fields() {
+    let mut scalars = ScalarsBuilder::new();
+    scalars.set_a_bool(true);
+    scalars.set_a_int(-3);
+    scalars.set_a_double(0.25);
+
+    let mut nested = ExampleBuilder::new();
+    nested.set_version(2);
+    let mut child = ChildExampleBuilder::new();
+    child.set_buffer_nested_flatbuffer(nested);
+
+    let mut example = ExampleBuilder::new();
+    example.set_version(1);
+    example.set_language(Language::Python);
+    example.set_scalars(&scalars);
+    example.add_blocks(vec![block("alice", Some(&[1, 2])), block("bob", None)]);
+    example.add_children(vec![child]);
+    let buf = example.finish();
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_ok());
+
+    let ex = get_root_as_example(&buf);
+    assert_eq!(1, ex.version());
+    assert_eq!(Language::Python, ex.language());
+    assert_eq!(0, ex.lines());
+
+    let scalars = ex.scalars().unwrap();
+    assert!(scalars.a_bool());
+    assert_eq!(-3, scalars.a_int());
+    assert_eq!(0.25, scalars.a_double());
+    assert_eq!(0, scalars.a_long());
+
+    let names: Vec<&str> = ex
+        .blocks()
+        .unwrap()
+        .map(|block| block.author().unwrap().name().unwrap())
+        .collect();
+    assert_eq!(vec!["alice", "bob"], names);
+
+    let blocks = ex.blocks().unwrap();
+    assert_eq!(2, blocks.len());
+    let alice = blocks.get(0).unwrap();
+    assert_eq!(1990, alice.author().unwrap().birth());
+    assert_eq!(CodeOption::Code, alice.code_type());
+    let code = alice.code_as_code().unwrap();
+    assert_eq!(&[1u8, 2], code.bytes().unwrap().as_bytes());
+
+    let bob = blocks.get(1).unwrap();
+    assert_eq!(CodeOption::NONE, bob.code_type());
+    assert!(bob.code().is_none());
+    assert!(bob.code_as_code().is_none());
+
+    let child = ex.children().unwrap().next().unwrap();
+    assert_eq!(2, child.buffer_nested_flatbuffer().unwrap().version());
+}
+
+fn example() -> Example {
+    let author = Author {
+        name: Some("alice".to_string()),
+        birth: 1990,
+    };
+    let code = Code {
+        bytes: Some(vec![1, 2]),
+    };
+    Example {
+        version: 1,
+        language: Language::Python,
+        scalars: Some(Scalars {
+            a_byte: -1,
+            a_ubyte: 1,
+            a_bool: true,
+            a_short: -2,
+            a_ushort: 2,
+            a_int: -3,
+            a_uint: 3,
+            a_float: 0.5,
+            a_long: -4,
+            a_ulong: 4,
+            a_double: 0.25,
+        }),
+        blocks: Some(vec![
+            Block {
+                author: Some(Box::new(author)),
+                code: Some(CodeOptionValue::Code(Box::new(code))),
+            },
+            Block::default(),
+        ]),
+        children: Some(vec![]),
+        lines: 0,
+    }
+}
+
+#[test]
+fn io_encode_decode() {
+    let example = example();
+    let buf = example.encode();
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_ok());
+    assert_eq!(Ok(example), Example::decode(&buf));
+
+    let ex = get_root_as_example(&buf);
+    assert_eq!(Some(-4), ex.scalars().map(|scalars| scalars.a_long()));
+    assert_eq!(0, ex.children().unwrap().len());
+
+    assert_eq!(
+        Ok(Example::default()),
+        Example::decode(&Example::default().encode())
+    );
+}
+
+#[test]
+fn io_decode_invalid() {
+    let buf = example().encode();
+    assert!(Example::decode(&buf[..buf.len() - 1]).is_err());
+}
+
+#[test]
+fn io_decode_invalid_nested_flatbuffer() {
+    // The root offset of the nested buffer points past its end.
+    let mut child = TableBuilder::new();
+    child.add_vector(0, &[8u8, 0, 0, 0]);
+    let mut example = TableBuilder::new();
+    example.add_table_vector(5, vec![child]);
+    let buf = example.finish();
+
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_err());
+    assert!(Example::decode(&buf).is_err());
+}
+
+#[test]
+fn io_unknown_enum_value() {
+    // A language added by a newer peer.
+    let mut example = TableBuilder::new();
+    example.add_scalar(1, 2i16, 0);
+    let buf = example.finish();
+    assert!(verify_canonical(&SCHEMA, EXAMPLE, &buf).is_ok());
+
+    let language = get_root_as_example(&buf).language();
+    assert_eq!(Language(2), language);
+    assert_eq!(None, language.known());
+
+    let example = Example::decode(&buf).unwrap();
+    assert_eq!(Language(2), example.language);
+    assert_eq!(buf, example.encode());
 }
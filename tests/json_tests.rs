@@ -0,0 +1,199 @@
+extern crate blockbuffers;
+
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+use blockbuffers::json::{from_json, to_json};
+use blockbuffers::reflection::Schema;
+use blockbuffers::Error;
+
+fn example_bfbs() -> Schema {
+    Schema::from_slice(&include_bytes!("common/example.bfbs")[..]).unwrap()
+}
+
+fn example_buf() -> Vec<u8> {
+    let mut author = TableBuilder::new();
+    author.add_string(0, "alice");
+    author.add_scalar(1, 1990u32, 0);
+
+    let mut code = TableBuilder::new();
+    code.add_vector(0, &[1u8, 2]);
+
+    let mut block = TableBuilder::new();
+    block.add_table(0, author);
+    block.add_union(2, 1, code);
+
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(0, -1i8);
+    scalars.set(2, true);
+    scalars.set(16, 0.5f32);
+    scalars.set(32, u64::max_value());
+    scalars.set(40, 0.1f64);
+
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 2u32, 0);
+    example.add_scalar(1, 1i16, 0);
+    example.add_struct(2, &scalars);
+    example.add_string(3, "a \"quoted\"\nline \u{e9}\u{1F600}");
+    example.add_table_vector(4, vec![block, TableBuilder::new()]);
+    example.finish()
+}
+
+const EXAMPLE_JSON: &str = r#"{
+  "version": 2,
+  "language": "Python",
+  "scalars": {
+    "a_byte": -1,
+    "a_ubyte": 0,
+    "a_bool": true,
+    "a_short": 0,
+    "a_ushort": 0,
+    "a_int": 0,
+    "a_uint": 0,
+    "a_float": 0.5,
+    "a_long": 0,
+    "a_ulong": 18446744073709551615,
+    "a_double": 0.1
+  },
+  "info": "a \"quoted\"\nline \u00E9\uD83D\uDE00",
+  "blocks": [
+    {
+      "author": {
+        "name": "alice",
+        "birth": 1990
+      },
+      "code_type": "Code",
+      "code": {
+        "bytes": [
+          1,
+          2
+        ]
+      }
+    },
+    {
+    }
+  ]
+}
+"#;
+
+#[test]
+fn test_to_json() {
+    let schema = example_bfbs();
+    assert_eq!(EXAMPLE_JSON, to_json(&schema, &example_buf()).unwrap());
+}
+
+#[test]
+fn test_round_trip() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    let json = to_json(&schema, &buf).unwrap();
+    assert_eq!(buf, from_json(&schema, &json).unwrap());
+}
+
+#[test]
+fn test_from_json_is_canonical() {
+    let schema = example_bfbs();
+    let json = r#"{
+        "blocks": [{"code": {"bytes": [1, 2]}, "code_type": 1,
+                    "author": {"birth": 1990, "name": "alice"}}, {}],
+        "info": "a \"quoted\"\nline \u00e9\ud83d\ude00",
+        "scalars": {"a_double": 0.1, "a_ulong": 18446744073709551615, "a_float": 0.5,
+                    "a_bool": true, "a_byte": -1},
+        "language": 1,
+        "lines": 0,
+        "children": null,
+        "version": 2
+    }"#;
+    assert_eq!(example_buf(), from_json(&schema, json).unwrap());
+}
+
+#[test]
+fn test_empty_vector() {
+    let schema = example_bfbs();
+    let mut example = TableBuilder::new();
+    example.add_table_vector(4, vec![]);
+    let buf = example.finish();
+
+    let json = to_json(&schema, &buf).unwrap();
+    assert_eq!("{\n  \"blocks\": [\n\n  ]\n}\n", json);
+    assert_eq!(buf, from_json(&schema, &json).unwrap());
+}
+
+#[test]
+fn test_invalid_json() {
+    let schema = example_bfbs();
+    let cases = [
+        ("{\"version\": 1", 13),
+        ("{\"version\": 1} x", 15),
+        ("{\"no_such_field\": 1}", 18),
+        ("{\"version\": -1}", 12),
+        ("{\"version\": \"one\"}", 12),
+        ("{\"language\": \"Go\"}", 13),
+        ("{\"blocks\": [{\"code\": {}}]}", 21),
+        ("{\"blocks\": [{\"code_type\": \"Code\"}]}", 26),
+        ("{\"info\": \"\\uD83D\"}", 16),
+    ];
+    for &(json, pos) in cases.iter() {
+        assert_eq!(
+            Err(Error::InvalidJson { pos }),
+            from_json(&schema, json),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn test_to_json_verifies() {
+    let schema = example_bfbs();
+    let buf = example_buf();
+    assert!(to_json(&schema, &buf[..buf.len() - 1]).is_err());
+}
+
+#[test]
+fn test_non_finite_floats() {
+    let schema = example_bfbs();
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(16, f32::from_bits(0x7fc0_0000));
+    scalars.set(40, f64::from_bits(0xfff0_0000_0000_0000));
+    let mut example = TableBuilder::new();
+    example.add_struct(2, &scalars);
+    let buf = example.finish();
+
+    let json = to_json(&schema, &buf).unwrap();
+    assert!(json.contains("\"a_float\": nan,"));
+    assert!(json.contains("\"a_double\": -inf\n"));
+    assert_eq!(buf, from_json(&schema, &json).unwrap());
+
+    // Other NaNs are printed as `nan` as well, and parsed back as the canonical NaN.
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(16, f32::from_bits(0xffc0_0001));
+    scalars.set(40, f64::from_bits(0xfff0_0000_0000_0000));
+    let mut example = TableBuilder::new();
+    example.add_struct(2, &scalars);
+    assert_eq!(json, to_json(&schema, &example.finish()).unwrap());
+}
+
+#[test]
+fn test_required_field_missing() {
+    let mut schema = example_bfbs();
+    let author = schema.object_index("example.Author").unwrap();
+    schema.objects[author]
+        .fields
+        .iter_mut()
+        .find(|f| f.name == "name")
+        .unwrap()
+        .required = true;
+
+    let json = r#"{"blocks": [{"author": {"name": "alice"}}]}"#;
+    assert!(from_json(&schema, json).is_ok());
+    for &json in &[
+        r#"{"blocks": [{"author": {"birth": 1990}}]}"#,
+        r#"{"blocks": [{"author": {"name": null}}]}"#,
+    ] {
+        assert_eq!(
+            Err(Error::RequiredFieldMissing { pos: 23, id: 0 }),
+            from_json(&schema, json),
+            "{}",
+            json
+        );
+    }
+}
@@ -0,0 +1,441 @@
+#[macro_use]
+extern crate blockbuffers;
+
+pub mod common;
+
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+use blockbuffers::position::{
+    StringPosition, StructLayout, StructPosition, TablePosition, UnionPosition, VectorPosition,
+};
+use blockbuffers::schema::{Element, Field, Schema, Table, Type};
+use blockbuffers::seek::seek_uoffset;
+use blockbuffers::verify::verify;
+use blockbuffers::Error;
+use common::example_schema::{AUTHOR, SCALARS, SCHEMA};
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum CodeOption {
+    NONE = 0,
+    Code = 1,
+}
+const ENUM_MAX_CODE_OPTION: u8 = 1;
+
+// The macro expands to a block, but the impl inside is visible everywhere.
+#[allow(dead_code)]
+fn impl_le() {
+    impl_le_for_enum!(CodeOption, u8, NONE, Code);
+}
+
+fn block(tag: u8, code: Option<&[u8]>) -> Vec<u8> {
+    let mut block = TableBuilder::new();
+    block.add_scalar(1, tag, 0);
+    if let Some(bytes) = code {
+        let mut code = TableBuilder::new();
+        code.add_vector(0, bytes);
+        block.add_table(2, code);
+    }
+    block.finish()
+}
+
+fn code_option(buf: &[u8]) -> UnionPosition {
+    UnionPosition::new(TablePosition(seek_uoffset(buf, 0)), 8)
+}
+
+#[test]
+fn test_union() {
+    let buf = block(1, Some(&[1, 2]));
+    let code = code_option(&buf);
+    assert_eq!(CodeOption::Code, code.tag(&buf));
+    assert_eq!(
+        Ok(CodeOption::Code),
+        code.try_tag(&buf, ENUM_MAX_CODE_OPTION)
+    );
+
+    let value = code.try_value(&buf, ENUM_MAX_CODE_OPTION).unwrap().unwrap();
+    assert_eq!(Some(value), code.value(&buf));
+    let bytes_pos = value.field_position(&buf, 4).unwrap();
+    let bytes = VectorPosition(seek_uoffset(&buf, bytes_pos));
+    assert_eq!(&[1u8, 2], bytes.as_le_slice::<u8>(&buf).as_bytes());
+
+    let buf = block(0, None);
+    let code = code_option(&buf);
+    assert_eq!(CodeOption::NONE, code.tag(&buf));
+    assert_eq!(None, code.value(&buf));
+    assert_eq!(Ok(None), code.try_value(&buf, ENUM_MAX_CODE_OPTION));
+}
+
+#[test]
+fn test_bad_union() {
+    let buf = block(1, None);
+    let code = code_option(&buf);
+    let pos = code.table.0;
+    assert_eq!(None, code.value(&buf));
+    assert_eq!(
+        Err(Error::BadUnion { pos }),
+        code.try_value(&buf, ENUM_MAX_CODE_OPTION)
+    );
+
+    let buf = block(0, Some(&[]));
+    let code = code_option(&buf);
+    let pos = code.table.0;
+    assert_eq!(None, code.value(&buf));
+    assert_eq!(
+        Err(Error::BadUnion { pos }),
+        code.try_value(&buf, ENUM_MAX_CODE_OPTION)
+    );
+
+    let buf = block(2, Some(&[]));
+    let code = code_option(&buf);
+    let pos = code.table.0;
+    assert_eq!(Ok(2), code.try_tag_value(&buf));
+    assert_eq!(
+        Err(Error::UnknownUnionType { pos, tag: 2 }),
+        code.try_tag::<CodeOption>(&buf, ENUM_MAX_CODE_OPTION)
+    );
+    assert_eq!(
+        Err(Error::UnknownUnionType { pos, tag: 2 }),
+        code.try_value(&buf, ENUM_MAX_CODE_OPTION)
+    );
+
+    // The tag cannot be read as a type wider than a byte.
+    let buf = block(1, Some(&[]));
+    let code = code_option(&buf);
+    let pos = code.table.0;
+    assert_eq!(
+        Err(Error::UnknownUnionType { pos, tag: 1 }),
+        code.try_tag::<u16>(&buf, ENUM_MAX_CODE_OPTION)
+    );
+
+    // The union value cannot be the field 0, which has no tag field before it.
+    let code = UnionPosition {
+        table: TablePosition(pos),
+        value_slot: 0,
+    };
+    assert_eq!(Err(Error::BadUnion { pos }), code.try_tag_value(&buf));
+    assert_eq!(
+        Err(Error::BadUnion { pos }),
+        code.try_value(&buf, ENUM_MAX_CODE_OPTION)
+    );
+}
+
+fn scalars(n: i32) -> StructBuilder {
+    let mut scalars = StructBuilder::new(48, 8);
+    scalars.set(4, -2i16);
+    scalars.set(8, n);
+    scalars.set(40, 0.25f64);
+    scalars
+}
+
+#[test]
+fn test_struct_layout() {
+    let st = &SCHEMA.structs[SCALARS];
+    let mut layout = StructLayout::new();
+    for field in st.fields {
+        let (size, align) = SCHEMA.inline_size_and_align(field.ty);
+        assert_eq!(field.offset, layout.add_field(size, align));
+    }
+    assert_eq!(st.layout(), layout.finish());
+}
+
+#[test]
+fn test_read_array() {
+    // The array may be misaligned in memory, and is copied in native endian.
+    let bytes = [0u8, 1, 0, 0, 0, 2, 0, 0, 0];
+    let buf = &bytes[1..];
+    let words: [u32; 2] = StructPosition(0).read_array(buf);
+    assert_eq!([1, 2], words);
+    assert_eq!(Ok(words), StructPosition(0).try_read_array(buf));
+    assert_eq!(buf, StructBuilder::from_array(&words).as_bytes());
+}
+
+#[test]
+fn test_struct_in_table() {
+    let mut example = TableBuilder::new();
+    example.add_struct(3, &scalars(-3));
+    let buf = example.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let pos = root.field_position(&buf, 10).unwrap();
+    let scalars = SCHEMA.structs[SCALARS]
+        .layout()
+        .try_struct(&buf, pos)
+        .unwrap();
+    assert_eq!(-2i16, scalars.read(&buf, 4));
+    assert_eq!(Ok(-3i32), scalars.try_read(&buf, 8));
+    assert_eq!(0.25f64, scalars.read(&buf, 40));
+    assert_eq!(StructPosition(pos + 40), scalars.field_struct(40));
+    assert_eq!(
+        Err(Error::Misaligned {
+            pos: pos + 1,
+            align: 8
+        }),
+        SCHEMA.structs[SCALARS].layout().try_struct(&buf, pos + 1)
+    );
+}
+
+#[test]
+fn test_vector_of_structs() {
+    let mut table = TableBuilder::new();
+    table.add_struct_vector(0, 8, &[scalars(1), scalars(2)]);
+    let buf = table.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let vector = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    let layout = SCHEMA.structs[SCALARS].layout();
+    for i in 0..2 {
+        let element = layout.try_element(&buf, vector, i).unwrap();
+        assert_eq!(layout.element(vector, i), element);
+        assert_eq!(i as i32 + 1, element.read::<i32>(&buf, 8));
+    }
+    assert!(layout.try_element(&buf, vector, 2).is_err());
+}
+
+fn author(name: &str) -> TableBuilder {
+    let mut author = TableBuilder::new();
+    author.add_string(0, name);
+    author
+}
+
+#[test]
+fn test_vector_iterators() {
+    let mut example = TableBuilder::new();
+    let blocks = ["alice", "bob", "carol"]
+        .iter()
+        .map(|name| {
+            let mut block = TableBuilder::new();
+            block.add_table(0, author(name));
+            block
+        })
+        .collect();
+    example.add_table_vector(5, blocks);
+    let mut child = TableBuilder::new();
+    child.add_vector(0, &[1u8, 2, 3]);
+    example.add_table_vector(6, vec![child]);
+    let buf = example.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let author_name = |block: TablePosition| {
+        let author = TablePosition(seek_uoffset(&buf, block.field_position(&buf, 4).unwrap()));
+        StringPosition(seek_uoffset(&buf, author.field_position(&buf, 4).unwrap())).as_str(&buf)
+    };
+
+    let blocks = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 14).unwrap()));
+    let mut iter = blocks.try_tables(&buf).unwrap();
+    assert_eq!(3, iter.len());
+    assert_eq!("bob", author_name(iter.get(1).unwrap()));
+    assert_eq!(None, iter.get(3));
+    assert_eq!("alice", author_name(iter.next().unwrap()));
+    assert_eq!("carol", author_name(iter.next_back().unwrap()));
+    assert_eq!(1, iter.len());
+    assert_eq!(iter.get(0), iter.next());
+    assert_eq!(None, iter.next());
+    assert_eq!(None, iter.next_back());
+
+    let names: Vec<&str> = blocks.tables(&buf).rev().map(author_name).collect();
+    assert_eq!(vec!["carol", "bob", "alice"], names);
+
+    let children = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 16).unwrap()));
+    let child = children.tables(&buf).next().unwrap();
+    let bytes = VectorPosition(seek_uoffset(&buf, child.field_position(&buf, 4).unwrap()));
+    assert_eq!(&[1u8, 2, 3], bytes.as_le_slice::<u8>(&buf).as_bytes());
+}
+
+#[test]
+fn test_checked_vector_iterators() {
+    let mut table = TableBuilder::new();
+    table.add_string_vector(0, &["a", "b"]);
+    table.add_struct_vector(1, 8, &[scalars(1), scalars(2)]);
+    let buf = table.finish();
+
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let strings = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 4).unwrap()));
+    let structs = VectorPosition(seek_uoffset(&buf, root.field_position(&buf, 6).unwrap()));
+    let layout = SCHEMA.structs[SCALARS].layout();
+
+    let values: Vec<&str> = strings
+        .try_strings(&buf)
+        .unwrap()
+        .map(|s| s.as_str(&buf))
+        .collect();
+    assert_eq!(vec!["a", "b"], values);
+    let values: Vec<i32> = structs
+        .try_structs(&buf, layout)
+        .unwrap()
+        .map(|s| s.read(&buf, 8))
+        .collect();
+    assert_eq!(vec![1, 2], values);
+
+    // Truncate the buffer inside the last struct.
+    let truncated = &buf[..structs.0 + 4 + 48 + 8];
+    assert!(structs.try_structs(truncated, layout).is_err());
+    assert!(structs.try_tables(&buf[..structs.0 + 2]).is_err());
+}
+
+fn authors(authors: &[(&str, u32)]) -> Vec<u8> {
+    authors_with_default_birth(authors, 0)
+}
+
+fn authors_with_default_birth(authors: &[(&str, u32)], default_birth: u32) -> Vec<u8> {
+    let authors = authors
+        .iter()
+        .map(|&(name, birth)| {
+            let mut author = author(name);
+            author.add_scalar(1, birth, default_birth);
+            author
+        })
+        .collect();
+    let mut table = TableBuilder::new();
+    table.add_table_vector(0, authors);
+    table.finish()
+}
+
+fn authors_vector(buf: &[u8]) -> VectorPosition {
+    let root = TablePosition(seek_uoffset(buf, 0));
+    VectorPosition(seek_uoffset(buf, root.field_position(buf, 4).unwrap()))
+}
+
+#[test]
+fn test_lookup_by_key() {
+    let buf = authors(&[("alice", 1990), ("bob", 1970), ("carol", 1980)]);
+    let vector = authors_vector(&buf);
+
+    let bob = vector.lookup_by_key(&buf, "bob", 4, "").unwrap();
+    assert_eq!(Ok(1970u32), bob.try_read_field(&buf, 6, 0));
+    assert_eq!(Ok(Some(bob)), vector.try_lookup_by_key(&buf, "bob", 4, ""));
+    for name in &["", "aaron", "bill", "dave"] {
+        assert_eq!(None, vector.lookup_by_key(&buf, *name, 4, ""));
+    }
+    assert!(vector
+        .try_lookup_by_key(&buf[..bob.0], "bob", 4, "")
+        .is_err());
+
+    let buf = authors(&[("alice", 1970), ("bob", 1980), ("carol", 1990)]);
+    let vector = authors_vector(&buf);
+    let carol = vector.lookup_by_key(&buf, 1990u32, 6, 0).unwrap();
+    assert_eq!(Some(carol), vector.lookup_by_key(&buf, "carol", 4, ""));
+    assert_eq!(None, vector.lookup_by_key(&buf, 1975u32, 6, 0));
+
+    let buf = authors(&[]);
+    assert_eq!(
+        None,
+        authors_vector(&buf).lookup_by_key(&buf, "alice", 4, "")
+    );
+}
+
+#[test]
+fn test_verify_sorted_by_key() {
+    // table Authors { authors: [Author]; }, Author is sorted by either name or birth.
+    let authors_fields = [Field {
+        name: "authors",
+        id: 0,
+        ty: Type::Vector(Element::Table(1)),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    }];
+    let root = Table {
+        name: "Authors",
+        fields: &authors_fields,
+        key: None,
+    };
+    let by_name = [
+        root,
+        Table {
+            key: Some(0),
+            ..SCHEMA.tables[AUTHOR]
+        },
+    ];
+    let by_birth = [
+        root,
+        Table {
+            key: Some(1),
+            ..SCHEMA.tables[AUTHOR]
+        },
+    ];
+    let schema = |tables| Schema {
+        tables,
+        structs: &[],
+        unions: &[],
+    };
+
+    let buf = authors(&[("alice", 1990), ("bob", 1970)]);
+    let bob = authors_vector(&buf).tables(&buf).nth(1).unwrap();
+    assert!(verify(&schema(&by_name), 0, &buf).is_ok());
+    assert_eq!(
+        Err(Error::KeyNotSorted { pos: bob.0 }),
+        verify(&schema(&by_birth), 0, &buf)
+    );
+
+    let buf = authors(&[("alice", 1990), ("alice", 1991)]);
+    let second = authors_vector(&buf).tables(&buf).nth(1).unwrap();
+    assert_eq!(
+        Err(Error::KeyNotSorted { pos: second.0 }),
+        verify(&schema(&by_name), 0, &buf)
+    );
+    assert!(verify(&schema(&by_birth), 0, &buf).is_ok());
+
+    // Absent keys are compared as the default values.
+    let buf = authors(&[("", 0), ("bob", 0)]);
+    assert!(verify(&schema(&by_name), 0, &buf).is_ok());
+    assert!(verify(&schema(&by_birth), 0, &buf).is_err());
+}
+
+#[test]
+fn test_lookup_by_key_with_default() {
+    // Author with `birth: uint = 1980 (key)`.
+    let author_fields = [
+        SCHEMA.tables[AUTHOR].fields[0],
+        Field {
+            default_integer: 1980,
+            ..SCHEMA.tables[AUTHOR].fields[1]
+        },
+    ];
+    let authors_fields = [Field {
+        name: "authors",
+        id: 0,
+        ty: Type::Vector(Element::Table(1)),
+        required: false,
+        default_integer: 0,
+        default_real: 0.0,
+        since_version: 0,
+        deprecated_version: None,
+        nested_flatbuffer: None,
+    }];
+    let tables = [
+        Table {
+            name: "Authors",
+            fields: &authors_fields,
+            key: None,
+        },
+        Table {
+            name: "Author",
+            fields: &author_fields,
+            key: Some(1),
+        },
+    ];
+    let schema = Schema {
+        tables: &tables,
+        structs: &[],
+        unions: &[],
+    };
+
+    // The birth of bob is omitted, so it is read as 1980 only with the schema default.
+    let buf = authors_with_default_birth(&[("alice", 1970), ("bob", 1980), ("carol", 1990)], 1980);
+    let vector = authors_vector(&buf);
+    let bob = vector.tables(&buf).nth(1).unwrap();
+    assert_eq!(None, bob.field_position(&buf, 6));
+    assert!(verify(&schema, 0, &buf).is_ok());
+
+    assert_eq!(Some(bob), vector.lookup_by_key(&buf, 1980u32, 6, 1980));
+    assert_eq!(
+        Ok(Some(bob)),
+        vector.try_lookup_by_key(&buf, 1980u32, 6, 1980)
+    );
+    let carol = vector.lookup_by_key(&buf, 1990u32, 6, 1980).unwrap();
+    assert_eq!(Some(carol), vector.lookup_by_key(&buf, "carol", 4, ""));
+    assert_eq!(None, vector.lookup_by_key(&buf, 0u32, 6, 1980));
+}
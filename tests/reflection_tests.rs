@@ -0,0 +1,166 @@
+extern crate blockbuffers;
+
+use blockbuffers::builder::TableBuilder;
+use blockbuffers::reflection::{BaseType, Schema, Type};
+use blockbuffers::verify::{verify, verify_canonical};
+use blockbuffers::Error;
+
+fn example_bfbs() -> Schema {
+    Schema::from_slice(&include_bytes!("common/example.bfbs")[..]).unwrap()
+}
+
+#[test]
+fn test_objects() {
+    let schema = example_bfbs();
+
+    let names: Vec<&str> = schema.objects.iter().map(|o| o.name.as_str()).collect();
+    assert!(names.contains(&"example.Example"));
+    assert!(names.contains(&"example.Block"));
+    assert_eq!(
+        Some("example.Example"),
+        schema.root_table.as_ref().map(|o| o.name.as_str())
+    );
+
+    let example = schema.object("example.Example").unwrap();
+    assert!(!example.is_struct);
+    let version = example.field("version").unwrap();
+    assert_eq!(0, version.id);
+    assert_eq!(4, version.slot());
+    assert_eq!(BaseType::UInt, version.ty.base_type);
+
+    let lines = example.field("lines").unwrap();
+    assert_eq!(Some("2"), lines.attribute("since_version"));
+
+    let scalars = schema.object("example.Scalars").unwrap();
+    assert!(scalars.is_struct);
+    assert_eq!(8, scalars.minalign);
+}
+
+#[test]
+fn test_attributes() {
+    let schema = example_bfbs();
+
+    let child = schema.object("example.ChildExample").unwrap();
+    let buffer = child.field("buffer").unwrap();
+    assert_eq!(BaseType::Vector, buffer.ty.base_type);
+    assert_eq!(BaseType::UByte, buffer.ty.element);
+    assert_eq!(Some("Example"), buffer.attribute("nested_flatbuffer"));
+    assert_eq!(None, buffer.attribute("since_version"));
+}
+
+#[test]
+fn test_enums() {
+    let schema = example_bfbs();
+
+    let code_option = schema.enum_("example.CodeOption").unwrap();
+    assert!(code_option.is_union);
+    let code = code_option.value(1).unwrap();
+    assert_eq!("Code", code.name);
+    let index = code.union_type.as_ref().unwrap().index as usize;
+    assert_eq!("example.Code", schema.objects[index].name);
+
+    let language = schema.enum_("example.Language").unwrap();
+    assert!(!language.is_union);
+    assert_eq!(BaseType::Short, language.underlying_type.base_type);
+    assert_eq!("Python", language.value(1).unwrap().name);
+}
+
+#[test]
+fn test_with_description() {
+    let schema = example_bfbs();
+    let example = schema.object_index("example.Example").unwrap();
+    let child = schema.object_index("example.ChildExample").unwrap();
+
+    let mut builder = TableBuilder::new();
+    builder.add_scalar(0, 2u32, 0);
+    builder.add_string(3, "deprecated");
+    let buf = builder.finish();
+
+    schema
+        .with_description(|desc| {
+            assert_eq!("example.Example", desc.tables[example].name);
+            assert_eq!(
+                Some(example),
+                desc.tables[child].fields[0].nested_flatbuffer
+            );
+            assert!(verify(desc, example, &buf).is_ok());
+            assert!(verify_canonical(desc, example, &buf).is_ok());
+        })
+        .unwrap();
+}
+
+/// Replaces the type of the field, and returns the indices of the object and the field id.
+fn set_type(schema: &mut Schema, object: &str, field: &str, ty: Type) -> (usize, u16) {
+    let index = schema.object_index(object).unwrap();
+    let field = schema.objects[index]
+        .fields
+        .iter_mut()
+        .find(|f| f.name == field)
+        .unwrap();
+    field.ty = ty;
+    (index, field.id)
+}
+
+#[test]
+fn test_invalid_description() {
+    let missing_table = Type {
+        base_type: BaseType::Obj,
+        element: BaseType::None,
+        index: 99,
+    };
+    let mut schema = example_bfbs();
+    let (object, id) = set_type(
+        &mut schema,
+        "example.Example",
+        "version",
+        missing_table.clone(),
+    );
+    assert_eq!(
+        Err(Error::BadFieldType { object, id }),
+        schema.with_description(|_| ())
+    );
+
+    // Struct fields cannot be strings.
+    let string = Type {
+        base_type: BaseType::String,
+        element: BaseType::None,
+        index: -1,
+    };
+    let mut schema = example_bfbs();
+    let (object, id) = set_type(&mut schema, "example.Scalars", "a_int", string);
+    assert_eq!(
+        Err(Error::BadFieldType { object, id }),
+        schema.with_description(|_| ())
+    );
+
+    let mut schema = example_bfbs();
+    let index = schema
+        .enums
+        .iter()
+        .position(|e| e.name == "example.CodeOption")
+        .unwrap();
+    schema.enums[index]
+        .values
+        .iter_mut()
+        .find(|v| v.value == 1)
+        .unwrap()
+        .union_type = Some(missing_table);
+    assert_eq!(
+        Err(Error::BadUnionVariant { index, value: 1 }),
+        schema.with_description(|_| ())
+    );
+}
+
+#[test]
+fn test_invalid_buffer() {
+    let bfbs = include_bytes!("common/example.bfbs");
+    assert!(Schema::from_slice(&bfbs[..8]).is_err());
+    assert_eq!(
+        Err(Error::OutOfBounds {
+            pos: 0,
+            need: 4,
+            buf_len: 0
+        }),
+        Schema::from_slice(&[])
+    );
+}
@@ -0,0 +1,90 @@
+extern crate blockbuffers;
+
+use blockbuffers::builder::TableBuilder;
+use blockbuffers::le::LE;
+use blockbuffers::root::{
+    has_identifier, root_table, root_table_with_identifier,
+    size_prefixed_root_table_with_identifier, try_root_table_with_identifier,
+    try_size_prefixed_root_table, try_size_prefixed_root_table_with_identifier,
+};
+use blockbuffers::Error;
+
+fn size_prefixed(buf: &[u8]) -> Vec<u8> {
+    let mut framed = vec![0u8; 4];
+    (buf.len() as u32).write_le_slice(&mut framed);
+    framed.extend_from_slice(buf);
+    framed
+}
+
+#[test]
+fn test_bfbs_identifier() {
+    let bfbs = &include_bytes!("common/example.bfbs")[..];
+    assert!(has_identifier(bfbs, b"BFBS"));
+    assert_eq!(
+        Some(root_table(bfbs)),
+        root_table_with_identifier(bfbs, b"BFBS")
+    );
+    assert_eq!(
+        Ok(root_table(bfbs)),
+        try_root_table_with_identifier(bfbs, b"BFBS")
+    );
+    assert_eq!(None, root_table_with_identifier(bfbs, b"JSON"));
+    assert_eq!(
+        Err(Error::IdentifierMismatch { pos: 4 }),
+        try_root_table_with_identifier(bfbs, b"JSON")
+    );
+
+    let framed = size_prefixed(bfbs);
+    let (buf, root) = size_prefixed_root_table_with_identifier(&framed, b"BFBS").unwrap();
+    assert_eq!(bfbs, buf);
+    assert_eq!(root_table(bfbs), root);
+    assert_eq!(
+        Ok((bfbs, root)),
+        try_size_prefixed_root_table_with_identifier(&framed, b"BFBS")
+    );
+}
+
+#[test]
+fn test_size_prefixed_stream() {
+    let mut first = TableBuilder::new();
+    first.add_scalar(0, 1u32, 0);
+    let mut second = TableBuilder::new();
+    second.add_scalar(0, 2u32, 0);
+
+    let mut stream = size_prefixed(&first.finish());
+    stream.extend(size_prefixed(&second.finish()));
+
+    let mut rest = &stream[..];
+    let mut values = Vec::new();
+    while !rest.is_empty() {
+        let (buf, root) = try_size_prefixed_root_table(rest).unwrap();
+        values.push(buf[root.field_position(buf, 4).unwrap()]);
+        rest = &rest[4 + buf.len()..];
+    }
+    assert_eq!(vec![1, 2], values);
+
+    let truncated = &stream[..stream.len() / 2 - 1];
+    assert_eq!(
+        Err(Error::OutOfBounds {
+            pos: 4,
+            need: stream.len() / 2 - 4,
+            buf_len: truncated.len()
+        }),
+        try_size_prefixed_root_table(truncated)
+    );
+}
+
+#[test]
+fn test_missing_identifier() {
+    let buf = &[8u8, 0, 0, 0, b'B'][..];
+    assert!(!has_identifier(buf, b"BFBS"));
+    assert_eq!(None, root_table_with_identifier(buf, b"BFBS"));
+    assert_eq!(
+        Err(Error::OutOfBounds {
+            pos: 4,
+            need: 4,
+            buf_len: 5
+        }),
+        try_root_table_with_identifier(buf, b"BFBS")
+    );
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+extern crate blockbuffers;
+#[macro_use]
+extern crate serde_derive;
+
+pub mod common;
+
+use blockbuffers::builder::{StructBuilder, TableBuilder};
+use blockbuffers::verify::verify_canonical;
+use blockbuffers::wire::{from_slice, from_slice_with_schema, to_vec, Error};
+use blockbuffers::Error as BufferError;
+use common::example_hash::SCHEMA;
+use std::collections::HashMap;
+
+const AUTHOR: usize = 0;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Author {
+    name: String,
+    birth: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+enum Kind {
+    Text,
+    Code,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Point(i16, u8);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Document {
+    id: u64,
+    kind: Kind,
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<Author>,
+    hash: [u8; 4],
+    origin: Point,
+    tags: Vec<String>,
+    points: Vec<Point>,
+    authors: Vec<Author>,
+    ratio: f64,
+    initial: char,
+    draft: bool,
+}
+
+fn document() -> Document {
+    Document {
+        id: 7,
+        kind: Kind::Code,
+        title: Some("notes".to_string()),
+        author: Some(Author {
+            name: "alice".to_string(),
+            birth: 1990,
+        }),
+        hash: [1, 2, 3, 4],
+        origin: Point(-1, 2),
+        tags: vec!["a".to_string(), "b".to_string()],
+        points: vec![Point(1, 2), Point(3, 4)],
+        authors: vec![Author {
+            name: "bob".to_string(),
+            birth: 0,
+        }],
+        ratio: 0.5,
+        initial: 'x',
+        draft: true,
+    }
+}
+
+#[test]
+fn serde_roundtrip() {
+    let buf = to_vec(&document()).unwrap();
+    assert_eq!(Ok(document()), from_slice(&buf));
+
+    let empty = Document {
+        title: None,
+        author: None,
+        tags: vec![],
+        points: vec![],
+        authors: vec![],
+        ..document()
+    };
+    let buf = to_vec(&empty).unwrap();
+    assert_eq!(Ok(empty), from_slice(&buf));
+}
+
+#[test]
+fn serde_layout() {
+    let mut author = TableBuilder::new();
+    author.add_string(0, "alice");
+    author.add_scalar(1, 1990u32, 0);
+
+    let mut hash = StructBuilder::new(4, 1);
+    for (offset, byte) in [1u8, 2, 3, 4].iter().enumerate() {
+        hash.set(offset, *byte);
+    }
+
+    let mut origin = StructBuilder::new(4, 2);
+    origin.set(0, -1i16);
+    origin.set(2, 2u8);
+    let points: Vec<_> = [(1i16, 2u8), (3, 4)]
+        .iter()
+        .map(|&(x, y)| {
+            let mut point = StructBuilder::new(4, 2);
+            point.set(0, x);
+            point.set(2, y);
+            point
+        })
+        .collect();
+
+    let mut bob = TableBuilder::new();
+    bob.add_string(0, "bob");
+
+    let mut builder = TableBuilder::new();
+    builder.add_scalar(0, 7u64, 0);
+    builder.add_scalar(1, 1u32, 0);
+    builder.add_string(2, "notes");
+    builder.add_table(3, author);
+    builder.add_struct(4, &hash);
+    builder.add_struct(5, &origin);
+    builder.add_string_vector(6, &["a", "b"]);
+    builder.add_struct_vector(7, 2, &points);
+    builder.add_table_vector(8, vec![bob]);
+    builder.add_scalar(9, 0.5f64, 0.0);
+    builder.add_scalar(10, u32::from('x'), 0);
+    builder.add_scalar(11, true, false);
+
+    assert_eq!(builder.finish(), to_vec(&document()).unwrap());
+}
+
+#[test]
+fn serde_borrowed() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        bytes: &'a [u8],
+    }
+
+    let mut builder = TableBuilder::new();
+    builder.add_string(0, "alice");
+    builder.add_vector(1, &[1u8, 2]);
+    let buf = builder.finish();
+
+    let borrowed: Borrowed = from_slice(&buf).unwrap();
+    assert_eq!("alice", borrowed.name);
+    assert_eq!(&[1u8, 2], borrowed.bytes);
+}
+
+#[test]
+fn serde_schema() {
+    let author = Author {
+        name: "alice".to_string(),
+        birth: 1990,
+    };
+    let buf = to_vec(&author).unwrap();
+    assert!(verify_canonical(&SCHEMA, AUTHOR, &buf).is_ok());
+    assert_eq!(Ok(author), from_slice_with_schema(&SCHEMA, AUTHOR, &buf));
+
+    assert_eq!(
+        Err(Error::Buffer(BufferError::OutOfBounds {
+            pos: 0,
+            need: 4,
+            buf_len: 2
+        })),
+        from_slice_with_schema::<Author>(&SCHEMA, AUTHOR, &buf[..2])
+    );
+}
+
+#[test]
+fn serde_defaults() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Optional {
+        count: Option<u32>,
+        name: Option<String>,
+    }
+
+    let zero = Optional {
+        count: Some(0),
+        name: Some(String::new()),
+    };
+    let buf = to_vec(&zero).unwrap();
+    assert_eq!(
+        Ok(Optional {
+            count: None,
+            name: Some(String::new()),
+        }),
+        from_slice(&buf)
+    );
+
+    let buf = TableBuilder::new().finish();
+    assert_eq!(
+        Err(Error::Message("missing field `name`".to_string())),
+        from_slice::<Author>(&buf)
+    );
+}
+
+#[test]
+fn serde_unsupported() {
+    #[derive(Serialize)]
+    struct Counts {
+        counts: HashMap<String, u32>,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Circle(u32),
+    }
+
+    #[derive(Serialize)]
+    struct Drawing {
+        shape: Shape,
+    }
+
+    #[derive(Serialize)]
+    struct Mixed {
+        nested: Vec<Vec<u8>>,
+    }
+
+    let counts = Counts {
+        counts: HashMap::new(),
+    };
+    assert_eq!(Err(Error::Unsupported("map")), to_vec(&counts));
+    assert_eq!(
+        Err(Error::Unsupported("enum variant with data")),
+        to_vec(&Drawing {
+            shape: Shape::Circle(1)
+        })
+    );
+    assert_eq!(
+        Err(Error::Unsupported("vector of vectors or absent values")),
+        to_vec(&Mixed {
+            nested: vec![vec![1]]
+        })
+    );
+    assert_eq!(
+        Err(Error::Unsupported("root which is not a struct")),
+        to_vec(&1u32)
+    );
+}
@@ -0,0 +1,111 @@
+#[macro_use]
+extern crate blockbuffers;
+
+pub mod common;
+
+use blockbuffers::builder::TableBuilder;
+use blockbuffers::position::TablePosition;
+use blockbuffers::reflection;
+use blockbuffers::seek::seek_uoffset;
+use blockbuffers::verify::{verify_with_options, Options};
+use blockbuffers::version::VersionedTable;
+use blockbuffers::Error;
+use common::example_schema::{EXAMPLE, SCHEMA};
+
+// `info` is deprecated in version 2, and `lines` is added in version 2.
+const INFO: usize = 4;
+const LINES: usize = 7;
+
+fn example(info: bool, lines: bool) -> Vec<u8> {
+    let mut example = TableBuilder::new();
+    example.add_scalar(0, 1u32, 0);
+    if info {
+        example.add_string(4, "info");
+    }
+    if lines {
+        example.add_scalar(7, 10u32, 0);
+    }
+    example.finish()
+}
+
+fn verify_version(buf: &[u8], version: u32) -> Result<TablePosition, Error> {
+    let options = Options {
+        version: Some(version),
+        ..Options::default()
+    };
+    verify_with_options(&SCHEMA, EXAMPLE, buf, options)
+}
+
+#[test]
+fn test_versioned_field_position() {
+    let buf = example(true, true);
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    let fields = SCHEMA.tables[EXAMPLE].fields;
+
+    let v1 = VersionedTable::new(root, 1);
+    assert!(v1.field_position(&buf, &fields[INFO]).is_some());
+    assert_eq!(None, v1.field_position(&buf, &fields[LINES]));
+    assert_eq!(Ok(None), v1.try_field_position(&buf, &fields[LINES]));
+
+    let v2 = VersionedTable::new(root, 2);
+    assert_eq!(None, v2.field_position(&buf, &fields[INFO]));
+    assert!(v2.field_position(&buf, &fields[LINES]).is_some());
+    assert_eq!(
+        Err(Error::FieldNotInVersion {
+            pos: root.0,
+            id: 4,
+            version: 2
+        }),
+        v2.try_field_position(&buf, &fields[INFO])
+    );
+
+    let buf = example(false, true);
+    let root = TablePosition(seek_uoffset(&buf, 0));
+    assert_eq!(
+        Ok(None),
+        VersionedTable::new(root, 2).try_field_position(&buf, &fields[INFO])
+    );
+}
+
+#[test]
+fn test_verify_version() {
+    assert!(verify_version(&example(true, false), 1).is_ok());
+    assert!(verify_version(&example(false, true), 2).is_ok());
+    assert!(
+        verify_with_options(&SCHEMA, EXAMPLE, &example(true, true), Options::default()).is_ok()
+    );
+
+    let buf = example(true, true);
+    let root = seek_uoffset(&buf, 0);
+    assert_eq!(
+        Err(Error::FieldNotInVersion {
+            pos: root,
+            id: 7,
+            version: 1
+        }),
+        verify_version(&buf, 1)
+    );
+    assert_eq!(
+        Err(Error::FieldNotInVersion {
+            pos: root,
+            id: 4,
+            version: 2
+        }),
+        verify_version(&buf, 2)
+    );
+}
+
+#[test]
+fn test_versions_from_bfbs() {
+    let schema =
+        reflection::Schema::from_slice(&include_bytes!("common/example.bfbs")[..]).unwrap();
+    let example = schema.object("example.Example").unwrap();
+
+    let lines = example.field("lines").unwrap();
+    assert_eq!(2, lines.since_version());
+    assert_eq!(None, lines.deprecated_version());
+
+    let info = example.field("info").unwrap();
+    assert_eq!(0, info.since_version());
+    assert_eq!(Some(0), info.deprecated_version());
+}